@@ -2,7 +2,8 @@ use sp_core::{Pair, Public, sr25519, crypto::UncheckedInto};
 use subsocial_runtime::{
 	AccountId, AuraConfig, BalancesConfig,
 	GenesisConfig, GrandpaConfig, UtilsConfig,
-	SudoConfig, SpacesConfig, SystemConfig,
+	SudoConfig, SpacesConfig, SpaceFollowsConfig, SpaceHistoryConfig, PostsConfig, PostHistoryConfig, ProfilesConfig,
+	ProfileFollowsConfig, ReactionsConfig, SystemConfig,
 	WASM_BINARY, Signature, constants::currency::DOLLARS,
 };
 use subsocial_primitives::Block;
@@ -208,6 +209,13 @@ fn testnet_genesis(
 		spaces: SpacesConfig {
             endowed_account: root_key,
         },
+		space_follows: SpaceFollowsConfig {},
+		space_history: SpaceHistoryConfig {},
+		posts: PostsConfig {},
+		post_history: PostHistoryConfig {},
+		profiles: ProfilesConfig {},
+		profile_follows: ProfileFollowsConfig {},
+		reactions: ReactionsConfig {},
 	}
 }
 
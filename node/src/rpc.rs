@@ -33,12 +33,15 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc
         C: Send + Sync + 'static,
         C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
         C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+        C::Api: account_blocks_rpc::AccountBlocksRuntimeApi<Block, AccountId>,
+        C::Api: bookmarks_rpc::BookmarksRuntimeApi<Block, AccountId, BlockNumber>,
+        C::Api: notifications_rpc::NotificationsRuntimeApi<Block, AccountId, BlockNumber>,
         C::Api: posts_rpc::PostsRuntimeApi<Block, AccountId, BlockNumber>,
-        C::Api: profile_follows_rpc::ProfileFollowsRuntimeApi<Block, AccountId>,
+        C::Api: profile_follows_rpc::ProfileFollowsRuntimeApi<Block, AccountId, BlockNumber>,
         C::Api: profiles_rpc::ProfilesRuntimeApi<Block, AccountId, BlockNumber>,
         C::Api: reactions_rpc::ReactionsRuntimeApi<Block, AccountId, BlockNumber>,
         C::Api: roles_rpc::RolesRuntimeApi<Block, AccountId>,
-        C::Api: space_follows_rpc::SpaceFollowsRuntimeApi<Block, AccountId>,
+        C::Api: space_follows_rpc::SpaceFollowsRuntimeApi<Block, AccountId, BlockNumber>,
         C::Api: spaces_rpc::SpacesRuntimeApi<Block, AccountId, BlockNumber>,
         C::Api: BlockBuilder<Block>,
         P: TransactionPool + 'static,
@@ -46,6 +49,9 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
+    use account_blocks_rpc::{AccountBlocks, AccountBlocksApi};
+    use bookmarks_rpc::{Bookmarks, BookmarksApi};
+    use notifications_rpc::{Notifications, NotificationsApi};
     use posts_rpc::{Posts, PostsApi};
     use profile_follows_rpc::{ProfileFollows, ProfileFollowsApi};
     use profiles_rpc::{Profiles, ProfilesApi};
@@ -67,6 +73,12 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc
 
     io.extend_with(PostsApi::to_delegate(Posts::new(client.clone())));
 
+    io.extend_with(BookmarksApi::to_delegate(Bookmarks::new(client.clone())));
+
+    io.extend_with(NotificationsApi::to_delegate(Notifications::new(client.clone())));
+
+    io.extend_with(AccountBlocksApi::to_delegate(AccountBlocks::new(client.clone())));
+
     io.extend_with(ProfileFollowsApi::to_delegate(ProfileFollows::new(client.clone())));
 
     io.extend_with(ProfilesApi::to_delegate(Profiles::new(client.clone())));
@@ -0,0 +1,14 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait AccountBlocksApi<AccountId> where
+        AccountId: Codec
+    {
+        fn get_blocked_accounts(account: AccountId) -> Vec<AccountId>;
+
+        fn filter_blocked_accounts(account: AccountId, maybe_blocked: Vec<AccountId>) -> Vec<AccountId>;
+    }
+}
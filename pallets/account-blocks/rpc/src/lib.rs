@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+
+use pallet_utils::rpc::map_rpc_error;
+pub use account_blocks_runtime_api::AccountBlocksApi as AccountBlocksRuntimeApi;
+
+#[rpc]
+pub trait AccountBlocksApi<BlockHash, AccountId> {
+    #[rpc(name = "accountBlocks_getBlockedAccounts")]
+    fn get_blocked_accounts(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+    ) -> Result<Vec<AccountId>>;
+
+    #[rpc(name = "accountBlocks_filterBlockedAccounts")]
+    fn filter_blocked_accounts(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+        maybe_blocked: Vec<AccountId>,
+    ) -> Result<Vec<AccountId>>;
+}
+
+pub struct AccountBlocks<C, M> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> AccountBlocks<C, M> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId> AccountBlocksApi<<Block as BlockT>::Hash, AccountId>
+    for AccountBlocks<C, Block>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: AccountBlocksRuntimeApi<Block, AccountId>,
+{
+    fn get_blocked_accounts(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+    ) -> Result<Vec<AccountId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_blocked_accounts(&at, account);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn filter_blocked_accounts(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+        maybe_blocked: Vec<AccountId>,
+    ) -> Result<Vec<AccountId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.filter_blocked_accounts(&at, account, maybe_blocked);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+}
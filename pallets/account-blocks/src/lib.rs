@@ -0,0 +1,156 @@
+//! # Account Blocks Module
+//!
+//! The Account Blocks module maintains a personal block list for every account.
+//! Unlike the Moderation module, blocks are not scoped to a space: an account that is blocked
+//! by another account cannot follow it, reply to its posts or react to its content anywhere.
+//!
+//! Blocking an account that follows the blocker makes it unfollow the blocker.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    dispatch::DispatchResult,
+    traits::Get,
+    transactional,
+};
+use frame_system::{self as system, ensure_signed};
+use sp_std::prelude::*;
+
+use df_traits::moderation::IsAccountBlockedBy;
+use pallet_profile_follows::{BeforeAccountFollowed, Module as ProfileFollows};
+
+pub mod rpc;
+
+/// The pallet's configuration trait.
+pub trait Config: system::Config
+    + pallet_utils::Config
+    + pallet_profile_follows::Config
+{
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+
+    /// The max number of accounts a single account can block.
+    type MaxBlockedAccounts: Get<u32>;
+}
+
+// This pallet's storage items.
+decl_storage! {
+    trait Store for Module<T: Config> as AccountBlocksModule {
+
+        /// Whether the second account is blocked by the first one.
+        /// Blocked accounts are stored one per key, so that a block does not
+        /// read and write the whole block list of an account.
+        pub AccountBlockedByAccount get(fn account_blocked_by_account):
+            double_map hasher(blake2_128_concat) /* blocker */ T::AccountId,
+            hasher(blake2_128_concat) /* blocked */ T::AccountId => bool;
+
+        /// The number of accounts blocked by a given account.
+        pub BlockedAccountsCount get(fn blocked_accounts_count):
+            map hasher(blake2_128_concat) T::AccountId => u32;
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as system::Config>::AccountId,
+    {
+        AccountBlocked(/* blocker */ AccountId, /* blocked */ AccountId),
+        AccountUnblocked(/* blocker */ AccountId, /* unblocked */ AccountId),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// Account cannot block itself.
+        AccountCannotBlockItself,
+        /// Account cannot unblock itself.
+        AccountCannotUnblockItself,
+        /// Account is already blocked.
+        AccountAlreadyBlocked,
+        /// Account is not blocked.
+        AccountNotBlocked,
+        /// Account cannot block more than `MaxBlockedAccounts` accounts.
+        TooManyBlockedAccounts,
+        /// Account is blocked by the account it tries to follow.
+        BlockedByFollowedAccount,
+    }
+}
+
+decl_module! {
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    const MaxBlockedAccounts: u32 = T::MaxBlockedAccounts::get();
+
+    // Initializing errors
+    type Error = Error<T>;
+
+    // Initializing events
+    fn deposit_event() = default;
+
+    /// Add an account to the block list of the caller.
+    /// If the blocked account follows the caller, it is made to unfollow the caller.
+    // Transactional, because the blocked account unfollows the caller before the block is stored,
+    // and the unfollow handlers may fail after the social accounts are changed.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(6, 7)]
+    #[transactional]
+    pub fn block_account(origin, account: T::AccountId) -> DispatchResult {
+      let blocker = ensure_signed(origin)?;
+
+      ensure!(blocker != account, Error::<T>::AccountCannotBlockItself);
+      ensure!(
+        !Self::account_blocked_by_account(&blocker, &account),
+        Error::<T>::AccountAlreadyBlocked
+      );
+
+      let blocked_accounts_count = Self::blocked_accounts_count(&blocker);
+      ensure!(
+        blocked_accounts_count < T::MaxBlockedAccounts::get(),
+        Error::<T>::TooManyBlockedAccounts
+      );
+
+      if ProfileFollows::<T>::account_followed_by_account((account.clone(), blocker.clone())) {
+        ProfileFollows::<T>::unfollow_account_by_account(account.clone(), blocker.clone())?;
+      }
+
+      <BlockedAccountsCount<T>>::insert(&blocker, blocked_accounts_count.saturating_add(1));
+      <AccountBlockedByAccount<T>>::insert(&blocker, &account, true);
+
+      Self::deposit_event(RawEvent::AccountBlocked(blocker, account));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+    pub fn unblock_account(origin, account: T::AccountId) -> DispatchResult {
+      let blocker = ensure_signed(origin)?;
+
+      ensure!(blocker != account, Error::<T>::AccountCannotUnblockItself);
+      ensure!(
+        Self::account_blocked_by_account(&blocker, &account),
+        Error::<T>::AccountNotBlocked
+      );
+
+      <BlockedAccountsCount<T>>::mutate(&blocker, |count| *count = count.saturating_sub(1));
+      <AccountBlockedByAccount<T>>::remove(&blocker, &account);
+
+      Self::deposit_event(RawEvent::AccountUnblocked(blocker, account));
+      Ok(())
+    }
+  }
+}
+
+impl<T: Config> IsAccountBlockedBy<T::AccountId> for Module<T> {
+    fn is_account_blocked_by(account: T::AccountId, blocker: T::AccountId) -> bool {
+        Self::account_blocked_by_account(blocker, account)
+    }
+}
+
+impl<T: Config> BeforeAccountFollowed<T> for Module<T> {
+    fn before_account_followed(follower: T::AccountId, _follower_reputation: u32, following: T::AccountId) -> DispatchResult {
+        ensure!(
+            !Self::account_blocked_by_account(following, follower),
+            Error::<T>::BlockedByFollowedAccount
+        );
+        Ok(())
+    }
+}
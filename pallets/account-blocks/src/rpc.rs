@@ -0,0 +1,19 @@
+use frame_support::storage::IterableStorageDoubleMap;
+use sp_std::prelude::*;
+
+use crate::{Module, Config, AccountBlockedByAccount};
+
+impl<T: Config> Module<T> {
+    /// Get the accounts blocked by a given account.
+    /// Blocked accounts are not sorted by the time they were blocked.
+    pub fn get_blocked_accounts(account: T::AccountId) -> Vec<T::AccountId> {
+        AccountBlockedByAccount::<T>::iter_key_prefix(account).collect()
+    }
+
+    /// Filter the accounts that are blocked by `account`, e.g. to hide their posts and replies.
+    pub fn filter_blocked_accounts(account: T::AccountId, maybe_blocked: Vec<T::AccountId>) -> Vec<T::AccountId> {
+        maybe_blocked.into_iter()
+            .filter(|maybe_blocked| Self::account_blocked_by_account(&account, maybe_blocked))
+            .collect()
+    }
+}
@@ -0,0 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use pallet_bookmarks::{BookmarkListId, rpc::FlatBookmarkList};
+use pallet_posts::rpc::FlatPost;
+
+sp_api::decl_runtime_apis! {
+    pub trait BookmarksApi<AccountId, BlockNumber> where
+        AccountId: Codec,
+        BlockNumber: Codec
+    {
+        fn get_bookmark_lists_by_account(
+            account: AccountId,
+            only_public: bool,
+        ) -> Vec<FlatBookmarkList<AccountId, BlockNumber>>;
+
+        fn get_bookmarked_posts(
+            list_id: BookmarkListId,
+            offset: u64,
+            limit: u16,
+        ) -> Vec<FlatPost<AccountId, BlockNumber>>;
+    }
+}
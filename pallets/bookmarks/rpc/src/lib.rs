@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+
+use pallet_bookmarks::{BookmarkListId, rpc::FlatBookmarkList};
+use pallet_posts::rpc::FlatPost;
+use pallet_utils::rpc::map_rpc_error;
+pub use bookmarks_runtime_api::BookmarksApi as BookmarksRuntimeApi;
+
+#[rpc]
+pub trait BookmarksApi<BlockHash, AccountId, BlockNumber> {
+    #[rpc(name = "bookmarks_getBookmarkListsByAccount")]
+    fn get_bookmark_lists_by_account(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+        only_public: bool,
+    ) -> Result<Vec<FlatBookmarkList<AccountId, BlockNumber>>>;
+
+    #[rpc(name = "bookmarks_getBookmarkedPosts")]
+    fn get_bookmarked_posts(
+        &self,
+        at: Option<BlockHash>,
+        list_id: BookmarkListId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>>;
+}
+
+pub struct Bookmarks<C, M> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> Bookmarks<C, M> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId, BlockNumber> BookmarksApi<<Block as BlockT>::Hash, AccountId, BlockNumber>
+    for Bookmarks<C, Block>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    BlockNumber: Codec,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: BookmarksRuntimeApi<Block, AccountId, BlockNumber>,
+{
+    fn get_bookmark_lists_by_account(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+        only_public: bool,
+    ) -> Result<Vec<FlatBookmarkList<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_bookmark_lists_by_account(&at, account, only_public);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_bookmarked_posts(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        list_id: BookmarkListId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_bookmarked_posts(&at, list_id, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+}
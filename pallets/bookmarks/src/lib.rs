@@ -0,0 +1,319 @@
+//! # Bookmarks Module
+//!
+//! The Bookmarks module allows accounts to save posts for later to named bookmark lists.
+//! An account can have several lists, add posts to them, remove and reorder saved posts.
+//! A list can be marked as public, so that other accounts can browse it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    dispatch::DispatchResult,
+    traits::Get
+};
+use frame_system::{self as system, ensure_signed};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{RuntimeDebug, DispatchError};
+use sp_std::prelude::*;
+
+use pallet_posts::Module as Posts;
+use pallet_utils::{remove_from_vec, WhoAndWhen, PostId};
+
+pub mod rpc;
+
+pub type BookmarkListId = u64;
+
+pub const FIRST_BOOKMARK_LIST_ID: u64 = 1;
+
+/// A named list of posts saved by an account.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct BookmarkList<T: Config> {
+
+    /// Unique sequential identifier of a bookmark list. Examples of list ids: `1`, `2`, `3`,
+    /// and so on.
+    pub id: BookmarkListId,
+
+    pub created: WhoAndWhen<T>,
+    pub updated: Option<WhoAndWhen<T>>,
+
+    /// The current owner of a given list.
+    pub owner: T::AccountId,
+
+    /// The name of a list, e.g. `Read later`.
+    pub name: Vec<u8>,
+
+    /// Public lists can be browsed by other accounts via RPC.
+    pub is_public: bool,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BookmarkListUpdate {
+    pub name: Option<Vec<u8>>,
+    pub is_public: Option<bool>,
+}
+
+/// The pallet's configuration trait.
+pub trait Config: system::Config
+    + pallet_utils::Config
+    + pallet_posts::Config
+{
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+
+    /// The max length of a bookmark list name in bytes.
+    type MaxBookmarkListNameLen: Get<u32>;
+
+    /// The max number of bookmark lists an account can have.
+    type MaxBookmarkListsPerAccount: Get<u32>;
+
+    /// The max number of posts that can be saved to a single bookmark list.
+    type MaxPostsPerBookmarkList: Get<u32>;
+}
+
+// This pallet's storage items.
+decl_storage! {
+    trait Store for Module<T: Config> as BookmarksModule {
+
+        /// The next bookmark list id.
+        pub NextBookmarkListId get(fn next_bookmark_list_id): BookmarkListId = FIRST_BOOKMARK_LIST_ID;
+
+        pub BookmarkListById get(fn bookmark_list_by_id):
+            map hasher(twox_64_concat) BookmarkListId => Option<BookmarkList<T>>;
+
+        /// Ids of bookmark lists owned by a given account.
+        pub BookmarkListIdsByAccount get(fn bookmark_list_ids_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Vec<BookmarkListId>;
+
+        /// Ids of posts saved to a given bookmark list in the order set by the list owner.
+        pub PostIdsByBookmarkListId get(fn post_ids_by_bookmark_list_id):
+            map hasher(twox_64_concat) BookmarkListId => Vec<PostId>;
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as system::Config>::AccountId,
+    {
+        BookmarkListCreated(AccountId, BookmarkListId),
+        BookmarkListUpdated(AccountId, BookmarkListId),
+        BookmarkListDeleted(AccountId, BookmarkListId),
+        PostBookmarked(AccountId, BookmarkListId, PostId),
+        PostUnbookmarked(AccountId, BookmarkListId, PostId),
+        BookmarkListReordered(AccountId, BookmarkListId),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// Bookmark list was not found by id.
+        BookmarkListNotFound,
+        /// Only a list owner can manage their bookmark list.
+        NotBookmarkListOwner,
+        /// Nothing to update in a bookmark list.
+        NoUpdatesForBookmarkList,
+
+        /// Bookmark list name is empty.
+        BookmarkListNameIsEmpty,
+        /// Bookmark list name is longer than `MaxBookmarkListNameLen`.
+        BookmarkListNameIsTooLong,
+        /// Account cannot have more than `MaxBookmarkListsPerAccount` bookmark lists.
+        TooManyBookmarkLists,
+        /// Bookmark list cannot have more than `MaxPostsPerBookmarkList` posts.
+        TooManyPostsInBookmarkList,
+
+        /// Post is already saved to this bookmark list.
+        PostAlreadyBookmarked,
+        /// Post is not saved to this bookmark list.
+        PostNotBookmarked,
+        /// New order of posts should contain exactly the same posts as the bookmark list.
+        InvalidPostsOrder,
+    }
+}
+
+decl_module! {
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    const MaxBookmarkListNameLen: u32 = T::MaxBookmarkListNameLen::get();
+
+    const MaxBookmarkListsPerAccount: u32 = T::MaxBookmarkListsPerAccount::get();
+
+    const MaxPostsPerBookmarkList: u32 = T::MaxPostsPerBookmarkList::get();
+
+    // Initializing errors
+    type Error = Error<T>;
+
+    // Initializing events
+    fn deposit_event() = default;
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 3)]
+    pub fn create_bookmark_list(origin, name: Vec<u8>, is_public: bool) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::ensure_valid_name(&name)?;
+
+      let mut list_ids = Self::bookmark_list_ids_by_account(&owner);
+      ensure!(
+        list_ids.len() < T::MaxBookmarkListsPerAccount::get() as usize,
+        Error::<T>::TooManyBookmarkLists
+      );
+
+      let list_id = Self::next_bookmark_list_id();
+      let new_list = BookmarkList {
+        id: list_id,
+        created: WhoAndWhen::<T>::new(owner.clone()),
+        updated: None,
+        owner: owner.clone(),
+        name,
+        is_public,
+      };
+
+      list_ids.push(list_id);
+
+      <BookmarkListById<T>>::insert(list_id, new_list);
+      <BookmarkListIdsByAccount<T>>::insert(&owner, list_ids);
+      NextBookmarkListId::mutate(|n| { *n += 1; });
+
+      Self::deposit_event(RawEvent::BookmarkListCreated(owner, list_id));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+    pub fn update_bookmark_list(origin, list_id: BookmarkListId, update: BookmarkListUpdate) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      let has_updates =
+        update.name.is_some() ||
+        update.is_public.is_some();
+
+      ensure!(has_updates, Error::<T>::NoUpdatesForBookmarkList);
+
+      let mut list = Self::require_bookmark_list_owned_by(list_id, &owner)?;
+      let mut is_update_applied = false;
+
+      if let Some(name) = update.name {
+        if name != list.name {
+          Self::ensure_valid_name(&name)?;
+          list.name = name;
+          is_update_applied = true;
+        }
+      }
+
+      if let Some(is_public) = update.is_public {
+        if is_public != list.is_public {
+          list.is_public = is_public;
+          is_update_applied = true;
+        }
+      }
+
+      if is_update_applied {
+        list.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+        <BookmarkListById<T>>::insert(list_id, list);
+        Self::deposit_event(RawEvent::BookmarkListUpdated(owner, list_id));
+      }
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 3)]
+    pub fn delete_bookmark_list(origin, list_id: BookmarkListId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::require_bookmark_list_owned_by(list_id, &owner)?;
+
+      <BookmarkListById<T>>::remove(list_id);
+      PostIdsByBookmarkListId::remove(list_id);
+      <BookmarkListIdsByAccount<T>>::mutate(&owner, |ids| remove_from_vec(ids, list_id));
+
+      Self::deposit_event(RawEvent::BookmarkListDeleted(owner, list_id));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn add_post_to_bookmark_list(origin, list_id: BookmarkListId, post_id: PostId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::require_bookmark_list_owned_by(list_id, &owner)?;
+      Posts::<T>::ensure_post_exists(post_id)?;
+
+      let mut post_ids = Self::post_ids_by_bookmark_list_id(list_id);
+      ensure!(!post_ids.contains(&post_id), Error::<T>::PostAlreadyBookmarked);
+      ensure!(
+        post_ids.len() < T::MaxPostsPerBookmarkList::get() as usize,
+        Error::<T>::TooManyPostsInBookmarkList
+      );
+
+      post_ids.push(post_id);
+      PostIdsByBookmarkListId::insert(list_id, post_ids);
+
+      Self::deposit_event(RawEvent::PostBookmarked(owner, list_id, post_id));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
+    pub fn remove_post_from_bookmark_list(origin, list_id: BookmarkListId, post_id: PostId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::require_bookmark_list_owned_by(list_id, &owner)?;
+
+      let mut post_ids = Self::post_ids_by_bookmark_list_id(list_id);
+      ensure!(post_ids.contains(&post_id), Error::<T>::PostNotBookmarked);
+
+      remove_from_vec(&mut post_ids, post_id);
+      PostIdsByBookmarkListId::insert(list_id, post_ids);
+
+      Self::deposit_event(RawEvent::PostUnbookmarked(owner, list_id, post_id));
+      Ok(())
+    }
+
+    /// Replace the order of posts in a bookmark list.
+    /// `post_ids` should contain exactly the same posts that are already saved to this list.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
+    pub fn reorder_bookmark_list(origin, list_id: BookmarkListId, post_ids: Vec<PostId>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::require_bookmark_list_owned_by(list_id, &owner)?;
+
+      let mut current_post_ids = Self::post_ids_by_bookmark_list_id(list_id);
+      let mut new_post_ids = post_ids.clone();
+
+      current_post_ids.sort_unstable();
+      new_post_ids.sort_unstable();
+      ensure!(current_post_ids == new_post_ids, Error::<T>::InvalidPostsOrder);
+
+      PostIdsByBookmarkListId::insert(list_id, post_ids);
+
+      Self::deposit_event(RawEvent::BookmarkListReordered(owner, list_id));
+      Ok(())
+    }
+  }
+}
+
+impl<T: Config> Module<T> {
+
+    /// Get `BookmarkList` by id from the storage or return `BookmarkListNotFound` error.
+    pub fn require_bookmark_list(list_id: BookmarkListId) -> Result<BookmarkList<T>, DispatchError> {
+        Ok(Self::bookmark_list_by_id(list_id).ok_or(Error::<T>::BookmarkListNotFound)?)
+    }
+
+    fn require_bookmark_list_owned_by(
+        list_id: BookmarkListId,
+        account: &T::AccountId,
+    ) -> Result<BookmarkList<T>, DispatchError> {
+        let list = Self::require_bookmark_list(list_id)?;
+        ensure!(list.owner == *account, Error::<T>::NotBookmarkListOwner);
+        Ok(list)
+    }
+
+    fn ensure_valid_name(name: &[u8]) -> DispatchResult {
+        ensure!(!name.is_empty(), Error::<T>::BookmarkListNameIsEmpty);
+        ensure!(
+            name.len() <= T::MaxBookmarkListNameLen::get() as usize,
+            Error::<T>::BookmarkListNameIsTooLong
+        );
+        Ok(())
+    }
+}
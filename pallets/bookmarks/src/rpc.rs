@@ -0,0 +1,79 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::*;
+
+use pallet_posts::{Module as Posts, rpc::FlatPost};
+use pallet_utils::rpc::FlatWhoAndWhen;
+
+use crate::{BookmarkList, BookmarkListId, Config, Module};
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FlatBookmarkList<AccountId, BlockNumber> {
+    pub id: BookmarkListId,
+
+    #[cfg_attr(feature = "std", serde(flatten))]
+    pub who_and_when: FlatWhoAndWhen<AccountId, BlockNumber>,
+
+    pub owner_id: AccountId,
+
+    #[cfg_attr(feature = "std", serde(serialize_with = "name_to_string"))]
+    pub name: Vec<u8>,
+
+    pub is_public: bool,
+
+    pub posts_count: u32,
+}
+
+#[cfg(feature = "std")]
+fn name_to_string<S>(name: &[u8], serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+    // If a name is not a valid UTF-8 string, then an empty string will be returned
+    serializer.serialize_str(std::str::from_utf8(name).unwrap_or_default())
+}
+
+impl<T: Config> From<BookmarkList<T>> for FlatBookmarkList<T::AccountId, T::BlockNumber> {
+    fn from(from: BookmarkList<T>) -> Self {
+        let BookmarkList { id, created, updated, owner, name, is_public } = from;
+
+        Self {
+            id,
+            who_and_when: (created, updated).into(),
+            owner_id: owner,
+            name,
+            is_public,
+            // Deleted posts stay in a list until its owner removes them, but they are not counted.
+            posts_count: Module::<T>::post_ids_by_bookmark_list_id(id)
+                .into_iter()
+                .filter(|post_id| Posts::<T>::ensure_post_exists(*post_id).is_ok())
+                .count() as u32,
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Get the bookmark lists of a given account. Private lists are skipped if `only_public` is `true`.
+    pub fn get_bookmark_lists_by_account(
+        account: T::AccountId,
+        only_public: bool,
+    ) -> Vec<FlatBookmarkList<T::AccountId, T::BlockNumber>> {
+        Self::bookmark_list_ids_by_account(account)
+            .into_iter()
+            .filter_map(Self::bookmark_list_by_id)
+            .filter(|list| list.is_public || !only_public)
+            .map(|list| list.into())
+            .collect()
+    }
+
+    /// Get the public posts saved to a given bookmark list in the order set by the list owner.
+    /// Deleted posts are skipped.
+    pub fn get_bookmarked_posts(
+        list_id: BookmarkListId,
+        offset: u64,
+        limit: u16,
+    ) -> Vec<FlatPost<T::AccountId, T::BlockNumber>> {
+        let post_ids = Self::post_ids_by_bookmark_list_id(list_id);
+        Posts::<T>::get_public_posts_by_ids(post_ids, offset, limit)
+    }
+}
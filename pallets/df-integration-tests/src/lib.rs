@@ -13,24 +13,28 @@ mod tests {
         assert_ok, assert_noop,
         parameter_types,
         dispatch::{DispatchResult, DispatchError},
-        storage::StorageMap,
-        traits::Everything,
+        storage::{IterableStorageDoubleMap, StorageMap},
+        traits::{Everything, OnInitialize},
     };
     use frame_system as system;
 
+    use pallet_account_blocks::Error as AccountBlocksError;
+    use pallet_bookmarks::{BookmarkListId, BookmarkListUpdate, Error as BookmarksError};
     use pallet_permissions::{
         SpacePermission,
         SpacePermission as SP,
         SpacePermissions,
     };
-    use pallet_posts::{Post, PostUpdate, PostExtension, Comment, Error as PostsError};
-    use pallet_profiles::{ProfileUpdate, Error as ProfilesError};
-    use pallet_profile_follows::Error as ProfileFollowsError;
-    use pallet_reactions::{ReactionId, ReactionKind, Error as ReactionsError};
+    use pallet_posts::{Post, PostUpdate, PostExtension, PostExpiryAction, PostsExpiringAt, Comment, Poll, PollKind, PollOptionIndex, PollResults, Tag, ScheduledPostId, TimelineSource, PostById, Error as PostsError, rpc::{FlatPostKind, TimelineCursor, MAX_TIMELINE_SCAN_DEPTH}};
+    use pallet_profiles::{ProfileUpdate, SocialAccountById, Error as ProfilesError};
+    use pallet_profile_follows::{AccountFollowers, Error as ProfileFollowsError};
+    use pallet_reactions::{ReactionId, ReactionKind, ReactionTarget, Error as ReactionsError};
+    use pallet_scores::ScoringAction;
     use pallet_spaces::{SpaceById, SpaceUpdate, Error as SpacesError, SpacesSettings};
-    use pallet_space_follows::Error as SpaceFollowsError;
+    use pallet_space_follows::{SpaceFollowers, Error as SpaceFollowsError};
     use pallet_space_ownership::Error as SpaceOwnershipError;
-    use pallet_moderation::{EntityId, EntityStatus, ReportId};
+    use pallet_moderation::{EntityId, EntityStatus, ReportId, StatusByEntityInSpace};
+    use pallet_notifications::{NotificationId, NotificationKind, Error as NotificationsError};
     use pallet_utils::{
         mock_functions::*,
         DEFAULT_MIN_HANDLE_LEN, DEFAULT_MAX_HANDLE_LEN,
@@ -50,7 +54,11 @@ mod tests {
             System: system::{Pallet, Call, Config, Storage, Event<T>},
             Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
             Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+            Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+            AccountBlocks: pallet_account_blocks::{Pallet, Call, Storage, Event<T>},
+            Bookmarks: pallet_bookmarks::{Pallet, Call, Storage, Event<T>},
             Moderation: pallet_moderation::{Pallet, Call, Storage, Event<T>},
+            Notifications: pallet_notifications::{Pallet, Call, Storage, Event<T>},
             Permissions: pallet_permissions::{Pallet, Call},
             Posts: pallet_posts::{Pallet, Call, Storage, Event<T>},
             PostHistory: pallet_post_history::{Pallet, Storage},
@@ -59,6 +67,7 @@ mod tests {
             ProfileHistory: pallet_profile_history::{Pallet, Storage},
             Reactions: pallet_reactions::{Pallet, Call, Storage, Event<T>},
             Roles: pallet_roles::{Pallet, Call, Storage, Event<T>},
+            Scores: pallet_scores::{Pallet, Storage, Event<T>},
             SpaceFollows: pallet_space_follows::{Pallet, Call, Storage, Event<T>},
             SpaceHistory: pallet_space_history::{Pallet, Storage},
             SpaceOwnership: pallet_space_ownership::{Pallet, Call, Storage, Event<T>},
@@ -108,6 +117,22 @@ mod tests {
         type WeightInfo = ();
     }
 
+    parameter_types! {
+        pub const MaximumSchedulerWeight: u64 = 1_000_000_000;
+        pub const MaxScheduledPerBlock: u32 = 10;
+    }
+
+    impl pallet_scheduler::Config for TestRuntime {
+        type Event = Event;
+        type Origin = Origin;
+        type PalletsOrigin = OriginCaller;
+        type Call = Call;
+        type MaximumWeight = MaximumSchedulerWeight;
+        type ScheduleOrigin = system::EnsureRoot<AccountId>;
+        type MaxScheduledPerBlock = MaxScheduledPerBlock;
+        type WeightInfo = ();
+    }
+
     parameter_types! {
         pub const ExistentialDeposit: u64 = 1;
     }
@@ -136,59 +161,184 @@ mod tests {
         type MaxHandleLen = MaxHandleLen;
     }
 
+    parameter_types! {
+        pub const MaxBlockedAccounts: u32 = 2;
+    }
+
+    impl pallet_account_blocks::Config for TestRuntime {
+        type Event = Event;
+        type MaxBlockedAccounts = MaxBlockedAccounts;
+    }
+
+    parameter_types! {
+        pub const MaxBookmarkListNameLen: u32 = 20;
+        pub const MaxBookmarkListsPerAccount: u32 = 2;
+        pub const MaxPostsPerBookmarkList: u32 = 2;
+    }
+
+    impl pallet_bookmarks::Config for TestRuntime {
+        type Event = Event;
+        type MaxBookmarkListNameLen = MaxBookmarkListNameLen;
+        type MaxBookmarkListsPerAccount = MaxBookmarkListsPerAccount;
+        type MaxPostsPerBookmarkList = MaxPostsPerBookmarkList;
+    }
+
     use pallet_permissions::default_permissions::DefaultSpacePermissions;
 
     impl pallet_permissions::Config for TestRuntime {
         type DefaultSpacePermissions = DefaultSpacePermissions;
     }
 
+    const SCHEDULED_POST_DEPOSIT: u64 = 10;
+
     parameter_types! {
         pub const MaxCommentDepth: u32 = 10;
+        pub const MaxRepliesToDelete: u32 = 5;
+        pub const MaxPinnedPostsPerSpace: u32 = 2;
+        pub const MaxTagsPerPost: u32 = 3;
+        pub const MaxTagLen: u32 = 10;
+        pub const MaxMentionsPerPost: u32 = 2;
+        pub const MaxExpiredPostsPerBlock: u32 = 3;
+        pub const MaxPollOptions: u8 = 4;
+        pub const MaxQuoteDepth: u32 = 2;
+        pub const ScheduledPostDeposit: u64 = SCHEDULED_POST_DEPOSIT;
+        pub const MaxScheduledPostsPerAccount: u32 = 2;
     }
 
     impl pallet_posts::Config for TestRuntime {
         type Event = Event;
         type MaxCommentDepth = MaxCommentDepth;
+        type MaxRepliesToDelete = MaxRepliesToDelete;
+        type MaxPinnedPostsPerSpace = MaxPinnedPostsPerSpace;
+        type MaxTagsPerPost = MaxTagsPerPost;
+        type MaxTagLen = MaxTagLen;
+        type MaxMentionsPerPost = MaxMentionsPerPost;
+        type MaxExpiredPostsPerBlock = MaxExpiredPostsPerBlock;
+        type MaxPollOptions = MaxPollOptions;
+        type MaxQuoteDepth = MaxQuoteDepth;
+        type ScheduledPostDeposit = ScheduledPostDeposit;
+        type MaxScheduledPostsPerAccount = MaxScheduledPostsPerAccount;
+        type AfterPostCreated = Notifications;
         type AfterPostUpdated = PostHistory;
         type IsPostBlocked = Moderation;
+        type IsAccountBlockedBy = AccountBlocks;
+        type PostScores = Scores;
+        type ScheduledCall = Call;
+        type Scheduler = Scheduler;
+        type PalletsOrigin = OriginCaller;
     }
 
     impl pallet_post_history::Config for TestRuntime {}
 
+    parameter_types! {
+        pub const MaxNotificationsPerAccount: u32 = 3;
+    }
+
+    impl pallet_notifications::Config for TestRuntime {
+        type Event = Event;
+        type MaxNotificationsPerAccount = MaxNotificationsPerAccount;
+    }
+
+    parameter_types! {
+        pub const MaxFollowedAccounts: u32 = 2;
+    }
+
     impl pallet_profile_follows::Config for TestRuntime {
         type Event = Event;
-        type BeforeAccountFollowed = ();
-        type BeforeAccountUnfollowed = ();
+        type BeforeAccountFollowed = (AccountBlocks, Scores);
+        type BeforeAccountUnfollowed = Scores;
+        type AfterAccountFollowed = Notifications;
+        type MaxFollowedAccounts = MaxFollowedAccounts;
     }
 
     impl pallet_profiles::Config for TestRuntime {
         type Event = Event;
+        type Currency = Balances;
+        type HandleDeposit = HandleDeposit;
+        type SpaceHandles = Spaces;
         type AfterProfileUpdated = ProfileHistory;
     }
 
     impl pallet_profile_history::Config for TestRuntime {}
 
+    parameter_types! {
+        pub const MaxEmojiLen: u32 = 16;
+        pub const MaxAllowedEmojisPerSpace: u32 = 3;
+    }
+
     impl pallet_reactions::Config for TestRuntime {
         type Event = Event;
+        type ReactionScores = Scores;
+        type AfterReactionCreated = Notifications;
+        type MaxEmojiLen = MaxEmojiLen;
+        type MaxAllowedEmojisPerSpace = MaxAllowedEmojisPerSpace;
     }
 
     parameter_types! {
         pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+        pub const MaxParentSpacesToCheck: u32 = 5;
     }
 
     impl pallet_roles::Config for TestRuntime {
         type Event = Event;
         type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+        type MaxParentSpacesToCheck = MaxParentSpacesToCheck;
         type Spaces = Spaces;
         type SpaceFollows = SpaceFollows;
+        type Reputation = Profiles;
         type IsAccountBlocked = Moderation;
         type IsContentBlocked = Moderation;
     }
 
+    parameter_types! {
+        pub const FollowSpaceActionWeight: i16 = 7;
+        pub const FollowAccountActionWeight: i16 = 3;
+
+        pub const SharePostActionWeight: i16 = 7;
+        pub const UpvotePostActionWeight: i16 = 5;
+        pub const DownvotePostActionWeight: i16 = -3;
+
+        pub const CreateCommentActionWeight: i16 = 5;
+        pub const ShareCommentActionWeight: i16 = 5;
+        pub const UpvoteCommentActionWeight: i16 = 4;
+        pub const DownvoteCommentActionWeight: i16 = -2;
+
+        pub const UpvoteSpaceActionWeight: i16 = 5;
+        pub const DownvoteSpaceActionWeight: i16 = -3;
+        pub const UpvoteAccountActionWeight: i16 = 3;
+        pub const DownvoteAccountActionWeight: i16 = -2;
+    }
+
+    impl pallet_scores::Config for TestRuntime {
+        type Event = Event;
+
+        type FollowSpaceActionWeight = FollowSpaceActionWeight;
+        type FollowAccountActionWeight = FollowAccountActionWeight;
+
+        type SharePostActionWeight = SharePostActionWeight;
+        type UpvotePostActionWeight = UpvotePostActionWeight;
+        type DownvotePostActionWeight = DownvotePostActionWeight;
+
+        type CreateCommentActionWeight = CreateCommentActionWeight;
+        type ShareCommentActionWeight = ShareCommentActionWeight;
+        type UpvoteCommentActionWeight = UpvoteCommentActionWeight;
+        type DownvoteCommentActionWeight = DownvoteCommentActionWeight;
+
+        type UpvoteSpaceActionWeight = UpvoteSpaceActionWeight;
+        type DownvoteSpaceActionWeight = DownvoteSpaceActionWeight;
+        type UpvoteAccountActionWeight = UpvoteAccountActionWeight;
+        type DownvoteAccountActionWeight = DownvoteAccountActionWeight;
+    }
+
+    parameter_types! {
+        pub const MaxFollowedSpaces: u32 = 3;
+    }
+
     impl pallet_space_follows::Config for TestRuntime {
         type Event = Event;
-        type BeforeSpaceFollowed = ();
-        type BeforeSpaceUnfollowed = ();
+        type BeforeSpaceFollowed = Scores;
+        type BeforeSpaceUnfollowed = Scores;
+        type MaxFollowedSpaces = MaxFollowedSpaces;
     }
 
     impl pallet_space_ownership::Config for TestRuntime {
@@ -199,6 +349,7 @@ mod tests {
 
     parameter_types! {
         pub const HandleDeposit: u64 = HANDLE_DEPOSIT;
+        pub const MaxSpaceCleanupItemsPerBlock: u32 = 3;
     }
 
     impl pallet_spaces::Config for TestRuntime {
@@ -211,6 +362,9 @@ mod tests {
         type IsAccountBlocked = Moderation;
         type IsContentBlocked = Moderation;
         type HandleDeposit = HandleDeposit;
+        type ProfileHandles = Profiles;
+        type SpaceCleanupHandler = (Posts, Roles, SpaceFollows, Reactions);
+        type MaxSpaceCleanupItemsPerBlock = MaxSpaceCleanupItemsPerBlock;
     }
 
     impl pallet_space_history::Config for TestRuntime {}
@@ -381,6 +535,7 @@ mod tests {
     const ACCOUNT1: AccountId = 1;
     const ACCOUNT2: AccountId = 2;
     const ACCOUNT3: AccountId = 3;
+    const ACCOUNT4: AccountId = 4;
 
     const SPACE1: SpaceId = 1001;
     const SPACE2: SpaceId = 1002;
@@ -388,6 +543,7 @@ mod tests {
     const POST1: PostId = 1;
     const POST2: PostId = 2;
     const POST3: PostId = 3;
+    const POST4: PostId = 4;
 
     const REACTION1: ReactionId = 1;
     const REACTION2: ReactionId = 2;
@@ -483,6 +639,14 @@ mod tests {
             space_id,
             content,
             hidden,
+            tags: None,
+        }
+    }
+
+    fn post_update_with_tags(tags: Vec<Tag>) -> PostUpdate {
+        PostUpdate {
+            tags: Some(tags),
+            ..post_update(None, None, None)
         }
     }
 
@@ -494,6 +658,10 @@ mod tests {
         Content::IPFS(b"QmYA2fn8cMbVWo4v95RwcwJVyQsNtnEwHerfWR8UNtEwoE".to_vec())
     }
 
+    fn profile_handle() -> Vec<u8> {
+        b"profile_handle".to_vec()
+    }
+
     fn profile_content_ipfs() -> Content {
         Content::IPFS(b"QmRAQB6YaCyidP37UdDnjFY5vQuiaRtqdyoW2CuDgwxkA5".to_vec())
     }
@@ -506,15 +674,31 @@ mod tests {
         ReactionKind::Downvote
     }
 
-    fn extension_regular_post() -> PostExtension {
+    fn reaction_emoji() -> ReactionKind {
+        ReactionKind::Emoji(tada_emoji())
+    }
+
+    fn tada_emoji() -> Vec<u8> {
+        ":tada:".as_bytes().to_vec()
+    }
+
+    fn rust_tag() -> Tag {
+        b"rust".to_vec()
+    }
+
+    fn news_tag() -> Tag {
+        b"news".to_vec()
+    }
+
+    fn extension_regular_post() -> PostExtension<BlockNumber> {
         PostExtension::RegularPost
     }
 
-    fn extension_comment(parent_id: Option<PostId>, root_post_id: PostId) -> PostExtension {
+    fn extension_comment(parent_id: Option<PostId>, root_post_id: PostId) -> PostExtension<BlockNumber> {
         PostExtension::Comment(Comment { parent_id, root_post_id })
     }
 
-    fn extension_shared_post(post_id: PostId) -> PostExtension {
+    fn extension_shared_post(post_id: PostId) -> PostExtension<BlockNumber> {
         PostExtension::SharedPost(post_id)
     }
 
@@ -581,6 +765,17 @@ mod tests {
         )
     }
 
+    fn _delete_default_space() -> DispatchResult {
+        _delete_space(None, None)
+    }
+
+    fn _delete_space(origin: Option<Origin>, space_id: Option<SpaceId>) -> DispatchResult {
+        Spaces::delete_space(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+        )
+    }
+
     fn _update_space_settings_with_handles_enabled() -> DispatchResult {
         _update_space_settings(None, Some(space_settings_with_handles_enabled()))
     }
@@ -609,6 +804,13 @@ mod tests {
         )
     }
 
+    /// Followers of a given space, sorted by account id.
+    fn space_follower_ids(space_id: SpaceId) -> Vec<AccountId> {
+        let mut followers: Vec<AccountId> = SpaceFollowers::<TestRuntime>::iter_key_prefix(space_id).collect();
+        followers.sort_unstable();
+        followers
+    }
+
     fn _default_unfollow_space() -> DispatchResult {
         _unfollow_space(None, None)
     }
@@ -627,7 +829,7 @@ mod tests {
     fn _create_post(
         origin: Option<Origin>,
         space_id_opt: Option<Option<SpaceId>>,
-        extension: Option<PostExtension>,
+        extension: Option<PostExtension<BlockNumber>>,
         content: Option<Content>,
     ) -> DispatchResult {
         Posts::create_post(
@@ -635,6 +837,34 @@ mod tests {
             space_id_opt.unwrap_or(Some(SPACE1)),
             extension.unwrap_or_else(extension_regular_post),
             content.unwrap_or_else(post_content_ipfs),
+            vec![],
+            vec![],
+        )
+    }
+
+    fn _create_post_with_tags(extension: Option<PostExtension<BlockNumber>>, tags: Vec<Tag>) -> DispatchResult {
+        Posts::create_post(
+            Origin::signed(ACCOUNT1),
+            Some(SPACE1),
+            extension.unwrap_or_else(extension_regular_post),
+            post_content_ipfs(),
+            tags,
+            vec![],
+        )
+    }
+
+    fn _create_post_with_mentions(
+        origin: Option<Origin>,
+        extension: Option<PostExtension<BlockNumber>>,
+        mentions: Vec<AccountId>,
+    ) -> DispatchResult {
+        Posts::create_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            Some(SPACE1),
+            extension.unwrap_or_else(extension_regular_post),
+            post_content_ipfs(),
+            vec![],
+            mentions,
         )
     }
 
@@ -671,6 +901,42 @@ mod tests {
         )
     }
 
+    fn _delete_default_post() -> DispatchResult {
+        _delete_post(None, None)
+    }
+
+    fn _delete_post(
+        origin: Option<Origin>,
+        post_id: Option<PostId>,
+    ) -> DispatchResult {
+        Posts::delete_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+        )
+    }
+
+    fn _update_post_permissions(
+        origin: Option<Origin>,
+        post_id: Option<PostId>,
+        permissions: Option<Option<SpacePermissions>>,
+    ) -> DispatchResult {
+        Posts::update_post_permissions(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+            permissions.unwrap_or_else(|| Some(permissions_where_post_disables(SP::CreateComments))),
+        )
+    }
+
+    fn permissions_where_post_disables(permission: SP) -> SpacePermissions {
+        SpacePermissions {
+            none: Some(vec![permission].into_iter().collect()),
+            everyone: None,
+            follower: None,
+            space_owner: None,
+            reputation_thresholds: None,
+        }
+    }
+
     fn _create_default_comment() -> DispatchResult {
         _create_comment(None, None, None, None)
     }
@@ -757,6 +1023,18 @@ mod tests {
         _update_post_reaction(origin, Some(post_id.unwrap_or(2)), reaction_id, kind)
     }
 
+    fn _update_allowed_emojis(
+        origin: Option<Origin>,
+        space_id: Option<SpaceId>,
+        emojis_opt: Option<Option<Vec<Vec<u8>>>>,
+    ) -> DispatchResult {
+        Reactions::update_allowed_emojis(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+            emojis_opt.unwrap_or_else(|| Some(vec![tada_emoji()])),
+        )
+    }
+
     fn _delete_post_reaction(
         origin: Option<Origin>,
         post_id: Option<PostId>,
@@ -769,6 +1047,44 @@ mod tests {
         )
     }
 
+    fn _create_reaction(
+        origin: Option<Origin>,
+        target: Option<ReactionTarget<AccountId>>,
+        kind: Option<ReactionKind>,
+    ) -> DispatchResult {
+        Reactions::create_reaction(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            target.unwrap_or(ReactionTarget::Space(SPACE1)),
+            kind.unwrap_or_else(reaction_upvote),
+        )
+    }
+
+    fn _update_reaction(
+        origin: Option<Origin>,
+        target: Option<ReactionTarget<AccountId>>,
+        reaction_id: ReactionId,
+        kind: Option<ReactionKind>,
+    ) -> DispatchResult {
+        Reactions::update_reaction(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            target.unwrap_or(ReactionTarget::Space(SPACE1)),
+            reaction_id,
+            kind.unwrap_or_else(reaction_upvote),
+        )
+    }
+
+    fn _delete_reaction(
+        origin: Option<Origin>,
+        target: Option<ReactionTarget<AccountId>>,
+        reaction_id: ReactionId,
+    ) -> DispatchResult {
+        Reactions::delete_reaction(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            target.unwrap_or(ReactionTarget::Space(SPACE1)),
+            reaction_id,
+        )
+    }
+
     fn _delete_comment_reaction(
         origin: Option<Origin>,
         post_id: Option<PostId>,
@@ -778,19 +1094,31 @@ mod tests {
     }
 
     fn _create_default_profile() -> DispatchResult {
-        _create_profile(None, None)
+        _create_profile(None, None, None)
     }
 
     fn _create_profile(
         origin: Option<Origin>,
+        handle: Option<Option<Vec<u8>>>,
         content: Option<Content>
     ) -> DispatchResult {
         Profiles::create_profile(
             origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            handle.unwrap_or(None),
             content.unwrap_or_else(profile_content_ipfs),
         )
     }
 
+    fn _update_profile_handle(
+        origin: Option<Origin>,
+        new_handle: Option<Option<Vec<u8>>>
+    ) -> DispatchResult {
+        Profiles::update_profile_handle(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            new_handle.unwrap_or_else(|| Some(profile_handle())),
+        )
+    }
+
     fn _update_profile(
         origin: Option<Origin>,
         content: Option<Content>
@@ -814,6 +1142,13 @@ mod tests {
         )
     }
 
+    /// Followers of a given account, sorted by account id.
+    fn account_follower_ids(account: AccountId) -> Vec<AccountId> {
+        let mut followers: Vec<AccountId> = AccountFollowers::<TestRuntime>::iter_key_prefix(account).collect();
+        followers.sort_unstable();
+        followers
+    }
+
     fn _default_unfollow_account() -> DispatchResult {
         _unfollow_account(None, None)
     }
@@ -825,6 +1160,28 @@ mod tests {
         )
     }
 
+    fn _default_block_account() -> DispatchResult {
+        _block_account(None, None)
+    }
+
+    fn _block_account(origin: Option<Origin>, account: Option<AccountId>) -> DispatchResult {
+        AccountBlocks::block_account(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            account.unwrap_or(ACCOUNT2),
+        )
+    }
+
+    fn _default_unblock_account() -> DispatchResult {
+        _unblock_account(None, None)
+    }
+
+    fn _unblock_account(origin: Option<Origin>, account: Option<AccountId>) -> DispatchResult {
+        AccountBlocks::unblock_account(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            account.unwrap_or(ACCOUNT2),
+        )
+    }
+
     fn _transfer_default_space_ownership() -> DispatchResult {
         _transfer_space_ownership(None, None, None)
     }
@@ -1028,6 +1385,12 @@ mod tests {
         );
     }
 
+    /// Blocking a post via `update_entity_status` also detaches it from the space,
+    /// so here we only set its status, to check that the post stays blocked in the space.
+    fn set_blocked_status_of_post_in_space(post_id: PostId, space_id: SpaceId) {
+        StatusByEntityInSpace::<TestRuntime>::insert(EntityId::Post(post_id), space_id, EntityStatus::Blocked);
+    }
+
     #[test]
     fn create_subspace_should_fail_when_content_is_blocked() {
         ExtBuilder::build_with_post().execute_with(|| {
@@ -1163,96 +1526,243 @@ mod tests {
         });
     }
 
-    // FIXME: uncomment when `update_post` will be able to move post from one space to another
-    /*
     #[test]
-    fn update_post_should_fail_when_post_is_blocked() {
+    fn delete_post_should_fail_when_account_is_blocked() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(
-                _update_entity_status(
-                    None,
-                    Some(EntityId::Post(POST1)),
-                    Some(SPACE1),
-                    Some(Some(EntityStatus::Blocked))
-                )
-            );
-            assert_noop!(
-                _update_post(
-                    None, // From ACCOUNT1 (has default permission to UpdateOwnPosts)
-                    Some(POST1),
-                    Some(
-                        post_update(
-                            Some(SPACE1),
-                            None,
-                            None
-                        )
-                    )
-                ), UtilsError::<TestRuntime>::PostIsBlocked
-            );
+            block_account_in_space_1();
+            assert_noop!(_delete_default_post(), UtilsError::<TestRuntime>::AccountIsBlocked);
         });
     }
-    */
-
-    /*---------------------------------------------------------------------------------------------------*/
-    // Spaces tests
 
     #[test]
-    fn create_space_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_space()); // SpaceId 1
-
-            // Check storages
-            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1), vec![SPACE1]);
-            assert_eq!(find_space_id_by_handle(space_handle()), Some(SPACE1));
-            assert_eq!(Spaces::next_space_id(), SPACE2);
-
-            // Check whether data stored correctly
-            let space = Spaces::space_by_id(SPACE1).unwrap();
-
-            assert_eq!(space.created.account, ACCOUNT1);
-            assert!(space.updated.is_none());
-            assert_eq!(space.hidden, false);
-
-            assert_eq!(space.owner, ACCOUNT1);
-            assert_eq!(space.handle, Some(space_handle()));
-            assert_eq!(space.content, space_content_ipfs());
-
-            assert_eq!(space.posts_count, 0);
-            assert_eq!(space.followers_count, 1);
-            assert!(SpaceHistory::edit_history(space.id).is_empty());
-
-            // Check that the handle deposit has been reserved:
-            let reserved_balance = Balances::reserved_balance(ACCOUNT1);
-            assert_eq!(reserved_balance, HANDLE_DEPOSIT);
+    fn create_comment_should_fail_when_content_is_blocked() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            block_content_in_space_1();
+            assert_noop!(
+                _create_comment(
+                    None,
+                    None,
+                    None,
+                    Some(valid_content_ipfs()),
+                ), UtilsError::<TestRuntime>::ContentIsBlocked
+            );
         });
     }
 
     #[test]
-    fn create_space_should_work_with_permissions_override() {
-        let perms = permissions_where_everyone_can_create_post();
-        ExtBuilder::build_with_space_and_custom_permissions(perms.clone()).execute_with(|| {
-            let space = Spaces::space_by_id(SPACE1).unwrap();
-            assert_eq!(space.permissions, Some(perms));
+    fn create_comment_should_fail_when_account_is_blocked() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            block_account_in_space_1();
+            assert_noop!(
+                _create_default_comment(),
+                UtilsError::<TestRuntime>::AccountIsBlocked
+            );
         });
     }
 
     #[test]
-    fn create_post_should_work_overridden_space_permission_for_everyone() {
-        ExtBuilder::build_with_space_and_custom_permissions(permissions_where_everyone_can_create_post()).execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            ));
+    fn create_post_reaction_should_fail_when_account_is_blocked() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            block_account_in_space_1();
+            assert_noop!(
+                _create_default_post_reaction(),
+                UtilsError::<TestRuntime>::AccountIsBlocked
+            );
         });
     }
 
     #[test]
-    fn create_post_should_work_overridden_space_permission_for_followers() {
-        ExtBuilder::build_with_space_and_custom_permissions(permissions_where_follower_can_create_post()).execute_with(|| {
+    fn follow_space_should_fail_when_account_is_blocked() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // ACCOUNT2 follows SpaceId 1
 
-            assert_ok!(_default_follow_space());
+            // Blocked account is unfollowed from the space
+            assert_ok!(
+                _update_entity_status(
+                    None,
+                    Some(EntityId::Account(ACCOUNT2)),
+                    Some(SPACE1),
+                    Some(Some(EntityStatus::Blocked))
+                )
+            );
+            assert!(!SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)));
+
+            assert_noop!(
+                _default_follow_space(),
+                UtilsError::<TestRuntime>::AccountIsBlocked
+            );
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_post_is_blocked() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            set_blocked_status_of_post_in_space(POST1, SPACE1);
+            assert_noop!(
+                _create_default_comment(),
+                UtilsError::<TestRuntime>::PostIsBlocked
+            );
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_parent_comment_is_blocked() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            set_blocked_status_of_post_in_space(POST2, SPACE1);
+            assert_noop!(
+                _create_comment(None, None, Some(Some(POST2)), None),
+                UtilsError::<TestRuntime>::PostIsBlocked
+            );
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_original_post_is_blocked() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            set_blocked_status_of_post_in_space(POST1, SPACE1);
+            assert_noop!(
+                _create_post(
+                    None,
+                    Some(Some(SPACE2)),
+                    Some(extension_shared_post(POST1)),
+                    None
+                ), UtilsError::<TestRuntime>::PostIsBlocked
+            );
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_original_post_is_blocked_in_target_space() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            set_blocked_status_of_post_in_space(POST1, SPACE2);
+            assert_noop!(
+                _create_post(
+                    None,
+                    Some(Some(SPACE2)),
+                    Some(extension_shared_post(POST1)),
+                    None
+                ), UtilsError::<TestRuntime>::PostIsBlocked
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_post_is_blocked() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            set_blocked_status_of_post_in_space(POST1, SPACE1);
+            assert_noop!(
+                _create_default_post_reaction(),
+                UtilsError::<TestRuntime>::PostIsBlocked
+            );
+        });
+    }
+
+    #[test]
+    fn create_role_should_fail_when_content_is_blocked() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            block_content_in_space_1();
+            assert_noop!(
+                _create_role(
+                    None,
+                    None,
+                    None,
+                    Some(valid_content_ipfs()),
+                    None
+                ), UtilsError::<TestRuntime>::ContentIsBlocked
+            );
+        });
+    }
+
+    // FIXME: uncomment when `update_post` will be able to move post from one space to another
+    /*
+    #[test]
+    fn update_post_should_fail_when_post_is_blocked() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(
+                _update_entity_status(
+                    None,
+                    Some(EntityId::Post(POST1)),
+                    Some(SPACE1),
+                    Some(Some(EntityStatus::Blocked))
+                )
+            );
+            assert_noop!(
+                _update_post(
+                    None, // From ACCOUNT1 (has default permission to UpdateOwnPosts)
+                    Some(POST1),
+                    Some(
+                        post_update(
+                            Some(SPACE1),
+                            None,
+                            None
+                        )
+                    )
+                ), UtilsError::<TestRuntime>::PostIsBlocked
+            );
+        });
+    }
+    */
+
+    /*---------------------------------------------------------------------------------------------------*/
+    // Spaces tests
+
+    #[test]
+    fn create_space_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_space()); // SpaceId 1
+
+            // Check storages
+            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1), vec![SPACE1]);
+            assert_eq!(find_space_id_by_handle(space_handle()), Some(SPACE1));
+            assert_eq!(Spaces::next_space_id(), SPACE2);
+
+            // Check whether data stored correctly
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+
+            assert_eq!(space.created.account, ACCOUNT1);
+            assert!(space.updated.is_none());
+            assert_eq!(space.hidden, false);
+
+            assert_eq!(space.owner, ACCOUNT1);
+            assert_eq!(space.handle, Some(space_handle()));
+            assert_eq!(space.content, space_content_ipfs());
+
+            assert_eq!(space.posts_count, 0);
+            assert_eq!(space.followers_count, 1);
+            assert!(SpaceHistory::edit_history(space.id).is_empty());
+
+            // Check that the handle deposit has been reserved:
+            let reserved_balance = Balances::reserved_balance(ACCOUNT1);
+            assert_eq!(reserved_balance, HANDLE_DEPOSIT);
+        });
+    }
+
+    #[test]
+    fn create_space_should_work_with_permissions_override() {
+        let perms = permissions_where_everyone_can_create_post();
+        ExtBuilder::build_with_space_and_custom_permissions(perms.clone()).execute_with(|| {
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.permissions, Some(perms));
+        });
+    }
+
+    #[test]
+    fn create_post_should_work_overridden_space_permission_for_everyone() {
+        ExtBuilder::build_with_space_and_custom_permissions(permissions_where_everyone_can_create_post()).execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            ));
+        });
+    }
+
+    #[test]
+    fn create_post_should_work_overridden_space_permission_for_followers() {
+        ExtBuilder::build_with_space_and_custom_permissions(permissions_where_follower_can_create_post()).execute_with(|| {
+
+            assert_ok!(_default_follow_space());
 
             assert_ok!(_create_post(
                 Some(Origin::signed(ACCOUNT2)),
@@ -1708,102 +2218,346 @@ mod tests {
     }
 
     #[test]
-    fn update_space_settings_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_update_space_settings_with_handles_disabled());
+    fn create_post_should_work_in_subspace_when_role_is_granted_in_parent_space() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
 
-            let spaces_settings = Spaces::settings();
-            // Ensure that `handles_enabled` field is false
-            assert!(!spaces_settings.handles_enabled);
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), Some(Some(SPACE2)), None, None));
+            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST1]);
         });
     }
 
     #[test]
-    fn update_space_settings_should_fail_when_account_is_not_root() {
-        ExtBuilder::build().execute_with(|| {
+    fn create_post_should_fail_in_subspace_when_subspace_overrides_permissions() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_subspace(
+                None,
+                Some(Some(SPACE1)),
+                Some(None),
+                None,
+                Some(Some(DefaultSpacePermissions::get()))
+            )); // SpaceId 2
+
             assert_noop!(
-                _update_space_settings(Some(Origin::signed(ACCOUNT1)), None),
-                DispatchError::BadOrigin
+                _create_post(Some(Origin::signed(ACCOUNT2)), Some(Some(SPACE2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreatePosts
             );
         });
     }
 
     #[test]
-    fn update_space_settings_should_fail_when_same_settings_provided() {
+    fn create_comment_should_fail_in_subspace_when_parent_space_disables_comments() {
         ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_space(
+                None,
+                None,
+                None,
+                Some(Some(permissions_where_post_disables(SP::CreateComments)))
+            )); // SpaceId 1
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None)); // PostId 1
+
+            // The subspace inherits the permissions of its parent space, not the default ones
             assert_noop!(
-                _update_space_settings_with_handles_enabled(),
-                SpacesError::<TestRuntime>::NoUpdatesForSpacesSettings
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreateComments
             );
         });
     }
 
-    // TODO: refactor or remove. Deprecated tests
-    // Find public space ids tests
-    // --------------------------------------------------------------------------------------------
-    /*#[test]
-    fn find_public_space_ids_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_space(None, None, Some(Some(space_handle1())), None, None));
+    #[test]
+    fn update_subspace_should_work_when_role_with_update_any_subspace_is_granted_in_parent_space() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnySubspace]).execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
 
-            let space_ids = Spaces::find_public_space_ids(0, 3);
-            assert_eq!(space_ids, vec![SPACE1, SPACE2]);
+            assert_ok!(_update_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE2),
+                Some(update_for_space_content(updated_space_content()))
+            ));
+            assert_eq!(Spaces::space_by_id(SPACE2).unwrap().content, updated_space_content());
         });
     }
 
     #[test]
-    fn find_public_space_ids_should_work_with_zero_offset() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            let space_ids = Spaces::find_public_space_ids(0, 1);
-            assert_eq!(space_ids, vec![SPACE1]);
+    fn hide_subspace_should_work_when_role_with_hide_any_subspace_is_granted_in_parent_space() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::HideAnySubspace]).execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+
+            assert_ok!(_update_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE2),
+                Some(space_update(None, None, Some(true)))
+            ));
+            assert!(Spaces::space_by_id(SPACE2).unwrap().hidden);
+
+            // HideAnySubspace doesn't allow other updates of a subspace
+            assert_noop!(_update_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE2),
+                Some(update_for_space_content(updated_space_content()))
+            ), SpacesError::<TestRuntime>::NoPermissionToUpdateSpace);
         });
     }
 
     #[test]
-    fn find_public_space_ids_should_work_with_zero_limit() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            let space_ids = Spaces::find_public_space_ids(1, 0);
-            assert_eq!(space_ids, vec![SPACE1]);
+    fn create_subspace_should_fail_when_no_permission_to_override_subspace_permissions() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreateSubspaces]).execute_with(|| {
+            assert_noop!(_create_subspace(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE1)),
+                Some(None),
+                None,
+                Some(Some(DefaultSpacePermissions::get()))
+            ), SpacesError::<TestRuntime>::NoPermissionToOverrideSubspacePermissions);
         });
     }
 
     #[test]
-    fn find_public_space_ids_should_work_with_zero_offset_and_zero_limit() {
+    fn update_space_should_fail_when_moving_space_into_its_subspace() {
         ExtBuilder::build_with_space().execute_with(|| {
-            let space_ids = Spaces::find_public_space_ids(0, 0);
-            assert_eq!(space_ids, vec![]);
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+
+            let move_to_subspace = SpaceUpdate {
+                parent_id: Some(Some(SPACE2)),
+                ..space_update(None, None, None)
+            };
+
+            assert_noop!(
+                _update_space(None, Some(SPACE1), Some(move_to_subspace)),
+                SpacesError::<TestRuntime>::CannotMoveSpaceIntoItsSubspace
+            );
         });
     }
 
-    // Find unlisted space ids tests
-    // --------------------------------------------------------------------------------------------
-
     #[test]
-    fn find_unlisted_space_ids_should_work() {
+    fn create_comment_should_fail_in_subspace_when_post_overrides_permissions_of_parent_space() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_space(None, None, Some(Some(space_handle1())), None, None));
-            assert_ok!(
-                _update_space(
-                    None,
-                    Some(SPACE1),
-                    Some(
-                        space_update(
-                            None,
-                            None,
-                            Some(Content::None),
-                            Some(true),
-                            None
-                        )
-                    )
-                )
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None)); // PostId 1
+
+            // Nobody but the roles granted in the space can comment on this post
+            let post_permissions = SpacePermissions {
+                none: None,
+                everyone: Some(Default::default()),
+                follower: None,
+                space_owner: None,
+                reputation_thresholds: None,
+            };
+            assert_ok!(_update_post_permissions(None, None, Some(Some(post_permissions))));
+
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreateComments
             );
+        });
+    }
 
-            assert_ok!(
-                _update_space(
-                    None,
-                    Some(SPACE2),
-                    Some(
-                        space_update(
+    #[test]
+    fn update_space_settings_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_update_space_settings_with_handles_disabled());
+
+            let spaces_settings = Spaces::settings();
+            // Ensure that `handles_enabled` field is false
+            assert!(!spaces_settings.handles_enabled);
+        });
+    }
+
+    #[test]
+    fn update_space_settings_should_fail_when_account_is_not_root() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _update_space_settings(Some(Origin::signed(ACCOUNT1)), None),
+                DispatchError::BadOrigin
+            );
+        });
+    }
+
+    #[test]
+    fn update_space_settings_should_fail_when_same_settings_provided() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _update_space_settings_with_handles_enabled(),
+                SpacesError::<TestRuntime>::NoUpdatesForSpacesSettings
+            );
+        });
+    }
+
+    #[test]
+    fn delete_space_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_role()); // RoleId 1
+            assert_ok!(_grant_role(None, None, Some(vec![User::Account(ACCOUNT2), User::Account(ACCOUNT3)])));
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT2)), None));
+
+            assert_ok!(_delete_default_space());
+
+            // Check that the space handle and handle deposit are released right away,
+            // while the space itself is only marked as deleted
+            assert!(Spaces::is_space_deleted(SPACE1));
+            assert!(Spaces::space_by_id(SPACE1).is_some());
+            assert!(Spaces::require_space(SPACE1).is_err());
+            assert!(find_space_id_by_handle(space_handle()).is_none());
+            assert!(Spaces::space_ids_by_owner(ACCOUNT1).is_empty());
+            assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
+
+            // There are 7 items to clean up (1 post, 1 role granted to 2 users, 2 followers and
+            // the space itself) while only 3 items are allowed to be processed per block.
+            Spaces::on_initialize(2);
+            assert!(Spaces::is_space_deleted(SPACE1));
+            Spaces::on_initialize(3);
+            Spaces::on_initialize(4);
+            assert!(!Spaces::is_space_deleted(SPACE1));
+            assert!(Spaces::space_by_id(SPACE1).is_none());
+
+            // Check that posts are detached from the deleted space
+            assert!(Posts::post_by_id(POST1).unwrap().space_id.is_none());
+            assert!(Posts::post_ids_by_space_id(SPACE1).is_empty());
+
+            // Check that roles are deleted
+            assert!(Roles::role_ids_by_space_id(SPACE1).is_empty());
+            assert!(Roles::role_by_id(ROLE1).is_none());
+            assert!(Roles::users_by_role_id(ROLE1).is_empty());
+            assert!(Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1).is_empty());
+
+            // Check that follows are removed
+            assert!(space_follower_ids(SPACE1).is_empty());
+            assert!(!SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)));
+            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT1).is_empty());
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().following_spaces_count, 0);
+        });
+    }
+
+    #[test]
+    fn delete_post_should_work_after_space_deleted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_delete_default_space());
+
+            // The post is not detached from the deleted space yet
+            assert_ok!(_delete_default_post());
+            assert!(Posts::post_by_id(POST1).is_none());
+            assert!(Posts::post_ids_by_space_id(SPACE1).is_empty());
+        });
+    }
+
+    #[test]
+    fn delete_own_subspace_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+
+            assert_eq!(Spaces::subspaces_count(SPACE1), 1);
+
+            assert_ok!(_delete_space(None, Some(SPACE2)));
+            assert!(Spaces::is_space_deleted(SPACE2));
+            assert_eq!(Spaces::subspaces_count(SPACE1), 0);
+
+            // The parent space can be deleted once it has no subspaces
+            assert_ok!(_delete_default_space());
+        });
+    }
+
+    #[test]
+    fn delete_space_should_fail_when_space_has_subspaces() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+
+            assert_noop!(_delete_default_space(), SpacesError::<TestRuntime>::SpaceHasSubspaces);
+        });
+    }
+
+    #[test]
+    fn delete_space_should_fail_when_space_is_already_deleted() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_delete_default_space());
+
+            assert_noop!(_delete_default_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
+
+    #[test]
+    fn delete_space_should_fail_when_space_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_delete_default_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
+
+    #[test]
+    fn delete_space_should_fail_when_account_is_not_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _delete_space(Some(Origin::signed(ACCOUNT2)), None),
+                SpacesError::<TestRuntime>::NotASpaceOwner
+            );
+        });
+    }
+
+    // TODO: refactor or remove. Deprecated tests
+    // Find public space ids tests
+    // --------------------------------------------------------------------------------------------
+    /*#[test]
+    fn find_public_space_ids_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, None, Some(Some(space_handle1())), None, None));
+
+            let space_ids = Spaces::find_public_space_ids(0, 3);
+            assert_eq!(space_ids, vec![SPACE1, SPACE2]);
+        });
+    }
+
+    #[test]
+    fn find_public_space_ids_should_work_with_zero_offset() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let space_ids = Spaces::find_public_space_ids(0, 1);
+            assert_eq!(space_ids, vec![SPACE1]);
+        });
+    }
+
+    #[test]
+    fn find_public_space_ids_should_work_with_zero_limit() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let space_ids = Spaces::find_public_space_ids(1, 0);
+            assert_eq!(space_ids, vec![SPACE1]);
+        });
+    }
+
+    #[test]
+    fn find_public_space_ids_should_work_with_zero_offset_and_zero_limit() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let space_ids = Spaces::find_public_space_ids(0, 0);
+            assert_eq!(space_ids, vec![]);
+        });
+    }
+
+    // Find unlisted space ids tests
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn find_unlisted_space_ids_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, None, Some(Some(space_handle1())), None, None));
+            assert_ok!(
+                _update_space(
+                    None,
+                    Some(SPACE1),
+                    Some(
+                        space_update(
+                            None,
+                            None,
+                            Some(Content::None),
+                            Some(true),
+                            None
+                        )
+                    )
+                )
+            );
+
+            assert_ok!(
+                _update_space(
+                    None,
+                    Some(SPACE2),
+                    Some(
+                        space_update(
                             None,
                             None,
                             Some(Content::None),
@@ -2337,128 +3091,332 @@ mod tests {
         });
     }
 
-    // TODO: refactor or remove. Deprecated tests
-    // Find public post ids tests
-    // --------------------------------------------------------------------------------------------
-    /*#[test]
-    fn find_public_post_ids_in_space_should_work() {
+    #[test]
+    fn delete_post_should_work() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post(None, Some(Some(SPACE1)), None, None));
+            // Hide the post to check that the hidden posts counter is also decreased
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
 
-            let post_ids = Posts::find_public_post_ids_in_space(SPACE1, 0, 3);
-            assert_eq!(post_ids, vec![POST1, POST2]);
+            assert_ok!(_delete_default_post());
+
+            assert!(Posts::post_by_id(POST1).is_none());
+            assert!(Posts::post_ids_by_space_id(SPACE1).is_empty());
+
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.posts_count, 0);
+            assert_eq!(space.hidden_posts_count, 0);
         });
     }
 
     #[test]
-    fn find_public_post_ids_in_space_should_work_with_zero_offset() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            let post_ids = Posts::find_public_post_ids_in_space(SPACE1, 0, 1);
-            assert_eq!(post_ids, vec![POST1]);
+    fn delete_post_should_work_when_post_has_comments() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_comment(None, None, Some(Some(POST2)), None)); // PostId 3
+
+            assert_ok!(_delete_default_post());
+
+            // The post should be removed along with all its replies
+            for post_id in POST1..=POST3 {
+                assert!(Posts::post_by_id(post_id).is_none());
+                assert!(Posts::reply_ids_by_post_id(post_id).is_empty());
+            }
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().posts_count, 0);
         });
     }
 
     #[test]
-    fn find_public_post_ids_in_space_should_work_with_zero_limit() {
+    fn delete_post_should_work_when_post_has_no_space() {
         ExtBuilder::build_with_post().execute_with(|| {
-            let post_ids = Posts::find_public_post_ids_in_space(SPACE1, 1, 0);
-            assert_eq!(post_ids, vec![POST1]);
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert_ok!(_delete_default_post());
+
+            assert!(Posts::post_by_id(POST1).is_none());
         });
     }
 
     #[test]
-    fn find_public_post_ids_in_space_should_work_with_zero_offset_and_zero_limit() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            let post_ids = Posts::find_public_post_ids_in_space(SPACE1, 0, 0);
-            assert_eq!(post_ids, vec![]);
+    fn delete_any_post_should_work_when_account_has_default_permission() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None, // SpaceId 1
+                None, // RegularPost extension
+                None // Default post content
+            )); // PostId 1
+
+            // From ACCOUNT1 (has default permission to DeleteAnyPost as SpaceOwner)
+            assert_ok!(_delete_default_post());
+            assert!(Posts::post_by_id(POST1).is_none());
         });
     }
 
-    // Find unlisted post ids tests
-    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn delete_post_should_fail_when_post_not_found() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_delete_default_post(), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
 
     #[test]
-    fn find_unlisted_post_ids_in_space_should_work() {
+    fn delete_post_should_fail_when_account_has_no_permission_to_delete_any_post() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post(None, Some(Some(SPACE1)), None, None));
-            assert_ok!(
-                _update_post(
-                    None,
-                    None,
-                    Some(
-                        post_update(
-                            None,
-                            Some(Content::None),
-                            Some(true))
-                    )
-                )
-            );
-            assert_ok!(
-                _update_post(
-                    None,
-                    Some(POST2),
-                    Some(
-                        post_update(
-                            None,
-                            Some(Content::None),
-                            Some(true))
-                    )
-                )
+            assert_noop!(
+                _delete_post(Some(Origin::signed(ACCOUNT2)), None),
+                PostsError::<TestRuntime>::NoPermissionToDeleteAnyPost
             );
-
-            let post_ids = Posts::find_unlisted_post_ids_in_space(SPACE1, 0, 3);
-            assert_eq!(post_ids, vec![POST1, POST2]);
         });
     }
 
     #[test]
-    fn find_unlisted_post_ids_in_space_should_work_with_zero_offset() {
+    fn delete_post_should_fail_when_space_none_and_account_is_not_post_owner() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(
-                _update_post(
-                    None,
-                    None,
-                    Some(
-                        post_update(
-                            None,
-                            Some(Content::None),
-                            Some(true))
-                    )
-                )
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert_noop!(
+                _delete_post(Some(Origin::signed(ACCOUNT2)), None),
+                PostsError::<TestRuntime>::NotAPostOwner
             );
-
-            let post_ids = Posts::find_unlisted_post_ids_in_space(SPACE1, 0, 1);
-            assert_eq!(post_ids, vec![POST1]);
         });
     }
 
     #[test]
-    fn find_unlisted_post_ids_in_space_should_work_with_zero_limit() {
+    fn delete_post_should_fail_when_post_has_too_many_replies() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(
-                _update_post(
-                    None,
-                    None,
-                    Some(
-                        post_update(
-                            None,
-                            Some(Content::None),
-                            Some(true))
-                    )
-                )
-            );
+            for _ in 0..=MaxRepliesToDelete::get() {
+                assert_ok!(_create_default_comment());
+            }
 
-            let post_ids = Posts::find_unlisted_post_ids_in_space(SPACE1, 1, 0);
-            assert_eq!(post_ids, vec![POST1]);
+            assert_noop!(_delete_default_post(), PostsError::<TestRuntime>::TooManyRepliesToDelete);
         });
     }
 
     #[test]
-    fn find_unlisted_post_ids_in_space_should_work_with_zero_offset_and_zero_limit() {
+    fn update_post_permissions_should_work() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(
-                _update_post(
-                    None,
+            assert_ok!(_update_post_permissions(None, None, None));
+            assert!(Posts::post_by_id(POST1).unwrap().permissions.is_some());
+
+            // Remove the overrides from the post
+            assert_ok!(_update_post_permissions(None, None, Some(None)));
+            assert!(Posts::post_by_id(POST1).unwrap().permissions.is_none());
+        });
+    }
+
+    #[test]
+    fn update_post_permissions_should_fail_when_no_updates_for_post_provided() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _update_post_permissions(None, None, Some(None)),
+                PostsError::<TestRuntime>::NoUpdatesForPost
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_permissions_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _update_post_permissions(Some(Origin::signed(ACCOUNT2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToOverridePostPermissions
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_permissions_should_fail_when_post_is_comment() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_noop!(
+                _update_post_permissions(None, Some(POST2), None),
+                PostsError::<TestRuntime>::CannotOverrideCommentPermissions
+            );
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_comments_disabled_on_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post_permissions(None, None, None));
+
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreateComments
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_downvotes_disabled_on_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post_permissions(
+                None,
+                None,
+                Some(Some(permissions_where_post_disables(SP::Downvote)))
+            ));
+
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(reaction_downvote())),
+                ReactionsError::<TestRuntime>::NoPermissionToDownvote
+            );
+            // Upvotes are still allowed by the space permissions
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+        });
+    }
+
+    #[test]
+    fn update_post_reaction_should_fail_when_switching_emoji_to_disabled_downvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_emoji())
+            )); // ReactionId 1
+            assert_ok!(_update_post_permissions(
+                None,
+                None,
+                Some(Some(permissions_where_post_disables(SP::Downvote)))
+            ));
+
+            assert_noop!(
+                _update_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1, Some(reaction_downvote())),
+                ReactionsError::<TestRuntime>::NoPermissionToDownvote
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_reaction_should_fail_when_post_is_hidden() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
+
+            assert_noop!(
+                _update_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1, Some(reaction_downvote())),
+                ReactionsError::<TestRuntime>::CannotReactWhenPostHidden
+            );
+        });
+    }
+
+    // TODO: refactor or remove. Deprecated tests
+    // Find public post ids tests
+    // --------------------------------------------------------------------------------------------
+    /*#[test]
+    fn find_public_post_ids_in_space_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(SPACE1)), None, None));
+
+            let post_ids = Posts::find_public_post_ids_in_space(SPACE1, 0, 3);
+            assert_eq!(post_ids, vec![POST1, POST2]);
+        });
+    }
+
+    #[test]
+    fn find_public_post_ids_in_space_should_work_with_zero_offset() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let post_ids = Posts::find_public_post_ids_in_space(SPACE1, 0, 1);
+            assert_eq!(post_ids, vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn find_public_post_ids_in_space_should_work_with_zero_limit() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let post_ids = Posts::find_public_post_ids_in_space(SPACE1, 1, 0);
+            assert_eq!(post_ids, vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn find_public_post_ids_in_space_should_work_with_zero_offset_and_zero_limit() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let post_ids = Posts::find_public_post_ids_in_space(SPACE1, 0, 0);
+            assert_eq!(post_ids, vec![]);
+        });
+    }
+
+    // Find unlisted post ids tests
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn find_unlisted_post_ids_in_space_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(SPACE1)), None, None));
+            assert_ok!(
+                _update_post(
+                    None,
+                    None,
+                    Some(
+                        post_update(
+                            None,
+                            Some(Content::None),
+                            Some(true))
+                    )
+                )
+            );
+            assert_ok!(
+                _update_post(
+                    None,
+                    Some(POST2),
+                    Some(
+                        post_update(
+                            None,
+                            Some(Content::None),
+                            Some(true))
+                    )
+                )
+            );
+
+            let post_ids = Posts::find_unlisted_post_ids_in_space(SPACE1, 0, 3);
+            assert_eq!(post_ids, vec![POST1, POST2]);
+        });
+    }
+
+    #[test]
+    fn find_unlisted_post_ids_in_space_should_work_with_zero_offset() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(
+                _update_post(
+                    None,
+                    None,
+                    Some(
+                        post_update(
+                            None,
+                            Some(Content::None),
+                            Some(true))
+                    )
+                )
+            );
+
+            let post_ids = Posts::find_unlisted_post_ids_in_space(SPACE1, 0, 1);
+            assert_eq!(post_ids, vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn find_unlisted_post_ids_in_space_should_work_with_zero_limit() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(
+                _update_post(
+                    None,
+                    None,
+                    Some(
+                        post_update(
+                            None,
+                            Some(Content::None),
+                            Some(true))
+                    )
+                )
+            );
+
+            let post_ids = Posts::find_unlisted_post_ids_in_space(SPACE1, 1, 0);
+            assert_eq!(post_ids, vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn find_unlisted_post_ids_in_space_should_work_with_zero_offset_and_zero_limit() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(
+                _update_post(
+                    None,
                     None,
                     Some(
                         post_update(
@@ -2521,7 +3479,7 @@ mod tests {
             for comment_id in first_comment_id..penultimate_comment_id as PostId {
                 let comment = Posts::post_by_id(comment_id).unwrap();
                 let replies_should_be = last_comment_id - comment_id;
-                assert_eq!(comment.replies_count, replies_should_be as u16);
+                assert_eq!(comment.replies_count, replies_should_be as u32);
                 assert_eq!(Posts::reply_ids_by_post_id(comment_id), vec![comment_id + 1]);
 
                 assert_eq!(comment.hidden_replies_count, 0);
@@ -2725,6 +3683,17 @@ mod tests {
         });
     }
 
+    #[test]
+    fn create_post_reaction_should_count_upvotes_beyond_u16_max() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(Posts::mutate_post_by_id(POST1, |post| post.upvotes_count = u16::MAX.into()));
+
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().upvotes_count, u16::MAX as u32 + 1);
+        });
+    }
+
     #[test]
     fn create_post_reaction_should_work_downvote() {
         ExtBuilder::build_with_post().execute_with(|| {
@@ -2796,73 +3765,330 @@ mod tests {
         });
     }
 
-// Shares tests
-
     #[test]
-    fn share_post_should_work() {
+    fn create_post_reaction_should_work_with_emoji() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
+            assert_ok!(_create_post_reaction(
                 Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
                 None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
-
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2 which is permitted by default in both spaces
-
-            // Check storages
-            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
-            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST2]);
-            assert_eq!(Posts::next_post_id(), POST3);
-
-            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
-
-            // Check whether data stored correctly
-            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
-
-            let shared_post = Posts::post_by_id(POST2).unwrap();
+                Some(reaction_emoji())
+            ));
 
-            assert_eq!(shared_post.space_id, Some(SPACE2));
-            assert_eq!(shared_post.created.account, ACCOUNT2);
-            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+            // Emoji reactions don't change the upvote/downvote counters and scores
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 0);
+            assert_eq!(post.downvotes_count, 0);
+            assert_eq!(post.score, 0);
+
+            assert_eq!(Reactions::emoji_reactions_count(POST1, tada_emoji()), 1);
+            assert_eq!(
+                Reactions::get_reaction_counts_by_post_ids(vec![POST1]).get(&POST1),
+                Some(&vec![
+                    (reaction_upvote(), 0),
+                    (reaction_downvote(), 0),
+                    (reaction_emoji(), 1),
+                ])
+            );
         });
     }
 
     #[test]
-    fn share_post_should_work_when_one_of_roles_is_permitted() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_space(
-                None, // From ACCOUNT1
-                Some(None), // Provided without any handle
-                None, // With default space content,
-                None
-            ));
-            // SpaceId 2
-            assert_ok!(_create_post(
-                None, // From ACCOUNT1
-                Some(Some(SPACE2)),
-                None, // With RegularPost extension
-                None // With default post content
-            )); // PostId 1 on SpaceId 2
+    fn create_post_reaction_should_fail_when_emoji_is_invalid() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(ReactionKind::Emoji(Vec::new()))
+            ), ReactionsError::<TestRuntime>::InvalidEmoji);
 
-            assert_ok!(_create_post(
+            assert_noop!(_create_post_reaction(
                 Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 1 by ACCOUNT2 which is permitted by RoleId 1 from ext
+                None,
+                Some(ReactionKind::Emoji(vec![b'a'; MaxEmojiLen::get() as usize + 1]))
+            ), ReactionsError::<TestRuntime>::InvalidEmoji);
         });
     }
 
     #[test]
-    fn share_post_should_work_for_share_own_post_in_same_own_space() {
+    fn create_post_reaction_should_fail_when_emoji_is_plain_ascii_text() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post(
+            // `U` and `D` would look like an upvote and a downvote in RPC
+            for text in ["U", "D", "tada", ":Tada:", "::"].iter() {
+                assert_noop!(_create_post_reaction(
+                    Some(Origin::signed(ACCOUNT2)),
+                    None,
+                    Some(ReactionKind::Emoji(text.as_bytes().to_vec()))
+                ), ReactionsError::<TestRuntime>::InvalidEmoji);
+            }
+
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(ReactionKind::Emoji("🎉".as_bytes().to_vec()))
+            ));
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_emoji_is_not_allowed_in_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_allowed_emojis(None, None, None));
+            assert_eq!(Reactions::allowed_emojis_by_space_id(SPACE1), Some(vec![tada_emoji()]));
+
+            assert_noop!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(ReactionKind::Emoji(":fire:".as_bytes().to_vec()))
+            ), ReactionsError::<TestRuntime>::EmojiIsNotAllowed);
+
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_emoji())
+            ));
+        });
+    }
+
+    #[test]
+    fn update_post_reaction_should_work_from_upvote_to_emoji() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+            assert_ok!(_update_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1,
+                Some(reaction_emoji())
+            ));
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 0);
+            assert_eq!(post.score, 0);
+            assert_eq!(Reactions::emoji_reactions_count(POST1, tada_emoji()), 1);
+            assert_eq!(Reactions::reaction_by_id(REACTION1).unwrap().kind, reaction_emoji());
+        });
+    }
+
+    #[test]
+    fn delete_post_reaction_should_decrease_emoji_reactions_count() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_emoji())
+            ));
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1));
+
+            assert_eq!(Reactions::emoji_reactions_count(POST1, tada_emoji()), 0);
+        });
+    }
+
+    #[test]
+    fn delete_post_reaction_should_work_after_post_deleted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_emoji())
+            )); // ReactionId 1
+            assert_ok!(_delete_default_post());
+
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1));
+
+            assert!(Reactions::reaction_by_id(REACTION1).is_none());
+            assert!(Reactions::reaction_ids_by_post_id(POST1).is_empty());
+            assert!(Reactions::reaction_id_by_account_and_target(ACCOUNT2, ReactionTarget::Post(POST1)).is_none());
+            assert_eq!(Reactions::emoji_reactions_count(POST1, tada_emoji()), 0);
+        });
+    }
+
+    #[test]
+    fn create_space_reaction_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_reaction(None, None, None)); // ReactionId 1 by ACCOUNT2 on SPACE1
+
+            assert_eq!(Reactions::reaction_ids_by_target(ReactionTarget::Space(SPACE1)), vec![REACTION1]);
+            assert_eq!(
+                Reactions::reaction_id_by_account_and_target(ACCOUNT2, ReactionTarget::Space(SPACE1)),
+                Some(REACTION1)
+            );
+            assert_eq!(
+                Reactions::get_reaction_kinds_by_targets_and_reactor(
+                    vec![ReactionTarget::Space(SPACE1), ReactionTarget::Account(ACCOUNT1)],
+                    ACCOUNT2
+                ),
+                vec![(ReactionTarget::Space(SPACE1), reaction_upvote())]
+            );
+        });
+    }
+
+    #[test]
+    fn create_space_reaction_should_fail_with_emoji() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_reaction(None, None, Some(reaction_emoji())),
+                ReactionsError::<TestRuntime>::OnlyVotesAllowedOnTarget
+            );
+        });
+    }
+
+    #[test]
+    fn create_account_reaction_should_fail_when_reacting_on_own_account() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _create_reaction(None, Some(ReactionTarget::Account(ACCOUNT2)), None),
+                ReactionsError::<TestRuntime>::CannotReactOnOwnAccount
+            );
+        });
+    }
+
+    #[test]
+    fn delete_post_reaction_should_fail_when_reaction_is_on_another_target() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_reaction(None, None, None)); // ReactionId 1 by ACCOUNT2 on SPACE1
+
+            assert_noop!(
+                _delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1),
+                ReactionsError::<TestRuntime>::ReactionByAccountNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn update_allowed_emojis_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _update_allowed_emojis(Some(Origin::signed(ACCOUNT2)), None, None),
+                ReactionsError::<TestRuntime>::NoPermissionToUpdateAllowedEmojis
+            );
+        });
+    }
+
+    #[test]
+    fn update_allowed_emojis_should_fail_when_too_many_emojis() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let emojis = (0..=MaxAllowedEmojisPerSpace::get())
+                .map(|i| format!(":emoji_{}:", i).into_bytes())
+                .collect();
+
+            assert_noop!(
+                _update_allowed_emojis(None, None, Some(Some(emojis))),
+                ReactionsError::<TestRuntime>::TooManyAllowedEmojis
+            );
+        });
+    }
+
+
+    #[test]
+    fn delete_comment_should_work_when_comment_has_parents() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            let first_comment_id: PostId = 2;
+            let deleted_comment_id: PostId = 5;
+            let last_comment_id: PostId = 9;
+
+            for parent_id in first_comment_id..last_comment_id as PostId {
+                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
+            }
+
+            // Hide the last comment to check that hidden replies are also decreased
+            assert_ok!(_update_comment(None, Some(last_comment_id), Some(post_update(None, None, Some(true)))));
+
+            assert_ok!(_delete_post(None, Some(deleted_comment_id)));
+
+            // The comment and all its nested replies should be removed
+            for comment_id in deleted_comment_id..=last_comment_id {
+                assert!(Posts::post_by_id(comment_id).is_none());
+            }
+
+            let root_post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(root_post.replies_count, 3);
+            assert_eq!(root_post.hidden_replies_count, 0);
+
+            for comment_id in first_comment_id..deleted_comment_id {
+                let comment = Posts::post_by_id(comment_id).unwrap();
+                assert_eq!(comment.replies_count as PostId, deleted_comment_id - comment_id - 1);
+                assert_eq!(comment.hidden_replies_count, 0);
+            }
+            assert!(Posts::reply_ids_by_post_id(deleted_comment_id - 1).is_empty());
+        });
+    }
+
+    #[test]
+    fn delete_comment_should_fail_when_account_is_not_a_comment_author() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_noop!(
+                _delete_post(Some(Origin::signed(ACCOUNT2)), Some(POST2)),
+                PostsError::<TestRuntime>::NotACommentAuthor
+            );
+        });
+    }
+
+// Shares tests
+
+    #[test]
+    fn share_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2 which is permitted by default in both spaces
+
+            // Check storages
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST2]);
+            assert_eq!(Posts::next_post_id(), POST3);
+
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+
+            // Check whether data stored correctly
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+
+            let shared_post = Posts::post_by_id(POST2).unwrap();
+
+            assert_eq!(shared_post.space_id, Some(SPACE2));
+            assert_eq!(shared_post.created.account, ACCOUNT2);
+            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+        });
+    }
+
+    #[test]
+    fn share_post_should_work_when_one_of_roles_is_permitted() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_space(
+                None, // From ACCOUNT1
+                Some(None), // Provided without any handle
+                None, // With default space content,
+                None
+            ));
+            // SpaceId 2
+            assert_ok!(_create_post(
+                None, // From ACCOUNT1
+                Some(Some(SPACE2)),
+                None, // With RegularPost extension
+                None // With default post content
+            )); // PostId 1 on SpaceId 2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 1 by ACCOUNT2 which is permitted by RoleId 1 from ext
+        });
+    }
+
+    #[test]
+    fn share_post_should_work_for_share_own_post_in_same_own_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(
                 Some(Origin::signed(ACCOUNT1)),
                 Some(Some(SPACE1)),
                 Some(extension_shared_post(POST1)),
@@ -2885,6 +4111,27 @@ mod tests {
         });
     }
 
+    #[test]
+    fn delete_sharing_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(
+                None,
+                None,
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 1 by ACCOUNT1
+
+            assert_ok!(_delete_post(None, Some(POST2)));
+
+            assert!(Posts::post_by_id(POST2).is_none());
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert!(Posts::shared_post_ids_by_original_post_id(POST1).is_empty());
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 0);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().posts_count, 1);
+        });
+    }
+
     #[test]
     fn share_post_should_fail_when_original_post_not_found() {
         ExtBuilder::build_with_space().execute_with(|| {
@@ -2919,10 +4166,10 @@ mod tests {
                 Some(Origin::signed(ACCOUNT2)),
                 Some(Some(SPACE2)),
                 Some(extension_shared_post(POST1)),
-                None)
-            );
+                Some(Content::None))
+            ); // Repost PostId 1 without own content
 
-            // Try to share post with extension SharedPost
+            // Try to share a repost
             assert_noop!(_create_post(
                 Some(Origin::signed(ACCOUNT1)),
                 Some(Some(SPACE1)),
@@ -2932,15 +4179,118 @@ mod tests {
         });
     }
 
+    fn _repost(post_id: PostId) -> DispatchResult {
+        _create_post(None, None, Some(extension_shared_post(post_id)), Some(Content::None))
+    }
+
+    fn _quote(post_id: PostId) -> DispatchResult {
+        _create_post(None, None, Some(extension_shared_post(post_id)), None)
+    }
+
     #[test]
-    fn share_post_should_fail_when_account_has_no_permission_to_create_posts_in_new_space() {
+    fn share_post_should_count_reposts_and_quotes() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_quote(POST1)); // PostId 2
+            assert_ok!(_repost(POST1)); // PostId 3
+
+            let original_post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(original_post.shares_count, 2);
+            assert_eq!(original_post.quotes_count, 1);
+
+            let flat_posts = Posts::get_posts_by_ids(vec![POST1, POST2, POST3], 0, 3);
+            assert_eq!(flat_posts[0].reposts_count, 1);
+            assert_eq!(flat_posts[0].quotes_count, 1);
+
+            assert_eq!(flat_posts[1].extension.is_quote, Some(true));
+            assert_eq!(flat_posts[1].extension.is_repost, None);
+            assert_eq!(flat_posts[2].extension.is_quote, None);
+            assert_eq!(flat_posts[2].extension.is_repost, Some(true));
+        });
+    }
+
+    #[test]
+    fn quote_should_work_until_max_quote_depth_is_reached() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_quote(POST1)); // PostId 2
+            assert_ok!(_quote(POST2)); // PostId 3
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().quotes_count, 1);
+
+            // MaxQuoteDepth is 2, so PostId 3 can be neither quoted nor reposted.
+            assert_noop!(_quote(POST3), PostsError::<TestRuntime>::MaxQuoteDepthReached);
+            assert_noop!(_repost(POST3), PostsError::<TestRuntime>::MaxQuoteDepthReached);
+        });
+    }
+
+    #[test]
+    fn delete_quote_should_decrease_quotes_count() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_quote(POST1)); // PostId 2
+            assert_ok!(_delete_post(None, Some(POST2)));
+
+            let original_post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(original_post.shares_count, 0);
+            assert_eq!(original_post.quotes_count, 0);
+        });
+    }
+
+    #[test]
+    fn update_post_should_fail_when_turning_quote_into_repost() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_quote(POST1)); // PostId 2
+            assert_ok!(_repost(POST1)); // PostId 3
+
+            assert_noop!(
+                _update_post(None, Some(POST2), Some(post_update(None, Some(Content::None), None))),
+                PostsError::<TestRuntime>::CannotConvertSharingPost
+            );
+            assert_noop!(
+                _update_post(None, Some(POST3), Some(post_update(None, Some(updated_post_content()), None))),
+                PostsError::<TestRuntime>::CannotConvertSharingPost
+            );
+            // A quote can still be updated with new content
+            assert_ok!(_update_post(None, Some(POST2), Some(post_update(None, Some(updated_post_content()), None))));
+        });
+    }
+
+    #[test]
+    fn get_sharing_posts_should_return_latest_shares_first() {
         ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_quote(POST1)); // PostId 2
+            assert_ok!(_repost(POST1)); // PostId 3
             assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(None), // No space_handle provided (ok)
-                None, // Default space content,
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
                 None
-            )); // SpaceId 2 by ACCOUNT1
+            )); // SpaceId 2 by ACCOUNT2
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                Some(Content::None)
+            )); // PostId 4 by ACCOUNT2
+
+            assert_eq!(Posts::get_sharing_post_ids(POST1, 0, 2), vec![POST4, POST3]);
+            assert_eq!(Posts::get_sharing_post_ids(POST1, 2, 2), vec![POST2]);
+
+            let sharers: Vec<AccountId> = Posts::get_sharing_posts(POST1, 0, 10)
+                .into_iter()
+                .map(|post| post.owner)
+                .collect();
+            assert_eq!(sharers, vec![ACCOUNT2, ACCOUNT1, ACCOUNT1]);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_account_has_no_permission_to_create_posts_in_new_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(None), // No space_handle provided (ok)
+                None, // Default space content,
+                None
+            )); // SpaceId 2 by ACCOUNT1
 
             // Try to share post with extension SharedPost
             assert_noop!(_create_post(
@@ -2953,265 +4303,2009 @@ mod tests {
     }
 
     #[test]
-    fn share_post_should_fail_when_no_right_permission_in_account_roles() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_space(
-                None, // From ACCOUNT1
-                Some(None), // Provided without any handle
-                None, // With default space content
-                None
-            ));
-            // SpaceId 2
-            assert_ok!(_create_post(
-                None, // From ACCOUNT1
-                Some(Some(SPACE2)),
-                None, // With RegularPost extension
-                None // With default post content
-            )); // PostId 1 on SpaceId 2
+    fn share_post_should_fail_when_no_right_permission_in_account_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_space(
+                None, // From ACCOUNT1
+                Some(None), // Provided without any handle
+                None, // With default space content
+                None
+            ));
+            // SpaceId 2
+            assert_ok!(_create_post(
+                None, // From ACCOUNT1
+                Some(Some(SPACE2)),
+                None, // With RegularPost extension
+                None // With default post content
+            )); // PostId 1 on SpaceId 2
+
+            assert_ok!(_delete_default_role());
+
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+        });
+    }
+
+// Scheduled posts tests
+
+    const SCHEDULED_POST1: ScheduledPostId = 1;
+    const PUBLISH_AT_BLOCK: BlockNumber = 5;
+
+    fn _schedule_default_post() -> DispatchResult {
+        _schedule_post(None, None, None)
+    }
+
+    fn _schedule_post(
+        origin: Option<Origin>,
+        space_id: Option<SpaceId>,
+        publish_at: Option<BlockNumber>,
+    ) -> DispatchResult {
+        Posts::schedule_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+            post_content_ipfs(),
+            vec![],
+            vec![],
+            publish_at.unwrap_or(PUBLISH_AT_BLOCK),
+        )
+    }
+
+    fn _reschedule_post(origin: Option<Origin>, publish_at: BlockNumber) -> DispatchResult {
+        Posts::reschedule_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SCHEDULED_POST1,
+            publish_at,
+        )
+    }
+
+    fn _cancel_scheduled_post(origin: Option<Origin>) -> DispatchResult {
+        Posts::cancel_scheduled_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SCHEDULED_POST1,
+        )
+    }
+
+    fn run_scheduler_at(block: BlockNumber) {
+        System::set_block_number(block);
+        Scheduler::on_initialize(block);
+    }
+
+    #[test]
+    fn schedule_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_schedule_default_post()); // ScheduledPostId 1 by ACCOUNT1
+
+            let scheduled_post = Posts::scheduled_post_by_id(SCHEDULED_POST1).unwrap();
+            assert_eq!(scheduled_post.created.account, ACCOUNT1);
+            assert_eq!(scheduled_post.space_id, SPACE1);
+            assert_eq!(scheduled_post.publish_at, PUBLISH_AT_BLOCK);
+
+            assert_eq!(Posts::scheduled_posts_count_by_account(ACCOUNT1), 1);
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), SCHEDULED_POST_DEPOSIT);
+
+            // The draft is neither a post, nor counted in the space
+            assert!(Posts::post_by_id(POST1).is_none());
+            assert!(Posts::post_ids_by_space_id(SPACE1).is_empty());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().posts_count, 0);
+        });
+    }
+
+    #[test]
+    fn scheduled_post_should_be_published_at_target_block() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_schedule_default_post());
+
+            run_scheduler_at(PUBLISH_AT_BLOCK - 1);
+            assert!(Posts::post_by_id(POST1).is_none());
+
+            run_scheduler_at(PUBLISH_AT_BLOCK);
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.owner, ACCOUNT1);
+            assert_eq!(post.created.block, PUBLISH_AT_BLOCK);
+            assert!(!post.hidden);
+
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().posts_count, 1);
+            assert!(Posts::scheduled_post_by_id(SCHEDULED_POST1).is_none());
+
+            assert_eq!(Posts::scheduled_posts_count_by_account(ACCOUNT1), 0);
+            assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
+        });
+    }
+
+    #[test]
+    fn schedule_post_should_fail_when_publish_block_is_not_in_future() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _schedule_post(None, None, Some(System::block_number())),
+                PostsError::<TestRuntime>::CannotScheduleInPast
+            );
+        });
+    }
+
+    #[test]
+    fn schedule_post_should_fail_when_account_has_too_many_scheduled_posts() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            // MaxScheduledPostsPerAccount is 2
+            assert_ok!(_schedule_default_post());
+            assert_ok!(_schedule_default_post());
+
+            assert_noop!(_schedule_default_post(), PostsError::<TestRuntime>::TooManyScheduledPosts);
+        });
+    }
+
+    #[test]
+    fn schedule_post_should_fail_when_account_has_no_permission_to_create_posts() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _schedule_post(Some(Origin::signed(ACCOUNT2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+            );
+        });
+    }
+
+    #[test]
+    fn reschedule_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_schedule_default_post());
+            assert_ok!(_reschedule_post(None, PUBLISH_AT_BLOCK + 2));
+
+            assert_eq!(Posts::scheduled_post_by_id(SCHEDULED_POST1).unwrap().publish_at, PUBLISH_AT_BLOCK + 2);
+
+            run_scheduler_at(PUBLISH_AT_BLOCK);
+            assert!(Posts::post_by_id(POST1).is_none());
+
+            run_scheduler_at(PUBLISH_AT_BLOCK + 2);
+            assert!(Posts::post_by_id(POST1).is_some());
+        });
+    }
+
+    #[test]
+    fn reschedule_post_should_fail_when_account_is_not_scheduled_post_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_schedule_default_post());
+
+            assert_noop!(
+                _reschedule_post(Some(Origin::signed(ACCOUNT2)), PUBLISH_AT_BLOCK + 2),
+                PostsError::<TestRuntime>::NotAScheduledPostOwner
+            );
+        });
+    }
+
+    #[test]
+    fn cancel_scheduled_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_schedule_default_post());
+            assert_ok!(_cancel_scheduled_post(None));
+
+            assert!(Posts::scheduled_post_by_id(SCHEDULED_POST1).is_none());
+            assert_eq!(Posts::scheduled_posts_count_by_account(ACCOUNT1), 0);
+            assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
+
+            run_scheduler_at(PUBLISH_AT_BLOCK);
+            assert!(Posts::post_by_id(POST1).is_none());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().posts_count, 0);
+        });
+    }
+
+    #[test]
+    fn cancel_scheduled_post_should_fail_when_account_is_not_scheduled_post_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_schedule_default_post());
+
+            assert_noop!(
+                _cancel_scheduled_post(Some(Origin::signed(ACCOUNT2))),
+                PostsError::<TestRuntime>::NotAScheduledPostOwner
+            );
+        });
+    }
+
+    #[test]
+    fn publish_scheduled_post_should_fail_before_target_block() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_schedule_default_post());
+
+            assert_noop!(
+                Posts::publish_scheduled_post(Origin::signed(ACCOUNT1), SCHEDULED_POST1),
+                PostsError::<TestRuntime>::ScheduledPostNotReady
+            );
+        });
+    }
+
+    #[test]
+    fn publish_scheduled_post_should_fail_when_account_is_not_scheduled_post_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_schedule_default_post());
+            System::set_block_number(PUBLISH_AT_BLOCK);
+
+            assert_noop!(
+                Posts::publish_scheduled_post(Origin::signed(ACCOUNT2), SCHEDULED_POST1),
+                PostsError::<TestRuntime>::NotAScheduledPostOwner
+            );
+        });
+    }
+
+// Post expiry tests
+
+    const EXPIRES_AT_BLOCK: BlockNumber = 5;
+
+    fn _set_default_post_expiry() -> DispatchResult {
+        _set_post_expiry(None, None, None, None)
+    }
+
+    fn _set_post_expiry(
+        origin: Option<Origin>,
+        post_id: Option<PostId>,
+        expires_at: Option<Option<BlockNumber>>,
+        action: Option<PostExpiryAction>,
+    ) -> DispatchResult {
+        Posts::set_post_expiry(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+            expires_at.unwrap_or(Some(EXPIRES_AT_BLOCK)),
+            action.unwrap_or(PostExpiryAction::Hide),
+        )
+    }
+
+    fn posts_expiring_at(block: BlockNumber) -> Vec<(PostId, PostExpiryAction)> {
+        PostsExpiringAt::<TestRuntime>::iter_prefix(block).collect()
+    }
+
+    fn hidden_posts_count(post_ids: &[PostId]) -> usize {
+        post_ids.iter().filter(|post_id| Posts::post_by_id(**post_id).unwrap().hidden).count()
+    }
+
+    /// Run `on_initialize` of the posts pallet for every block up to a given one.
+    fn run_posts_to_block(block: BlockNumber) {
+        for n in System::block_number() + 1..=block {
+            System::set_block_number(n);
+            Posts::on_initialize(n);
+        }
+    }
+
+    #[test]
+    fn set_post_expiry_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_default_post_expiry());
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().expires_at, Some(EXPIRES_AT_BLOCK));
+            assert_eq!(posts_expiring_at(EXPIRES_AT_BLOCK), vec![(POST1, PostExpiryAction::Hide)]);
+        });
+    }
+
+    #[test]
+    fn expired_post_should_be_hidden() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_default_post_expiry());
+
+            run_posts_to_block(EXPIRES_AT_BLOCK - 1);
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
+
+            run_posts_to_block(EXPIRES_AT_BLOCK);
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert!(post.hidden);
+            assert!(post.expires_at.is_none());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().hidden_posts_count, 1);
+            assert!(posts_expiring_at(EXPIRES_AT_BLOCK).is_empty());
+        });
+    }
+
+    #[test]
+    fn expired_comment_should_be_hidden_and_counted_in_root_post() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_set_post_expiry(None, Some(POST2), None, None));
+
+            run_posts_to_block(EXPIRES_AT_BLOCK);
+
+            assert!(Posts::post_by_id(POST2).unwrap().hidden);
+
+            let root_post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(root_post.replies_count, 1);
+            assert_eq!(root_post.hidden_replies_count, 1);
+        });
+    }
+
+    #[test]
+    fn expired_post_should_be_deleted_with_replies_when_author_chose_to_delete_it() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_set_post_expiry(None, None, None, Some(PostExpiryAction::Delete)));
+
+            run_posts_to_block(EXPIRES_AT_BLOCK);
+
+            assert!(Posts::post_by_id(POST1).is_none());
+            assert!(Posts::post_by_id(POST2).is_none());
+            assert!(Posts::post_ids_by_space_id(SPACE1).is_empty());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().posts_count, 0);
+        });
+    }
+
+    #[test]
+    fn expired_posts_should_be_processed_in_bounded_batches() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
+
+            for post_id in POST1..=POST3 {
+                assert_ok!(_set_post_expiry(None, Some(post_id), None, None));
+            }
+
+            // Visiting a block counts as an item, so only 2 of 3 allowed items are posts.
+            run_posts_to_block(EXPIRES_AT_BLOCK);
+            assert_eq!(hidden_posts_count(&[POST1, POST2, POST3]), 2);
+            assert_eq!(posts_expiring_at(EXPIRES_AT_BLOCK).len(), 1);
+
+            run_posts_to_block(EXPIRES_AT_BLOCK + 1);
+            assert_eq!(hidden_posts_count(&[POST1, POST2, POST3]), 3);
+            assert!(posts_expiring_at(EXPIRES_AT_BLOCK).is_empty());
+        });
+    }
+
+    #[test]
+    fn expired_post_should_be_hidden_when_it_has_too_many_replies_to_delete() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_default_comment()); // PostId 3
+            assert_ok!(_set_post_expiry(None, None, None, Some(PostExpiryAction::Delete)));
+
+            // MaxExpiredPostsPerBlock is 3, so only one reply fits into a block
+            // besides visiting the block and the post itself.
+            run_posts_to_block(EXPIRES_AT_BLOCK);
+
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+            assert!(Posts::post_by_id(POST2).is_some());
+            assert!(Posts::post_by_id(POST3).is_some());
+            assert!(posts_expiring_at(EXPIRES_AT_BLOCK).is_empty());
+        });
+    }
+
+    #[test]
+    fn set_post_expiry_to_none_should_make_post_permanent() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_default_post_expiry());
+            assert_ok!(_set_post_expiry(None, None, Some(None), None));
+
+            assert!(posts_expiring_at(EXPIRES_AT_BLOCK).is_empty());
+
+            run_posts_to_block(EXPIRES_AT_BLOCK);
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
+        });
+    }
+
+    #[test]
+    fn set_post_expiry_should_fail_when_account_is_not_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _set_post_expiry(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::NotAPostOwner
+            );
+        });
+    }
+
+    #[test]
+    fn set_post_expiry_should_fail_to_delete_when_account_has_no_permission_to_delete_own_posts() {
+        let mut permissions = permissions_where_everyone_can_create_post();
+        permissions.everyone = permissions.everyone.map(|mut everyone| {
+            everyone.remove(&SP::DeleteOwnPosts);
+            everyone
+        });
+
+        ExtBuilder::build_with_space_and_custom_permissions(permissions).execute_with(|| {
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 1
+
+            assert_noop!(
+                _set_post_expiry(Some(Origin::signed(ACCOUNT2)), None, None, Some(PostExpiryAction::Delete)),
+                PostsError::<TestRuntime>::NoPermissionToDeleteOwnPosts
+            );
+            assert_ok!(_set_post_expiry(Some(Origin::signed(ACCOUNT2)), None, None, None));
+        });
+    }
+
+    #[test]
+    fn set_post_expiry_should_fail_when_expiry_block_is_not_in_future() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _set_post_expiry(None, None, Some(Some(System::block_number())), None),
+                PostsError::<TestRuntime>::CannotExpireInPast
+            );
+        });
+    }
+
+// Polls tests
+
+    const POLL_ENDS_AT_BLOCK: BlockNumber = 10;
+
+    fn extension_poll(options_count: PollOptionIndex, kind: PollKind) -> PostExtension<BlockNumber> {
+        PostExtension::Poll(Poll { options_count, kind, ends_at: POLL_ENDS_AT_BLOCK })
+    }
+
+    fn _create_default_poll() -> DispatchResult {
+        _create_poll(3, PollKind::SingleChoice)
+    }
+
+    fn _create_poll(options_count: PollOptionIndex, kind: PollKind) -> DispatchResult {
+        _create_post(None, None, Some(extension_poll(options_count, kind)), None)
+    }
+
+    fn _vote_in_poll(origin: Option<Origin>, option_indices: Vec<PollOptionIndex>) -> DispatchResult {
+        Posts::vote_in_poll(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            POST1,
+            option_indices,
+        )
+    }
+
+    fn poll_results(voters_count: u32, votes: Vec<u32>) -> PollResults {
+        PollResults { voters_count, votes }
+    }
+
+    #[test]
+    fn create_poll_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_poll());
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert!(post.is_poll());
+            assert_eq!(Posts::poll_results_by_post_id(POST1), poll_results(0, vec![0, 0, 0]));
+            assert_eq!(Posts::regular_post_ids_by_owner(ACCOUNT1), vec![POST1]);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().posts_count, 1);
+        });
+    }
+
+    #[test]
+    fn create_poll_should_fail_when_options_count_is_out_of_bounds() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_poll(1, PollKind::SingleChoice),
+                PostsError::<TestRuntime>::InvalidPollOptionsCount
+            );
+            assert_noop!(
+                _create_poll(MaxPollOptions::get() + 1, PollKind::MultipleChoice),
+                PostsError::<TestRuntime>::InvalidPollOptionsCount
+            );
+        });
+    }
+
+    #[test]
+    fn create_poll_should_fail_when_it_ends_in_past() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let poll = Poll { options_count: 2, kind: PollKind::SingleChoice, ends_at: System::block_number() };
+
+            assert_noop!(
+                _create_post(None, None, Some(PostExtension::Poll(poll)), None),
+                PostsError::<TestRuntime>::PollEndsInPast
+            );
+        });
+    }
+
+    #[test]
+    fn vote_in_single_choice_poll_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_poll());
+            assert_ok!(_vote_in_poll(None, vec![1]));
+
+            assert_eq!(Posts::poll_results_by_post_id(POST1), poll_results(1, vec![0, 1, 0]));
+            assert_eq!(Posts::poll_vote_by_account(POST1, ACCOUNT2), Some(vec![1]));
+        });
+    }
+
+    #[test]
+    fn vote_in_multiple_choice_poll_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_poll(3, PollKind::MultipleChoice));
+
+            assert_ok!(_vote_in_poll(None, vec![2, 0]));
+            assert_ok!(_vote_in_poll(Some(Origin::signed(ACCOUNT1)), vec![0]));
+
+            assert_eq!(Posts::poll_results_by_post_id(POST1), poll_results(2, vec![2, 0, 1]));
+            assert_eq!(Posts::poll_vote_by_account(POST1, ACCOUNT2), Some(vec![0, 2]));
+
+            let flat_posts = Posts::get_posts_by_ids(vec![POST1], 0, 1);
+            let flat_poll = flat_posts[0].extension.poll.as_ref().unwrap();
+            assert_eq!(flat_poll.voters_count, 2);
+            assert_eq!(flat_poll.votes, vec![2, 0, 1]);
+        });
+    }
+
+    #[test]
+    fn vote_in_poll_should_fail_when_account_already_voted() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_poll());
+            assert_ok!(_vote_in_poll(None, vec![0]));
+
+            assert_noop!(
+                _vote_in_poll(None, vec![1]),
+                PostsError::<TestRuntime>::AlreadyVotedInPoll
+            );
+        });
+    }
+
+    #[test]
+    fn vote_in_poll_should_fail_when_choice_is_invalid() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_poll());
+
+            assert_noop!(_vote_in_poll(None, vec![]), PostsError::<TestRuntime>::NoPollOptionsChosen);
+            assert_noop!(_vote_in_poll(None, vec![0, 1]), PostsError::<TestRuntime>::TooManyPollOptionsChosen);
+            assert_noop!(_vote_in_poll(None, vec![3]), PostsError::<TestRuntime>::InvalidPollOption);
+        });
+    }
+
+    #[test]
+    fn vote_in_poll_should_fail_when_option_is_chosen_twice() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_poll(3, PollKind::MultipleChoice));
+
+            assert_noop!(_vote_in_poll(None, vec![1, 1]), PostsError::<TestRuntime>::InvalidPollOption);
+        });
+    }
+
+    #[test]
+    fn vote_in_poll_should_fail_when_poll_ended() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_poll());
+
+            System::set_block_number(POLL_ENDS_AT_BLOCK + 1);
+            assert_noop!(_vote_in_poll(None, vec![0]), PostsError::<TestRuntime>::PollEnded);
+        });
+    }
+
+    #[test]
+    fn vote_in_poll_should_fail_when_space_is_hidden() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_poll());
+
+            // Hide the space
+            assert_ok!(_update_space(
+                None,
+                None,
+                Some(space_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_vote_in_poll(None, vec![0]), PostsError::<TestRuntime>::CannotVoteWhenSpaceHidden);
+        });
+    }
+
+    #[test]
+    fn vote_in_poll_should_fail_when_poll_is_hidden() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_poll());
+
+            // Hide the poll
+            assert_ok!(_update_post(
+                None,
+                None,
+                Some(post_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_vote_in_poll(None, vec![0]), PostsError::<TestRuntime>::CannotVoteWhenPollHidden);
+        });
+    }
+
+    #[test]
+    fn vote_in_poll_should_fail_when_post_is_not_a_poll() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(_vote_in_poll(None, vec![0]), PostsError::<TestRuntime>::NotAPoll);
+        });
+    }
+
+    #[test]
+    fn vote_in_poll_should_fail_when_no_permission_to_vote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_poll());
+            assert_ok!(_update_post_permissions(
+                None,
+                None,
+                Some(Some(permissions_where_post_disables(SP::VoteInPolls)))
+            ));
+
+            assert_noop!(
+                _vote_in_poll(None, vec![0]),
+                PostsError::<TestRuntime>::NoPermissionToVoteInPolls
+            );
+        });
+    }
+
+    #[test]
+    fn update_poll_content_should_fail_after_voting() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_poll());
+            assert_ok!(_vote_in_poll(None, vec![0]));
+
+            assert_noop!(
+                _update_post(None, None, Some(post_update(None, Some(updated_post_content()), None))),
+                PostsError::<TestRuntime>::CannotUpdatePollAfterVoting
+            );
+            // A poll can still be hidden
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
+        });
+    }
+
+// Pinned posts tests
+
+    fn _pin_post(origin: Option<Origin>, post_id: Option<PostId>) -> DispatchResult {
+        Posts::pin_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+        )
+    }
+
+    fn _unpin_post(origin: Option<Origin>, post_id: Option<PostId>) -> DispatchResult {
+        Posts::unpin_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+        )
+    }
+
+    fn public_post_ids_in_space_1(pinned_first: bool) -> Vec<PostId> {
+        Posts::get_public_posts_by_space_id(SPACE1, 0, 10, pinned_first)
+            .iter()
+            .map(|post| post.id)
+            .collect()
+    }
+
+    #[test]
+    fn pin_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_pin_post(None, None));
+
+            assert_eq!(Posts::pinned_post_ids_by_space_id(SPACE1), vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn pin_post_should_work_when_account_has_pin_posts_role() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_role(None, None, None, None, Some(vec![SP::PinPosts])));
+            assert_ok!(_grant_default_role()); // Grant the role to ACCOUNT2
+
+            assert_ok!(_pin_post(Some(Origin::signed(ACCOUNT2)), None));
+            assert_eq!(Posts::pinned_post_ids_by_space_id(SPACE1), vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn pin_post_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _pin_post(Some(Origin::signed(ACCOUNT2)), None),
+                PostsError::<TestRuntime>::NoPermissionToPinPosts
+            );
+        });
+    }
+
+    #[test]
+    fn pin_post_should_fail_when_post_is_comment() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_noop!(_pin_post(None, Some(POST2)), PostsError::<TestRuntime>::CannotPinComment);
+        });
+    }
+
+    #[test]
+    fn pin_post_should_fail_when_post_is_already_pinned() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_pin_post(None, None));
+
+            assert_noop!(_pin_post(None, None), PostsError::<TestRuntime>::PostIsAlreadyPinned);
+        });
+    }
+
+    #[test]
+    fn pin_post_should_fail_when_too_many_posts_pinned() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
+
+            assert_ok!(_pin_post(None, Some(POST1)));
+            assert_ok!(_pin_post(None, Some(POST2)));
+
+            assert_noop!(_pin_post(None, Some(POST3)), PostsError::<TestRuntime>::TooManyPinnedPosts);
+        });
+    }
+
+    #[test]
+    fn unpin_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_pin_post(None, None));
+            assert_ok!(_unpin_post(None, None));
+
+            assert!(Posts::pinned_post_ids_by_space_id(SPACE1).is_empty());
+            assert_noop!(_unpin_post(None, None), PostsError::<TestRuntime>::PostIsNotPinned);
+        });
+    }
+
+    #[test]
+    fn delete_post_should_unpin_it() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_pin_post(None, None));
+            assert_ok!(_delete_default_post());
+
+            assert!(Posts::pinned_post_ids_by_space_id(SPACE1).is_empty());
+        });
+    }
+
+    #[test]
+    fn get_public_posts_by_space_id_should_return_pinned_posts_first() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
+
+            assert_ok!(_pin_post(None, Some(POST1)));
+
+            assert_eq!(public_post_ids_in_space_1(true), vec![POST1, POST3, POST2]);
+            assert_eq!(public_post_ids_in_space_1(false), vec![POST3, POST2, POST1]);
+        });
+    }
+
+// Post tags tests
+
+    #[test]
+    fn create_post_should_store_normalized_tags() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_tags(None, vec![b"#Rust".to_vec(), news_tag(), rust_tag()]));
+
+            assert_eq!(Posts::tags_by_post_id(POST1), vec![rust_tag(), news_tag()]);
+            assert_eq!(Posts::post_ids_by_tag(rust_tag()), vec![POST1]);
+            assert_eq!(Posts::post_ids_by_tag(news_tag()), vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_with_too_many_tags() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let tags = vec![rust_tag(), news_tag(), b"web3".to_vec(), b"dev".to_vec()];
+
+            assert_noop!(_create_post_with_tags(None, tags), PostsError::<TestRuntime>::TooManyTags);
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_with_invalid_tags() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_post_with_tags(None, vec![b"#".to_vec()]),
+                PostsError::<TestRuntime>::TagIsEmpty
+            );
+            assert_noop!(
+                _create_post_with_tags(None, vec![b"rust_and_web3".to_vec()]),
+                PostsError::<TestRuntime>::TagIsTooLong
+            );
+            assert_noop!(
+                _create_post_with_tags(None, vec![b"rust-lang".to_vec()]),
+                PostsError::<TestRuntime>::TagContainsInvalidChars
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_when_comment_has_tags() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _create_post_with_tags(Some(extension_comment(None, POST1)), vec![rust_tag()]),
+                PostsError::<TestRuntime>::CannotTagComment
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_should_reindex_tags() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_tags(None, vec![rust_tag(), news_tag()]));
+            assert_ok!(_update_post(None, None, Some(post_update_with_tags(vec![news_tag(), b"web3".to_vec()]))));
+
+            assert_eq!(Posts::tags_by_post_id(POST1), vec![news_tag(), b"web3".to_vec()]);
+            assert!(Posts::post_ids_by_tag(rust_tag()).is_empty());
+            assert_eq!(Posts::post_ids_by_tag(news_tag()), vec![POST1]);
+            assert_eq!(Posts::post_ids_by_tag(b"web3".to_vec()), vec![POST1]);
+
+            let post_history = PostHistory::edit_history(POST1)[0].clone();
+            assert_eq!(post_history.old_data.tags, Some(vec![rust_tag(), news_tag()]));
+        });
+    }
+
+    #[test]
+    fn update_post_should_ignore_tags_that_are_not_changed() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_tags(None, vec![rust_tag()]));
+
+            // Tags are normalized before they are compared
+            assert_ok!(_update_post(None, None, Some(post_update_with_tags(vec![b"#RUST".to_vec()]))));
+            assert!(PostHistory::edit_history(POST1).is_empty());
+        });
+    }
+
+    #[test]
+    fn hidden_post_should_not_be_indexed_by_tags() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_tags(None, vec![rust_tag()]));
+
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
+            assert!(Posts::post_ids_by_tag(rust_tag()).is_empty());
+            assert_eq!(Posts::tags_by_post_id(POST1), vec![rust_tag()]);
+
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(false)))));
+            assert_eq!(Posts::post_ids_by_tag(rust_tag()), vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn post_moved_out_of_space_should_not_be_indexed_by_tags() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_tags(None, vec![rust_tag()]));
+
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert!(Posts::post_ids_by_tag(rust_tag()).is_empty());
+
+            assert_ok!(_move_post(None, None, Some(Some(SPACE1))));
+            assert_eq!(Posts::post_ids_by_tag(rust_tag()), vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn delete_post_should_remove_its_tags() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_tags(None, vec![rust_tag()]));
+            assert_ok!(_delete_post(None, None));
+
+            assert!(Posts::tags_by_post_id(POST1).is_empty());
+            assert!(Posts::post_ids_by_tag(rust_tag()).is_empty());
+        });
+    }
+
+    #[test]
+    fn get_posts_by_tag_should_return_latest_posts_first() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_tags(None, vec![rust_tag()]));
+            assert_ok!(_create_post_with_tags(None, vec![news_tag()]));
+            assert_ok!(_create_post_with_tags(None, vec![rust_tag(), news_tag()]));
+
+            assert_eq!(Posts::get_post_ids_by_tag(b"#Rust".to_vec(), 0, 10), vec![POST3, POST1]);
+            assert_eq!(Posts::get_post_ids_by_tag(rust_tag(), 1, 10), vec![POST1]);
+            assert_eq!(Posts::get_post_ids_by_tag(news_tag(), 0, 1), vec![POST3]);
+
+            let post_ids: Vec<PostId> = Posts::get_posts_by_tag(news_tag(), 0, 10)
+                .iter()
+                .map(|post| post.id)
+                .collect();
+            assert_eq!(post_ids, vec![POST3, POST2]);
+        });
+    }
+
+    #[test]
+    fn get_posts_by_tag_should_return_nothing_when_offset_is_out_of_range() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_tags(None, vec![rust_tag()]));
+
+            assert!(Posts::get_posts_by_tag(rust_tag(), 1, 10).is_empty());
+            assert!(Posts::get_posts_by_tag(rust_tag(), 5, 10).is_empty());
+            assert!(Posts::get_post_ids_by_tag(rust_tag(), 5, 10).is_empty());
+        });
+    }
+
+// Feed tests
+
+    fn feed_post_ids(account: AccountId, kind_filter: Vec<FlatPostKind>) -> Vec<PostId> {
+        Posts::get_feed(account, kind_filter, 0, 10).iter().map(|post| post.id).collect()
+    }
+
+    #[test]
+    fn get_feed_should_include_posts_of_followed_accounts() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None)); // PostId 2
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), Some(extension_shared_post(POST1)), None)); // PostId 3
+            assert_ok!(_create_default_comment()); // PostId 4
+
+            assert!(feed_post_ids(ACCOUNT2, vec![]).is_empty());
+
+            assert_ok!(_default_follow_account()); // ACCOUNT2 follows ACCOUNT1
+            assert_eq!(feed_post_ids(ACCOUNT2, vec![]), vec![POST3, POST2, POST1]);
+        });
+    }
+
+    #[test]
+    fn get_feed_should_filter_posts_by_kind() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 2
+            assert_ok!(_default_follow_account());
+
+            assert_eq!(feed_post_ids(ACCOUNT2, vec![FlatPostKind::RegularPost]), vec![POST1]);
+            assert_eq!(feed_post_ids(ACCOUNT2, vec![FlatPostKind::SharedPost]), vec![POST2]);
+            assert!(feed_post_ids(ACCOUNT2, vec![FlatPostKind::Comment]).is_empty());
+        });
+    }
+
+    #[test]
+    fn get_feed_should_not_duplicate_posts_from_followed_space_and_account() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_default_follow_space());
+            assert_ok!(_default_follow_account());
+
+            assert_eq!(feed_post_ids(ACCOUNT2, vec![]), vec![POST1]);
+        });
+    }
+
+// Posts by account tests
+
+    fn account_post_ids(kind_filter: Vec<FlatPostKind>) -> Vec<PostId> {
+        Posts::get_post_ids_by_account(ACCOUNT1, kind_filter, 0, 10)
+    }
+
+    #[test]
+    fn create_post_should_index_posts_by_owner_and_kind() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 3
+
+            assert_eq!(Posts::regular_post_ids_by_owner(ACCOUNT1), vec![POST1]);
+            assert_eq!(Posts::comment_ids_by_owner(ACCOUNT1), vec![POST2]);
+            assert_eq!(Posts::sharing_post_ids_by_owner(ACCOUNT1), vec![POST3]);
+            assert!(Posts::regular_post_ids_by_owner(ACCOUNT2).is_empty());
+        });
+    }
+
+    #[test]
+    fn delete_post_should_remove_it_and_its_replies_from_owner_indexes() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 3
+            assert_ok!(_delete_post(None, None));
+
+            assert_eq!(Posts::regular_post_ids_by_owner(ACCOUNT1), vec![POST3]);
+            assert!(Posts::comment_ids_by_owner(ACCOUNT1).is_empty());
+        });
+    }
+
+    #[test]
+    fn delete_comment_from_space_should_move_it_to_regular_posts_in_owner_index() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 3
+
+            // A comment becomes a regular post, e.g. when its entity is deleted by a moderator
+            assert_ok!(Posts::delete_post_from_space(POST2));
+
+            assert_eq!(Posts::regular_post_ids_by_owner(ACCOUNT1), vec![POST1, POST2, POST3]);
+            assert!(Posts::comment_ids_by_owner(ACCOUNT1).is_empty());
+        });
+    }
+
+    #[test]
+    fn move_post_should_keep_it_in_owner_index() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_move_post_1_to_space_2());
+
+            assert_eq!(account_post_ids(vec![]), vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn get_post_ids_by_account_should_filter_posts_by_kind() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 3
+
+            assert_eq!(account_post_ids(vec![]), vec![POST3, POST2, POST1]);
+            assert_eq!(account_post_ids(vec![FlatPostKind::Comment]), vec![POST2]);
+            assert_eq!(
+                account_post_ids(vec![FlatPostKind::RegularPost, FlatPostKind::SharedPost]),
+                vec![POST3, POST1]
+            );
+            assert_eq!(Posts::get_post_ids_by_account(ACCOUNT1, vec![], 1, 1), vec![POST2]);
+        });
+    }
+
+    #[test]
+    fn get_posts_by_account_should_not_return_hidden_posts() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_update_post(None, Some(POST2), Some(post_update(None, None, Some(true)))));
+
+            let post_ids: Vec<PostId> = Posts::get_posts_by_account(ACCOUNT1, vec![], 0, 10)
+                .iter()
+                .map(|post| post.id)
+                .collect();
+            assert_eq!(post_ids, vec![POST1]);
+            assert_eq!(account_post_ids(vec![]), vec![POST2, POST1]);
+        });
+    }
+
+// Timeline tests
+
+    fn timeline_post_ids(
+        account: AccountId,
+        cursor: Option<TimelineCursor<AccountId>>,
+        limit: u16,
+    ) -> (Vec<PostId>, Option<TimelineCursor<AccountId>>) {
+        let timeline = Posts::get_timeline(account, cursor, limit);
+        let post_ids = timeline.posts.iter().map(|post| post.id).collect();
+        (post_ids, timeline.next_cursor)
+    }
+
+    #[test]
+    fn get_timeline_should_be_empty_when_nothing_is_followed() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_eq!(timeline_post_ids(ACCOUNT2, None, 10), (vec![], None));
+        });
+    }
+
+    #[test]
+    fn get_timeline_should_merge_posts_from_followed_spaces_and_accounts() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None)); // PostId 2
+            assert_ok!(_create_default_comment()); // PostId 3
+
+            assert_ok!(_default_follow_space()); // ACCOUNT2 follows SPACE1
+            assert_eq!(timeline_post_ids(ACCOUNT2, None, 10), (vec![POST1], None));
+
+            assert_ok!(_default_follow_account()); // ACCOUNT2 follows ACCOUNT1
+            assert_eq!(timeline_post_ids(ACCOUNT2, None, 10), (vec![POST2, POST1], None));
+        });
+    }
+
+    #[test]
+    fn get_timeline_should_not_return_hidden_posts() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_default_follow_space());
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
+
+            assert_eq!(timeline_post_ids(ACCOUNT2, None, 10), (vec![], None));
+        });
+    }
+
+    #[test]
+    fn get_timeline_should_paginate_with_cursor() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_default_follow_space());
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
+
+            let (post_ids, next_cursor) = timeline_post_ids(ACCOUNT2, None, 2);
+            assert_eq!(post_ids, vec![POST3, POST2]);
+            assert_eq!(next_cursor, Some(vec![(TimelineSource::Space(SPACE1), POST1)]));
+
+            // A new post should not shift the next page
+            assert_ok!(_create_default_post());
+
+            assert_eq!(timeline_post_ids(ACCOUNT2, next_cursor, 2), (vec![POST1], None));
+        });
+    }
+
+    #[test]
+    fn get_timeline_should_skip_posts_from_sources_that_are_not_followed() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_default_follow_space()); // ACCOUNT2 follows SPACE1
+
+            // Posts in a space that is not followed
+            for _ in 0..5 {
+                assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None));
+            }
+            assert_ok!(_create_default_post()); // PostId 7
+            let latest_post_id: PostId = 7;
+
+            let next_cursor = Some(vec![(TimelineSource::Space(SPACE1), POST1)]);
+            assert_eq!(timeline_post_ids(ACCOUNT2, None, 1), (vec![latest_post_id], next_cursor.clone()));
+            assert_eq!(timeline_post_ids(ACCOUNT2, next_cursor, 1), (vec![POST1], None));
+        });
+    }
+
+    #[test]
+    fn get_timeline_should_continue_when_cursor_post_is_moved_to_another_space() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_default_follow_space()); // ACCOUNT2 follows SPACE1
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
+
+            let (post_ids, next_cursor) = timeline_post_ids(ACCOUNT2, None, 1);
+            assert_eq!(post_ids, vec![POST3]);
+            assert_eq!(next_cursor, Some(vec![(TimelineSource::Space(SPACE1), POST2)]));
+
+            assert_ok!(_move_post(None, Some(POST2), Some(Some(SPACE2))));
+
+            assert_eq!(timeline_post_ids(ACCOUNT2, next_cursor, 10), (vec![POST1], None));
+        });
+    }
+
+    #[test]
+    fn get_timeline_should_include_post_moved_to_followed_space() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None)); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
+            assert_ok!(_default_follow_space()); // ACCOUNT2 follows SPACE1
+
+            assert_ok!(_move_post(None, Some(POST2), Some(Some(SPACE1))));
+            assert_eq!(timeline_post_ids(ACCOUNT2, None, 10), (vec![POST3, POST2, POST1], None));
+
+            assert_ok!(_delete_post(None, Some(POST2)));
+            assert_eq!(timeline_post_ids(ACCOUNT2, None, 10), (vec![POST3, POST1], None));
+        });
+    }
+
+    #[test]
+    fn get_timeline_should_examine_at_most_max_scan_depth_posts() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_default_follow_space());
+
+            // Hidden posts are examined, but not included in a timeline
+            for _ in 0..MAX_TIMELINE_SCAN_DEPTH {
+                assert_ok!(_create_default_post());
+            }
+            for post_id in POST1 + 1..Posts::next_post_id() {
+                PostById::<TestRuntime>::mutate(post_id, |post| post.as_mut().unwrap().hidden = true);
+            }
+
+            let next_cursor = Some(vec![(TimelineSource::Space(SPACE1), POST1)]);
+            assert_eq!(timeline_post_ids(ACCOUNT2, None, 10), (vec![], next_cursor.clone()));
+            assert_eq!(timeline_post_ids(ACCOUNT2, next_cursor, 10), (vec![POST1], None));
+        });
+    }
+
+// Profiles tests
+
+    #[test]
+    fn create_profile_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile()); // AccountId 1
+
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.created.account, ACCOUNT1);
+            assert!(profile.updated.is_none());
+            assert_eq!(profile.content, profile_content_ipfs());
+
+            assert!(ProfileHistory::edit_history(ACCOUNT1).is_empty());
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_profile_is_already_created() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_noop!(_create_default_profile(), ProfilesError::<TestRuntime>::ProfileAlreadyCreated);
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_create_profile(
+                None,
+                None,
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_ok!(_update_profile(
+                None,
+                Some(space_content_ipfs())
+            ));
+
+            // Check whether profile updated correctly
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert!(profile.updated.is_some());
+            assert_eq!(profile.content, space_content_ipfs());
+
+            // Check whether profile history is written correctly
+            let profile_history = ProfileHistory::edit_history(ACCOUNT1)[0].clone();
+            assert_eq!(profile_history.old_data.content, Some(profile_content_ipfs()));
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_social_account_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_update_profile(
+                None,
+                Some(profile_content_ipfs())
+            ), ProfilesError::<TestRuntime>::SocialAccountNotFound);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_account_has_no_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
+            assert_noop!(_update_profile(
+                None,
+                Some(profile_content_ipfs())
+            ), ProfilesError::<TestRuntime>::AccountHasNoProfile);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_no_updates_for_profile_provided() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_noop!(_update_profile(
+                None,
+                None
+            ), ProfilesError::<TestRuntime>::NoUpdatesForProfile);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_noop!(_update_profile(
+                None,
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn create_profile_should_work_with_handle() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(None, Some(Some(b"Profile_Handle".to_vec())), None));
+
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.handle, Some(b"Profile_Handle".to_vec()));
+
+            // Check that the handle is stored in lowercase and the deposit is reserved:
+            assert_eq!(Profiles::account_id_by_handle(profile_handle()), Some(ACCOUNT1));
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT);
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_handle_is_reserved_by_another_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(None, Some(Some(profile_handle())), None));
+
+            assert_noop!(_create_profile(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(profile_handle())),
+                None
+            ), ProfilesError::<TestRuntime>::ProfileHandleIsNotUnique);
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_handle_is_reserved_by_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_create_profile(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(space_handle())),
+                None
+            ), ProfilesError::<TestRuntime>::ProfileHandleIsNotUnique);
+        });
+    }
+
+    #[test]
+    fn create_space_should_fail_when_handle_is_reserved_by_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(space_handle())),
+                None
+            ));
+
+            assert_noop!(
+                _create_default_space(),
+                SpacesError::<TestRuntime>::SpaceHandleIsNotUnique
+            );
+        });
+    }
+
+    #[test]
+    fn update_profile_handle_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_profile_handle(None, None));
+
+            assert_eq!(Profiles::account_id_by_handle(profile_handle()), Some(ACCOUNT1));
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT);
+
+            // Change the handle, the deposit should stay reserved:
+            assert_ok!(_update_profile_handle(None, Some(Some(space_handle_2()))));
+
+            assert!(Profiles::account_id_by_handle(profile_handle()).is_none());
+            assert_eq!(Profiles::account_id_by_handle(space_handle_2()), Some(ACCOUNT1));
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT);
+
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.handle, Some(space_handle_2()));
+            assert!(profile.updated.is_some());
+        });
+    }
+
+    #[test]
+    fn update_profile_handle_should_unreserve_deposit_when_handle_removed() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(None, Some(Some(profile_handle())), None));
+            assert_ok!(_update_profile_handle(None, Some(None)));
+
+            assert!(Profiles::account_id_by_handle(profile_handle()).is_none());
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), 0);
+            assert!(Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap().handle.is_none());
+        });
+    }
+
+    #[test]
+    fn update_profile_handle_should_fail_when_handle_is_not_changed() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(None, Some(Some(profile_handle())), None));
+
+            assert_noop!(
+                _update_profile_handle(None, None),
+                ProfilesError::<TestRuntime>::NoUpdatesForProfile
+            );
+        });
+    }
+
+// Space following tests
+
+    #[test]
+    fn follow_space_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 2);
+            assert_eq!(SpaceFollows::spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
+            assert_eq!(space_follower_ids(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
+            assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)), true);
+        });
+    }
+
+    #[test]
+    fn follow_space_should_fail_when_space_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_default_follow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
+
+    #[test]
+    fn follow_space_should_fail_when_account_is_already_space_follower() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::AlreadySpaceFollower);
+        });
+    }
+
+    #[test]
+    fn follow_space_should_fail_when_trying_to_follow_hidden_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_update_space(
+                None,
+                None,
+                Some(space_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::CannotFollowHiddenSpace);
+        });
+    }
+
+    #[test]
+    fn follow_space_should_fail_when_account_follows_too_many_spaces() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            // ACCOUNT1 follows SpaceId 1 and SpaceId 2 as their creator
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None));
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None));
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT1)), Some(SPACE2 + 1)));
+
+            // MaxFollowedSpaces is 3 in the test runtime
+            assert_noop!(
+                _follow_space(Some(Origin::signed(ACCOUNT1)), Some(SPACE2 + 2)),
+                SpaceFollowsError::<TestRuntime>::TooManyFollowedSpaces
+            );
+        });
+    }
+
+    #[test]
+    fn create_space_should_fail_when_creator_follows_too_many_spaces() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None));
+
+            // The creator of a space follows it, and ACCOUNT1 already follows 3 spaces
+            assert_noop!(
+                _create_space(None, Some(None), None, None),
+                SpaceFollowsError::<TestRuntime>::TooManyFollowedSpaces
+            );
+        });
+    }
+
+    #[test]
+    fn unfollow_space_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space());
+            // Follow SpaceId 1 by ACCOUNT2
+            assert_ok!(_default_unfollow_space());
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
+            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
+            assert_eq!(space_follower_ids(SPACE1), vec![ACCOUNT1]);
+        });
+    }
+
+    #[test]
+    fn unfollow_space_should_fail_when_space_not_found() {
+        ExtBuilder::build_with_space_follow_no_space().execute_with(|| {
+            assert_noop!(_default_unfollow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
+
+    #[test]
+    fn unfollow_space_should_fail_when_account_is_not_space_follower_yet() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_default_unfollow_space(), SpaceFollowsError::<TestRuntime>::NotSpaceFollower);
+        });
+    }
+
+    #[test]
+    fn get_space_followers_should_paginate_followers() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT3)), None));
+
+            let first_page = SpaceFollows::get_space_followers(SPACE1, 0, 2);
+            let second_page = SpaceFollows::get_space_followers(SPACE1, 2, 2);
+            assert_eq!(first_page.len(), 2);
+            assert_eq!(second_page.len(), 1);
+
+            let mut follower_ids: Vec<AccountId> = first_page.iter()
+                .chain(second_page.iter())
+                .map(|social_account| social_account.id)
+                .collect();
+            follower_ids.sort_unstable();
+            assert_eq!(follower_ids, vec![ACCOUNT1, ACCOUNT2, ACCOUNT3]);
+        });
+    }
+
+// Account following tests
+
+    #[test]
+    fn follow_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+
+            assert_eq!(ProfileFollows::accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1]);
+            assert_eq!(account_follower_ids(ACCOUNT1), vec![ACCOUNT2]);
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), true);
+        });
+    }
+
+    #[test]
+    fn follow_account_should_fail_when_account_tries_to_follow_themself() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_follow_account(
+                None,
+                Some(ACCOUNT2)
+            ), ProfileFollowsError::<TestRuntime>::AccountCannotFollowItself);
+        });
+    }
+
+    #[test]
+    fn follow_account_should_fail_when_account_is_already_following_account() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+
+            assert_noop!(_default_follow_account(), ProfileFollowsError::<TestRuntime>::AlreadyAccountFollower);
+        });
+    }
+
+    #[test]
+    fn follow_account_should_fail_when_account_follows_too_many_accounts() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            assert_ok!(_follow_account(None, Some(ACCOUNT3)));
+
+            // MaxFollowedAccounts is 2 in the test runtime
+            assert_noop!(
+                _follow_account(None, Some(ACCOUNT4)),
+                ProfileFollowsError::<TestRuntime>::TooManyFollowedAccounts
+            );
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_default_unfollow_account());
+
+            assert!(ProfileFollows::accounts_followed_by_account(ACCOUNT2).is_empty());
+            assert!(account_follower_ids(ACCOUNT1).is_empty());
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_fail_when_account_tries_to_unfollow_themself() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_unfollow_account(
+                None,
+                Some(ACCOUNT2)
+            ), ProfileFollowsError::<TestRuntime>::AccountCannotUnfollowItself);
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_fail_when_account_is_not_following_another_account_yet() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            assert_ok!(_default_unfollow_account());
+
+            assert_noop!(_default_unfollow_account(), ProfileFollowsError::<TestRuntime>::NotAccountFollower);
+        });
+    }
+
+    #[test]
+    fn get_account_followers_should_return_social_accounts_of_followers() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT3)), None));
+
+            let followers = ProfileFollows::get_account_followers(ACCOUNT1, 0, 10);
+            let mut follower_ids: Vec<AccountId> = followers.iter().map(|social_account| social_account.id).collect();
+            follower_ids.sort_unstable();
+
+            assert_eq!(follower_ids, vec![ACCOUNT2, ACCOUNT3]);
+            assert!(followers.iter().all(|social_account| social_account.following_accounts_count == 1));
+            assert_eq!(ProfileFollows::get_account_followers(ACCOUNT1, 1, 10).len(), 1);
+        });
+    }
+
+    #[test]
+    fn get_accounts_followed_by_account_should_paginate_in_follow_order() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_follow_account(None, Some(ACCOUNT3)));
+            assert_ok!(_default_follow_account());
+
+            let followed_ids = |offset, limit| -> Vec<AccountId> {
+                ProfileFollows::get_accounts_followed_by_account(ACCOUNT2, offset, limit)
+                    .iter()
+                    .map(|social_account| social_account.id)
+                    .collect()
+            };
+
+            assert_eq!(followed_ids(0, 10), vec![ACCOUNT3, ACCOUNT1]);
+            assert_eq!(followed_ids(1, 1), vec![ACCOUNT1]);
+            assert!(followed_ids(2, 10).is_empty());
+        });
+    }
+
+// Account blocks tests
+
+    #[test]
+    fn block_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_block_account()); // Block ACCOUNT2 by ACCOUNT1
+
+            assert_eq!(AccountBlocks::get_blocked_accounts(ACCOUNT1), vec![ACCOUNT2]);
+            assert_eq!(AccountBlocks::blocked_accounts_count(ACCOUNT1), 1);
+            assert!(AccountBlocks::account_blocked_by_account(ACCOUNT1, ACCOUNT2));
+            assert!(!AccountBlocks::account_blocked_by_account(ACCOUNT2, ACCOUNT1));
+        });
+    }
+
+    #[test]
+    fn block_account_should_make_blocked_account_unfollow_blocker() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_default_block_account());
+
+            assert!(ProfileFollows::accounts_followed_by_account(ACCOUNT2).is_empty());
+            assert!(account_follower_ids(ACCOUNT1).is_empty());
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().followers_count, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().following_accounts_count, 0);
+        });
+    }
+
+    #[test]
+    fn block_account_should_fail_when_account_tries_to_block_themself() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _block_account(None, Some(ACCOUNT1)),
+                AccountBlocksError::<TestRuntime>::AccountCannotBlockItself
+            );
+        });
+    }
+
+    #[test]
+    fn block_account_should_fail_when_account_is_already_blocked() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_block_account());
+
+            assert_noop!(_default_block_account(), AccountBlocksError::<TestRuntime>::AccountAlreadyBlocked);
+        });
+    }
+
+    #[test]
+    fn block_account_should_fail_when_too_many_accounts_blocked() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_block_account());
+            assert_ok!(_block_account(None, Some(ACCOUNT3)));
+
+            assert_noop!(
+                _block_account(None, Some(ACCOUNT3 + 1)),
+                AccountBlocksError::<TestRuntime>::TooManyBlockedAccounts
+            );
+        });
+    }
+
+    #[test]
+    fn unblock_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_block_account());
+            assert_ok!(_default_unblock_account());
 
-            assert_ok!(_delete_default_role());
+            assert!(AccountBlocks::get_blocked_accounts(ACCOUNT1).is_empty());
+            assert_eq!(AccountBlocks::blocked_accounts_count(ACCOUNT1), 0);
+            assert!(!AccountBlocks::account_blocked_by_account(ACCOUNT1, ACCOUNT2));
 
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            // ACCOUNT2 can follow ACCOUNT1 again
+            assert_ok!(_default_follow_account());
         });
     }
 
-// Profiles tests
+    #[test]
+    fn unblock_account_should_fail_when_account_is_not_blocked() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_default_unblock_account(), AccountBlocksError::<TestRuntime>::AccountNotBlocked);
+        });
+    }
 
     #[test]
-    fn create_profile_should_work() {
+    fn follow_account_should_fail_when_follower_is_blocked() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile()); // AccountId 1
+            assert_ok!(_default_block_account());
 
-            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
-            assert_eq!(profile.created.account, ACCOUNT1);
-            assert!(profile.updated.is_none());
-            assert_eq!(profile.content, profile_content_ipfs());
+            assert_noop!(_default_follow_account(), AccountBlocksError::<TestRuntime>::BlockedByFollowedAccount);
 
-            assert!(ProfileHistory::edit_history(ACCOUNT1).is_empty());
+            // The blocker can still follow the blocked account
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT1)), Some(ACCOUNT2)));
         });
     }
 
     #[test]
-    fn create_profile_should_fail_when_profile_is_already_created() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_noop!(_create_default_profile(), ProfilesError::<TestRuntime>::ProfileAlreadyCreated);
+    fn create_comment_should_fail_when_blocked_by_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_default_block_account());
+
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::BlockedByPostOwner
+            );
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT3)), None, None, None));
         });
     }
 
     #[test]
-    fn create_profile_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_create_profile(
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+    fn create_comment_should_fail_when_blocked_by_parent_comment_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 2
+            assert_ok!(_block_account(Some(Origin::signed(ACCOUNT2)), Some(ACCOUNT3)));
+
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT3)), None, Some(Some(POST2)), None),
+                PostsError::<TestRuntime>::BlockedByPostOwner
+            );
+            // ACCOUNT3 can still reply to the root post of ACCOUNT1
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT3)), None, None, None));
         });
     }
 
     #[test]
-    fn update_profile_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_ok!(_update_profile(
-                None,
-                Some(space_content_ipfs())
-            ));
+    fn create_post_reaction_should_fail_when_blocked_by_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_default_block_account());
 
-            // Check whether profile updated correctly
-            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
-            assert!(profile.updated.is_some());
-            assert_eq!(profile.content, space_content_ipfs());
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None),
+                ReactionsError::<TestRuntime>::BlockedByContentOwner
+            );
+        });
+    }
 
-            // Check whether profile history is written correctly
-            let profile_history = ProfileHistory::edit_history(ACCOUNT1)[0].clone();
-            assert_eq!(profile_history.old_data.content, Some(profile_content_ipfs()));
+    #[test]
+    fn update_post_reaction_should_fail_when_blocked_by_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+            assert_ok!(_default_block_account());
+
+            assert_noop!(
+                _update_post_reaction(Some(Origin::signed(ACCOUNT2)), None, 1, Some(reaction_downvote())),
+                ReactionsError::<TestRuntime>::BlockedByContentOwner
+            );
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_social_account_not_found() {
+    fn create_reaction_should_fail_when_blocked_by_reacted_account() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_update_profile(
-                None,
-                Some(profile_content_ipfs())
-            ), ProfilesError::<TestRuntime>::SocialAccountNotFound);
+            assert_ok!(_default_block_account());
+
+            assert_noop!(
+                _create_reaction(None, Some(ReactionTarget::Account(ACCOUNT1)), None),
+                ReactionsError::<TestRuntime>::BlockedByContentOwner
+            );
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_account_has_no_profile() {
+    fn filter_blocked_accounts_should_return_only_blocked_accounts() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
-            assert_noop!(_update_profile(
-                None,
-                Some(profile_content_ipfs())
-            ), ProfilesError::<TestRuntime>::AccountHasNoProfile);
+            assert_ok!(_block_account(None, Some(ACCOUNT3)));
+            assert_ok!(_default_block_account());
+
+            assert_eq!(AccountBlocks::get_blocked_accounts(ACCOUNT1), vec![ACCOUNT3, ACCOUNT2]);
+            assert_eq!(
+                AccountBlocks::filter_blocked_accounts(ACCOUNT1, vec![ACCOUNT1, ACCOUNT2, ACCOUNT3]),
+                vec![ACCOUNT2, ACCOUNT3]
+            );
+            assert!(AccountBlocks::filter_blocked_accounts(ACCOUNT2, vec![ACCOUNT1, ACCOUNT3]).is_empty());
         });
     }
 
+// Scoring tests
+
+    fn reputation_of(account: AccountId) -> u32 {
+        Profiles::social_account_by_id(account).map_or(0, |social_account| social_account.reputation)
+    }
+
+    fn assert_post_and_space_scores(post_score: i32, space_score: i32) {
+        assert_eq!(Posts::post_by_id(POST1).unwrap().score, post_score);
+        assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, space_score);
+    }
+
     #[test]
-    fn update_profile_should_fail_when_no_updates_for_profile_provided() {
+    fn score_diff_for_action_should_depend_on_reputation() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_noop!(_update_profile(
-                None,
-                None
-            ), ProfilesError::<TestRuntime>::NoUpdatesForProfile);
+            assert_eq!(Scores::score_diff_for_action(1, ScoringAction::UpvotePost), 5);
+            assert_eq!(Scores::score_diff_for_action(2, ScoringAction::UpvotePost), 10);
+            assert_eq!(Scores::score_diff_for_action(1024, ScoringAction::UpvotePost), 55);
+            assert_eq!(Scores::score_diff_for_action(1024, ScoringAction::DownvotePost), -33);
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            assert_noop!(_update_profile(
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+    fn upvote_post_should_change_scores_and_reputation() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            assert_post_and_space_scores(5, 5);
+            assert_eq!(reputation_of(ACCOUNT1), 6);
         });
     }
 
-// Space following tests
+    #[test]
+    fn delete_post_reaction_should_revert_scores_and_reputation() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1));
+
+            assert_post_and_space_scores(0, 0);
+            assert_eq!(reputation_of(ACCOUNT1), 1);
+            assert!(Scores::post_score_change_by_account((ACCOUNT2, POST1, ScoringAction::UpvotePost)).is_none());
+        });
+    }
 
     #[test]
-    fn follow_space_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+    fn update_post_reaction_should_replace_score_change() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+            assert_ok!(_update_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1,
+                Some(reaction_downvote())
+            ));
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 2);
-            assert_eq!(SpaceFollows::spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
-            assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
-            assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)), true);
+            assert_post_and_space_scores(-3, -3);
+            // Reputation cannot go below 1
+            assert_eq!(reputation_of(ACCOUNT1), 1);
+
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1));
+            assert_post_and_space_scores(0, 0);
+            assert_eq!(reputation_of(ACCOUNT1), 1);
         });
     }
 
     #[test]
-    fn follow_space_should_fail_when_space_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_default_follow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+    fn reaction_on_own_post_should_not_change_scores() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_post_reaction());
+
+            assert_post_and_space_scores(0, 0);
+            assert_eq!(reputation_of(ACCOUNT1), 1);
         });
     }
 
     #[test]
-    fn follow_space_should_fail_when_account_is_already_space_follower() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+    fn comment_should_change_root_post_scores_until_deleted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 2
 
-            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::AlreadySpaceFollower);
+            assert_post_and_space_scores(5, 5);
+            assert_eq!(reputation_of(ACCOUNT1), 6);
+
+            assert_ok!(_delete_post(Some(Origin::signed(ACCOUNT2)), Some(POST2)));
+
+            assert_post_and_space_scores(0, 0);
+            assert_eq!(reputation_of(ACCOUNT1), 1);
         });
     }
 
     #[test]
-    fn follow_space_should_fail_when_trying_to_follow_hidden_space() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_update_space(
-                None,
-                None,
-                Some(space_update(None, None, Some(true)))
-            ));
+    fn share_should_change_original_post_scores_until_deleted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // PostId 2
 
-            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::CannotFollowHiddenSpace);
+            assert_post_and_space_scores(7, 7);
+            assert_eq!(reputation_of(ACCOUNT1), 8);
+
+            assert_ok!(_delete_post(Some(Origin::signed(ACCOUNT2)), Some(POST2)));
+
+            assert_post_and_space_scores(0, 0);
+            assert_eq!(reputation_of(ACCOUNT1), 1);
         });
     }
 
     #[test]
-    fn unfollow_space_should_work() {
+    fn follow_space_should_change_scores_until_unfollowed() {
         ExtBuilder::build_with_space().execute_with(|| {
             assert_ok!(_default_follow_space());
-            // Follow SpaceId 1 by ACCOUNT2
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, 7);
+            assert_eq!(reputation_of(ACCOUNT1), 8);
+
             assert_ok!(_default_unfollow_space());
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
-            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
-            assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1]);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, 0);
+            assert_eq!(reputation_of(ACCOUNT1), 1);
         });
     }
 
     #[test]
-    fn unfollow_space_should_fail_when_space_not_found() {
-        ExtBuilder::build_with_space_follow_no_space().execute_with(|| {
-            assert_noop!(_default_unfollow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+    fn follow_account_should_change_reputation_until_unfollowed() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            assert_eq!(reputation_of(ACCOUNT1), 4);
+
+            assert_ok!(_default_unfollow_account());
+            assert_eq!(reputation_of(ACCOUNT1), 1);
         });
     }
 
     #[test]
-    fn unfollow_space_should_fail_when_account_is_not_space_follower_yet() {
+    fn space_reaction_should_change_scores_until_deleted() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_default_unfollow_space(), SpaceFollowsError::<TestRuntime>::NotSpaceFollower);
-        });
-    }
+            assert_ok!(_create_reaction(None, None, None)); // ReactionId 1
 
-// Account following tests
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, 5);
+            assert_eq!(reputation_of(ACCOUNT1), 6);
 
-    #[test]
-    fn follow_account_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_update_reaction(None, None, REACTION1, Some(reaction_downvote())));
 
-            assert_eq!(ProfileFollows::accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1]);
-            assert_eq!(ProfileFollows::account_followers(ACCOUNT1), vec![ACCOUNT2]);
-            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), true);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, -3);
+            assert_eq!(reputation_of(ACCOUNT1), 1);
+
+            assert_ok!(_delete_reaction(None, None, REACTION1));
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, 0);
+            assert_eq!(reputation_of(ACCOUNT1), 1);
+            assert!(Scores::space_score_change_by_reaction((ACCOUNT2, SPACE1)).is_none());
         });
     }
 
     #[test]
-    fn follow_account_should_fail_when_account_tries_to_follow_themself() {
+    fn account_reaction_should_change_reputation_until_deleted() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_follow_account(
-                None,
-                Some(ACCOUNT2)
-            ), ProfileFollowsError::<TestRuntime>::AccountCannotFollowItself);
+            assert_ok!(_create_reaction(None, Some(ReactionTarget::Account(ACCOUNT1)), None)); // ReactionId 1
+            assert_eq!(reputation_of(ACCOUNT1), 4);
+
+            assert_ok!(_delete_reaction(None, Some(ReactionTarget::Account(ACCOUNT1)), REACTION1));
+            assert_eq!(reputation_of(ACCOUNT1), 1);
         });
     }
 
+// Reputation thresholds tests
+
+    fn set_reputation(account: AccountId, reputation: u32) {
+        let mut social_account = Profiles::get_or_new_social_account(account);
+        social_account.reputation = reputation;
+        SocialAccountById::<TestRuntime>::insert(account, social_account);
+    }
+
+    /// Default permissions, where a given permission is granted only by a reputation threshold.
+    fn permissions_where_reputation_grants(permission: SP, threshold: u32) -> SpacePermissions {
+        let mut default_permissions = DefaultSpacePermissions::get();
+        default_permissions.everyone = default_permissions.everyone
+          .map(|mut permissions| {
+              permissions.remove(&permission);
+              permissions
+          });
+        default_permissions.reputation_thresholds = Some(vec![(permission, threshold)].into_iter().collect());
+
+        default_permissions
+    }
+
     #[test]
-    fn follow_account_should_fail_when_account_is_already_following_account() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
+    fn create_post_should_work_when_reputation_reaches_threshold() {
+        ExtBuilder::build_with_space_and_custom_permissions(
+            permissions_where_reputation_grants(SP::CreatePosts, 10)
+        ).execute_with(|| {
+            set_reputation(ACCOUNT2, 10);
 
-            assert_noop!(_default_follow_account(), ProfileFollowsError::<TestRuntime>::AlreadyAccountFollower);
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            ));
         });
     }
 
     #[test]
-    fn unfollow_account_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
-            // Follow ACCOUNT1 by ACCOUNT2
-            assert_ok!(_default_unfollow_account());
+    fn create_post_should_fail_when_reputation_is_below_threshold() {
+        ExtBuilder::build_with_space_and_custom_permissions(
+            permissions_where_reputation_grants(SP::CreatePosts, 10)
+        ).execute_with(|| {
+            set_reputation(ACCOUNT2, 9);
 
-            assert!(ProfileFollows::accounts_followed_by_account(ACCOUNT2).is_empty());
-            assert!(ProfileFollows::account_followers(ACCOUNT1).is_empty());
-            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
         });
     }
 
     #[test]
-    fn unfollow_account_should_fail_when_account_tries_to_unfollow_themself() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_unfollow_account(
+    fn downvote_should_fail_when_forbidden_even_if_reputation_reaches_threshold() {
+        let mut permissions = permissions_where_reputation_grants(SP::Downvote, 1);
+        permissions.none = Some(vec![SP::Downvote].into_iter().collect());
+
+        ExtBuilder::build_with_space_and_custom_permissions(permissions).execute_with(|| {
+            assert_ok!(_create_default_post());
+
+            assert_noop!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
                 None,
-                Some(ACCOUNT2)
-            ), ProfileFollowsError::<TestRuntime>::AccountCannotUnfollowItself);
+                Some(reaction_downvote())
+            ), ReactionsError::<TestRuntime>::NoPermissionToDownvote);
         });
     }
 
     #[test]
-    fn unfollow_account_should_fail_when_account_is_not_following_another_account_yet() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
-            assert_ok!(_default_unfollow_account());
+    fn create_comment_should_check_reputation_threshold_of_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post_permissions(
+                None,
+                None,
+                Some(Some(permissions_where_reputation_grants(SP::CreateComments, 5)))
+            ));
+
+            assert_noop!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreateComments);
 
-            assert_noop!(_default_unfollow_account(), ProfileFollowsError::<TestRuntime>::NotAccountFollower);
+            set_reputation(ACCOUNT2, 5);
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            ));
         });
     }
 
@@ -3372,4 +6466,452 @@ mod tests {
             ), SpaceOwnershipError::<TestRuntime>::NotAllowedToRejectOwnershipTransfer); // Rejecting a transfer from ACCOUNT2
         });
     }
+
+// Bookmarks tests
+
+    const BOOKMARK_LIST1: BookmarkListId = 1;
+    const BOOKMARK_LIST2: BookmarkListId = 2;
+
+    fn bookmark_list_name() -> Vec<u8> {
+        b"Read later".to_vec()
+    }
+
+    fn _create_default_bookmark_list() -> DispatchResult {
+        _create_bookmark_list(None, None, None)
+    }
+
+    fn _create_bookmark_list(
+        origin: Option<Origin>,
+        name: Option<Vec<u8>>,
+        is_public: Option<bool>,
+    ) -> DispatchResult {
+        Bookmarks::create_bookmark_list(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            name.unwrap_or_else(bookmark_list_name),
+            is_public.unwrap_or(true),
+        )
+    }
+
+    fn _update_bookmark_list(
+        origin: Option<Origin>,
+        list_id: Option<BookmarkListId>,
+        update: BookmarkListUpdate,
+    ) -> DispatchResult {
+        Bookmarks::update_bookmark_list(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            list_id.unwrap_or(BOOKMARK_LIST1),
+            update,
+        )
+    }
+
+    fn _delete_bookmark_list(origin: Option<Origin>, list_id: Option<BookmarkListId>) -> DispatchResult {
+        Bookmarks::delete_bookmark_list(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            list_id.unwrap_or(BOOKMARK_LIST1),
+        )
+    }
+
+    fn _add_post_to_bookmark_list(
+        origin: Option<Origin>,
+        list_id: Option<BookmarkListId>,
+        post_id: Option<PostId>,
+    ) -> DispatchResult {
+        Bookmarks::add_post_to_bookmark_list(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            list_id.unwrap_or(BOOKMARK_LIST1),
+            post_id.unwrap_or(POST1),
+        )
+    }
+
+    fn _remove_post_from_bookmark_list(
+        origin: Option<Origin>,
+        list_id: Option<BookmarkListId>,
+        post_id: Option<PostId>,
+    ) -> DispatchResult {
+        Bookmarks::remove_post_from_bookmark_list(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            list_id.unwrap_or(BOOKMARK_LIST1),
+            post_id.unwrap_or(POST1),
+        )
+    }
+
+    fn _reorder_bookmark_list(
+        origin: Option<Origin>,
+        list_id: Option<BookmarkListId>,
+        post_ids: Vec<PostId>,
+    ) -> DispatchResult {
+        Bookmarks::reorder_bookmark_list(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            list_id.unwrap_or(BOOKMARK_LIST1),
+            post_ids,
+        )
+    }
+
+    /// Creates a bookmark list by ACCOUNT2 with POST1 and POST2 saved to it.
+    fn create_bookmark_list_with_two_posts() {
+        assert_ok!(_create_default_post()); // PostId 2
+        assert_ok!(_create_default_bookmark_list()); // BookmarkListId 1
+        assert_ok!(_add_post_to_bookmark_list(None, None, Some(POST1)));
+        assert_ok!(_add_post_to_bookmark_list(None, None, Some(POST2)));
+    }
+
+    fn bookmarked_post_ids(list_id: BookmarkListId) -> Vec<PostId> {
+        Bookmarks::get_bookmarked_posts(list_id, 0, 10).iter().map(|post| post.id).collect()
+    }
+
+    #[test]
+    fn create_bookmark_list_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_bookmark_list()); // BookmarkListId 1
+
+            let list = Bookmarks::bookmark_list_by_id(BOOKMARK_LIST1).unwrap();
+            assert_eq!(list.owner, ACCOUNT2);
+            assert_eq!(list.name, bookmark_list_name());
+            assert!(list.is_public);
+
+            assert_eq!(Bookmarks::bookmark_list_ids_by_account(ACCOUNT2), vec![BOOKMARK_LIST1]);
+            assert_eq!(Bookmarks::next_bookmark_list_id(), BOOKMARK_LIST2);
+        });
+    }
+
+    #[test]
+    fn create_bookmark_list_should_fail_when_name_is_too_long() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _create_bookmark_list(None, Some(vec![b'a'; 21]), None),
+                BookmarksError::<TestRuntime>::BookmarkListNameIsTooLong
+            );
+        });
+    }
+
+    #[test]
+    fn create_bookmark_list_should_fail_when_account_has_too_many_lists() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_bookmark_list());
+            assert_ok!(_create_default_bookmark_list());
+
+            assert_noop!(
+                _create_default_bookmark_list(),
+                BookmarksError::<TestRuntime>::TooManyBookmarkLists
+            );
+        });
+    }
+
+    #[test]
+    fn update_bookmark_list_should_fail_when_not_list_owner() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_bookmark_list());
+
+            assert_noop!(
+                _update_bookmark_list(
+                    Some(Origin::signed(ACCOUNT1)),
+                    None,
+                    BookmarkListUpdate { name: None, is_public: Some(false) }
+                ),
+                BookmarksError::<TestRuntime>::NotBookmarkListOwner
+            );
+        });
+    }
+
+    #[test]
+    fn delete_bookmark_list_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_bookmark_list_with_two_posts();
+
+            assert_ok!(_delete_bookmark_list(None, None));
+
+            assert!(Bookmarks::bookmark_list_by_id(BOOKMARK_LIST1).is_none());
+            assert!(Bookmarks::post_ids_by_bookmark_list_id(BOOKMARK_LIST1).is_empty());
+            assert!(Bookmarks::bookmark_list_ids_by_account(ACCOUNT2).is_empty());
+        });
+    }
+
+    #[test]
+    fn add_post_to_bookmark_list_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_bookmark_list_with_two_posts();
+
+            assert_eq!(Bookmarks::post_ids_by_bookmark_list_id(BOOKMARK_LIST1), vec![POST1, POST2]);
+            assert_eq!(bookmarked_post_ids(BOOKMARK_LIST1), vec![POST1, POST2]);
+        });
+    }
+
+    #[test]
+    fn add_post_to_bookmark_list_should_fail_when_post_already_bookmarked() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_bookmark_list());
+            assert_ok!(_add_post_to_bookmark_list(None, None, None));
+
+            assert_noop!(
+                _add_post_to_bookmark_list(None, None, None),
+                BookmarksError::<TestRuntime>::PostAlreadyBookmarked
+            );
+        });
+    }
+
+    #[test]
+    fn add_post_to_bookmark_list_should_fail_when_list_is_full() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_bookmark_list_with_two_posts();
+            assert_ok!(_create_default_post()); // PostId 3
+
+            assert_noop!(
+                _add_post_to_bookmark_list(None, None, Some(POST3)),
+                BookmarksError::<TestRuntime>::TooManyPostsInBookmarkList
+            );
+        });
+    }
+
+    #[test]
+    fn add_post_to_bookmark_list_should_fail_when_not_list_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_bookmark_list());
+
+            assert_noop!(
+                _add_post_to_bookmark_list(Some(Origin::signed(ACCOUNT1)), None, None),
+                BookmarksError::<TestRuntime>::NotBookmarkListOwner
+            );
+        });
+    }
+
+    #[test]
+    fn remove_post_from_bookmark_list_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_bookmark_list_with_two_posts();
+
+            assert_ok!(_remove_post_from_bookmark_list(None, None, Some(POST1)));
+            assert_eq!(Bookmarks::post_ids_by_bookmark_list_id(BOOKMARK_LIST1), vec![POST2]);
+
+            assert_noop!(
+                _remove_post_from_bookmark_list(None, None, Some(POST1)),
+                BookmarksError::<TestRuntime>::PostNotBookmarked
+            );
+        });
+    }
+
+    #[test]
+    fn reorder_bookmark_list_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_bookmark_list_with_two_posts();
+
+            assert_ok!(_reorder_bookmark_list(None, None, vec![POST2, POST1]));
+            assert_eq!(bookmarked_post_ids(BOOKMARK_LIST1), vec![POST2, POST1]);
+        });
+    }
+
+    #[test]
+    fn reorder_bookmark_list_should_fail_when_posts_differ() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_bookmark_list_with_two_posts();
+
+            assert_noop!(
+                _reorder_bookmark_list(None, None, vec![POST2]),
+                BookmarksError::<TestRuntime>::InvalidPostsOrder
+            );
+            assert_noop!(
+                _reorder_bookmark_list(None, None, vec![POST2, POST3]),
+                BookmarksError::<TestRuntime>::InvalidPostsOrder
+            );
+        });
+    }
+
+    #[test]
+    fn get_bookmark_lists_by_account_should_skip_private_lists() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_bookmark_list()); // BookmarkListId 1
+            assert_ok!(_create_bookmark_list(None, None, Some(false))); // BookmarkListId 2
+
+            let list_ids = |only_public: bool| -> Vec<BookmarkListId> {
+                Bookmarks::get_bookmark_lists_by_account(ACCOUNT2, only_public)
+                    .iter()
+                    .map(|list| list.id)
+                    .collect()
+            };
+
+            assert_eq!(list_ids(true), vec![BOOKMARK_LIST1]);
+            assert_eq!(list_ids(false), vec![BOOKMARK_LIST1, BOOKMARK_LIST2]);
+        });
+    }
+
+    #[test]
+    fn get_bookmark_lists_by_account_should_not_count_deleted_posts() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_bookmark_list_with_two_posts();
+            assert_ok!(_delete_default_post());
+
+            let lists = Bookmarks::get_bookmark_lists_by_account(ACCOUNT2, false);
+            assert_eq!(lists[0].posts_count, 1);
+            assert_eq!(bookmarked_post_ids(BOOKMARK_LIST1), vec![POST2]);
+
+            // A deleted post can still be removed from a list
+            assert_ok!(_remove_post_from_bookmark_list(None, None, Some(POST1)));
+            assert_eq!(Bookmarks::post_ids_by_bookmark_list_id(BOOKMARK_LIST1), vec![POST2]);
+        });
+    }
+
+// Notifications tests
+
+    const NOTIFICATION1: NotificationId = 1;
+    const NOTIFICATION2: NotificationId = 2;
+
+    fn _mark_notifications_as_read(origin: Option<Origin>, last_read_id: NotificationId) -> DispatchResult {
+        Notifications::mark_notifications_as_read(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            last_read_id,
+        )
+    }
+
+    /// Kinds of notifications of a given account, starting from the latest ones.
+    fn notification_kinds(account: AccountId) -> Vec<NotificationKind> {
+        Notifications::get_notifications(account, 0, 10)
+            .iter()
+            .map(|notification| notification.kind)
+            .collect()
+    }
+
+    #[test]
+    fn create_post_should_notify_mentioned_accounts() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_mentions(None, None, vec![ACCOUNT2, ACCOUNT2, ACCOUNT1]));
+
+            assert_eq!(Posts::mentioned_accounts_by_post_id(POST1), vec![ACCOUNT2, ACCOUNT1]);
+            assert_eq!(notification_kinds(ACCOUNT2), vec![NotificationKind::Mention(POST1)]);
+
+            // An author is not notified about their own mention
+            assert!(notification_kinds(ACCOUNT1).is_empty());
+
+            let notification = Notifications::notification_by_id(NOTIFICATION1).unwrap();
+            assert_eq!(notification.created.account, ACCOUNT1);
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_with_too_many_mentions() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_post_with_mentions(None, None, vec![ACCOUNT1, ACCOUNT2, ACCOUNT3]),
+                PostsError::<TestRuntime>::TooManyMentions
+            );
+        });
+    }
+
+    #[test]
+    fn create_comment_should_notify_owner_of_replied_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 2
+            assert_eq!(notification_kinds(ACCOUNT1), vec![NotificationKind::Reply(POST2, POST1)]);
+
+            // The owner of a replied comment is notified about a reply, but not about a mention
+            assert_ok!(_create_post_with_mentions(
+                None,
+                Some(extension_comment(Some(POST2), POST1)),
+                vec![ACCOUNT2]
+            )); // PostId 3
+            assert_eq!(notification_kinds(ACCOUNT2), vec![NotificationKind::Reply(POST3, POST2)]);
+        });
+    }
+
+    #[test]
+    fn share_post_should_notify_original_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // PostId 2
+
+            assert_eq!(notification_kinds(ACCOUNT1), vec![NotificationKind::Share(POST2, POST1)]);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_notify_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            assert_eq!(notification_kinds(ACCOUNT1), vec![NotificationKind::Reaction(REACTION1, POST1)]);
+        });
+    }
+
+    #[test]
+    fn get_notifications_should_skip_notifications_about_deleted_posts() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_mentions(None, None, vec![ACCOUNT2])); // PostId 1
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT1)), Some(ACCOUNT2)));
+            assert_ok!(_delete_default_post());
+
+            assert_eq!(notification_kinds(ACCOUNT2), vec![NotificationKind::Follow]);
+            assert_eq!(Notifications::get_unread_notifications_count(ACCOUNT2), 1);
+        });
+    }
+
+    #[test]
+    fn follow_account_should_notify_followed_account() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account()); // ACCOUNT2 follows ACCOUNT1
+
+            assert_eq!(notification_kinds(ACCOUNT1), vec![NotificationKind::Follow]);
+            assert_eq!(Notifications::notification_by_id(NOTIFICATION1).unwrap().created.account, ACCOUNT2);
+        });
+    }
+
+    #[test]
+    fn inbox_should_keep_only_latest_notifications() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            for _ in 0..4 {
+                assert_ok!(_create_post_with_mentions(None, None, vec![ACCOUNT2]));
+            }
+
+            // MaxNotificationsPerAccount is 3, so the oldest notification is removed
+            assert_eq!(Notifications::notification_ids_by_account(ACCOUNT2), vec![2, 3, 4]);
+            assert!(Notifications::notification_by_id(NOTIFICATION1).is_none());
+        });
+    }
+
+    #[test]
+    fn mark_notifications_as_read_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_mentions(None, None, vec![ACCOUNT2]));
+            assert_ok!(_create_post_with_mentions(None, None, vec![ACCOUNT2]));
+            assert_eq!(Notifications::get_unread_notifications_count(ACCOUNT2), 2);
+
+            assert_ok!(_mark_notifications_as_read(None, NOTIFICATION1));
+
+            assert_eq!(Notifications::last_read_notification_id_by_account(ACCOUNT2), NOTIFICATION1);
+            assert_eq!(Notifications::get_unread_notifications_count(ACCOUNT2), 1);
+
+            let read_flags: Vec<(NotificationId, bool)> = Notifications::get_notifications(ACCOUNT2, 0, 10)
+                .iter()
+                .map(|notification| (notification.id, notification.is_read))
+                .collect();
+            assert_eq!(read_flags, vec![(NOTIFICATION2, false), (NOTIFICATION1, true)]);
+        });
+    }
+
+    #[test]
+    fn mark_notifications_as_read_should_fail_when_cursor_does_not_move_forward() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_mentions(None, None, vec![ACCOUNT2]));
+            assert_ok!(_mark_notifications_as_read(None, NOTIFICATION1));
+
+            assert_noop!(
+                _mark_notifications_as_read(None, NOTIFICATION1),
+                NotificationsError::<TestRuntime>::NotificationsAlreadyRead
+            );
+        });
+    }
+
+    #[test]
+    fn mark_notifications_as_read_should_fail_when_notification_not_in_inbox() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post_with_mentions(None, None, vec![ACCOUNT2]));
+
+            assert_noop!(
+                _mark_notifications_as_read(Some(Origin::signed(ACCOUNT3)), NOTIFICATION1),
+                NotificationsError::<TestRuntime>::NotificationNotFound
+            );
+        });
+    }
 }
@@ -35,8 +35,10 @@ frame_support::construct_runtime!(
         System: system::{Pallet, Call, Config, Storage, Event<T>},
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
         Moderation: moderation::{Pallet, Call, Storage, Event<T>},
 		Posts: pallet_posts::{Pallet, Call, Storage, Event<T>},
+        ProfileFollows: pallet_profile_follows::{Pallet, Call, Storage, Event<T>},
         Profiles: pallet_profiles::{Pallet, Call, Storage, Event<T>},
 		Roles: pallet_roles::{Pallet, Call, Storage, Event<T>},
 		SpaceFollows: pallet_space_follows::{Pallet, Call, Storage, Event<T>},
@@ -128,40 +130,105 @@ impl pallet_spaces::Config for Test {
     type IsAccountBlocked = Moderation;
     type IsContentBlocked = Moderation;
     type HandleDeposit = ();
+    type ProfileHandles = Profiles;
+    type SpaceCleanupHandler = ();
+    type MaxSpaceCleanupItemsPerBlock = ();
+}
+
+parameter_types! {
+    pub const MaxFollowedSpaces: u32 = 10;
+    pub const MaxFollowedAccounts: u32 = 10;
 }
 
 impl pallet_space_follows::Config for Test {
     type Event = Event;
     type BeforeSpaceFollowed = ();
     type BeforeSpaceUnfollowed = ();
+    type MaxFollowedSpaces = MaxFollowedSpaces;
+}
+
+impl pallet_profile_follows::Config for Test {
+    type Event = Event;
+    type BeforeAccountFollowed = ();
+    type BeforeAccountUnfollowed = ();
+    type AfterAccountFollowed = ();
+    type MaxFollowedAccounts = MaxFollowedAccounts;
 }
 
 parameter_types! {
     pub const MaxCommentDepth: u32 = 10;
+    pub const MaxRepliesToDelete: u32 = 100;
+    pub const MaxPinnedPostsPerSpace: u32 = 10;
+    pub const MaxTagsPerPost: u32 = 10;
+    pub const MaxTagLen: u32 = 32;
+    pub const MaxMentionsPerPost: u32 = 20;
+    pub const MaxExpiredPostsPerBlock: u32 = 100;
+    pub const MaxPollOptions: u8 = 10;
+    pub const MaxQuoteDepth: u32 = 5;
+    pub const ScheduledPostDeposit: u64 = 10;
+    pub const MaxScheduledPostsPerAccount: u32 = 10;
 }
 
 impl pallet_posts::Config for Test {
     type Event = Event;
     type MaxCommentDepth = MaxCommentDepth;
+    type MaxRepliesToDelete = MaxRepliesToDelete;
+    type MaxPinnedPostsPerSpace = MaxPinnedPostsPerSpace;
+    type MaxTagsPerPost = MaxTagsPerPost;
+    type MaxTagLen = MaxTagLen;
+    type MaxMentionsPerPost = MaxMentionsPerPost;
+    type MaxExpiredPostsPerBlock = MaxExpiredPostsPerBlock;
+    type MaxPollOptions = MaxPollOptions;
+    type MaxQuoteDepth = MaxQuoteDepth;
+    type ScheduledPostDeposit = ScheduledPostDeposit;
+    type MaxScheduledPostsPerAccount = MaxScheduledPostsPerAccount;
+    type AfterPostCreated = ();
     type AfterPostUpdated = ();
     type IsPostBlocked = Moderation;
+    type IsAccountBlockedBy = ();
+    type PostScores = ();
+    type ScheduledCall = Call;
+    type Scheduler = Scheduler;
+    type PalletsOrigin = OriginCaller;
+}
+
+parameter_types! {
+    pub const MaximumSchedulerWeight: u64 = 1_000_000_000;
+    pub const MaxScheduledPerBlock: u32 = 10;
+}
+
+impl pallet_scheduler::Config for Test {
+    type Event = Event;
+    type Origin = Origin;
+    type PalletsOrigin = OriginCaller;
+    type Call = Call;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = system::EnsureRoot<u64>;
+    type MaxScheduledPerBlock = MaxScheduledPerBlock;
+    type WeightInfo = ();
 }
 
 parameter_types! {
     pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+    pub const MaxParentSpacesToCheck: u32 = 5;
 }
 
 impl pallet_roles::Config for Test {
     type Event = Event;
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+    type MaxParentSpacesToCheck = MaxParentSpacesToCheck;
     type Spaces = Spaces;
     type SpaceFollows = SpaceFollows;
+    type Reputation = Profiles;
     type IsAccountBlocked = Moderation;
     type IsContentBlocked = Moderation;
 }
 
 impl pallet_profiles::Config for Test {
     type Event = Event;
+    type Currency = Balances;
+    type HandleDeposit = ();
+    type SpaceHandles = Spaces;
     type AfterProfileUpdated = ();
 }
 
@@ -318,6 +385,8 @@ pub(crate) fn create_space_and_post() {
         Some(SPACE1),
         PostExtension::RegularPost,
         valid_content_ipfs(),
+        vec![],
+        vec![],
     ));
 }
 
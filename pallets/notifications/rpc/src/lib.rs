@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+
+use pallet_notifications::rpc::FlatNotification;
+use pallet_utils::rpc::map_rpc_error;
+pub use notifications_runtime_api::NotificationsApi as NotificationsRuntimeApi;
+
+#[rpc]
+pub trait NotificationsApi<BlockHash, AccountId, BlockNumber> {
+    #[rpc(name = "notifications_getNotifications")]
+    fn get_notifications(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatNotification<AccountId, BlockNumber>>>;
+
+    #[rpc(name = "notifications_getUnreadNotificationsCount")]
+    fn get_unread_notifications_count(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+    ) -> Result<u32>;
+}
+
+pub struct Notifications<C, M> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> Notifications<C, M> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId, BlockNumber> NotificationsApi<<Block as BlockT>::Hash, AccountId, BlockNumber>
+    for Notifications<C, Block>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    BlockNumber: Codec,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: NotificationsRuntimeApi<Block, AccountId, BlockNumber>,
+{
+    fn get_notifications(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatNotification<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_notifications(&at, account, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_unread_notifications_count(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+    ) -> Result<u32> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_unread_notifications_count(&at, account);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+}
@@ -0,0 +1,238 @@
+//! # Notifications Module
+//!
+//! The Notifications module maintains a notification inbox for every account.
+//! An account is notified when it is mentioned in a post, when someone replies to its post
+//! or comment, shares or reacts to its post, or follows it.
+//!
+//! Notifications are added by the hooks of other pallets, so this module has no extrinsics
+//! to create them. An account can only move its read cursor forward.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    dispatch::DispatchResult,
+    traits::Get
+};
+use frame_system::{self as system, ensure_signed};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use pallet_posts::{AfterPostCreated, Module as Posts, Post, PostExtension};
+use pallet_profile_follows::AfterAccountFollowed;
+use pallet_reactions::{AfterReactionCreated, ReactionId, ReactionTarget};
+use pallet_utils::{WhoAndWhen, PostId};
+
+pub mod rpc;
+
+pub type NotificationId = u64;
+
+pub const FIRST_NOTIFICATION_ID: u64 = 1;
+
+/// The reason why an account was notified.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum NotificationKind {
+    /// An account was mentioned in a given post.
+    Mention(PostId),
+    /// Someone replied to a post of an account: (reply id, replied post id).
+    Reply(PostId, PostId),
+    /// Someone shared a post of an account: (sharing post id, original post id).
+    Share(PostId, PostId),
+    /// Someone reacted to a post of an account.
+    Reaction(ReactionId, PostId),
+    /// Someone followed an account.
+    Follow,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct Notification<T: Config> {
+
+    /// Unique sequential identifier of a notification. Examples of notification ids: `1`, `2`,
+    /// `3`, and so on.
+    pub id: NotificationId,
+
+    /// An account whose action caused this notification, and when it happened.
+    pub created: WhoAndWhen<T>,
+
+    pub kind: NotificationKind,
+}
+
+/// The pallet's configuration trait.
+pub trait Config: system::Config
+    + pallet_utils::Config
+    + pallet_posts::Config
+    + pallet_profile_follows::Config
+    + pallet_reactions::Config
+{
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+
+    /// The max number of notifications kept in the inbox of an account.
+    /// The oldest notifications are removed when this limit is reached.
+    type MaxNotificationsPerAccount: Get<u32>;
+}
+
+// This pallet's storage items.
+decl_storage! {
+    trait Store for Module<T: Config> as NotificationsModule {
+
+        /// The next notification id.
+        pub NextNotificationId get(fn next_notification_id): NotificationId = FIRST_NOTIFICATION_ID;
+
+        pub NotificationById get(fn notification_by_id):
+            map hasher(twox_64_concat) NotificationId => Option<Notification<T>>;
+
+        /// Ids of notifications of a given account, from the oldest to the latest one.
+        pub NotificationIdsByAccount get(fn notification_ids_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Vec<NotificationId>;
+
+        /// The id of the latest notification that a given account has read.
+        /// All notifications with lower ids are considered as read too.
+        pub LastReadNotificationIdByAccount get(fn last_read_notification_id_by_account):
+            map hasher(blake2_128_concat) T::AccountId => NotificationId;
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as system::Config>::AccountId,
+    {
+        NotificationsRead(AccountId, NotificationId),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// Notification was not found in the inbox of an account.
+        NotificationNotFound,
+        /// Notifications up to a given id are already read.
+        NotificationsAlreadyRead,
+    }
+}
+
+decl_module! {
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    const MaxNotificationsPerAccount: u32 = T::MaxNotificationsPerAccount::get();
+
+    // Initializing errors
+    type Error = Error<T>;
+
+    // Initializing events
+    fn deposit_event() = default;
+
+    /// Move the read cursor of the caller to a given notification,
+    /// marking it and all the previous notifications as read.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
+    pub fn mark_notifications_as_read(origin, last_read_id: NotificationId) -> DispatchResult {
+      let account = ensure_signed(origin)?;
+
+      ensure!(
+        last_read_id > Self::last_read_notification_id_by_account(&account),
+        Error::<T>::NotificationsAlreadyRead
+      );
+      ensure!(
+        Self::notification_ids_by_account(&account).contains(&last_read_id),
+        Error::<T>::NotificationNotFound
+      );
+
+      <LastReadNotificationIdByAccount<T>>::insert(&account, last_read_id);
+
+      Self::deposit_event(RawEvent::NotificationsRead(account, last_read_id));
+      Ok(())
+    }
+  }
+}
+
+impl<T: Config> Module<T> {
+
+    /// Add a notification to the inbox of `account`. Accounts are not notified about their own actions.
+    pub fn add_notification(account: T::AccountId, actor: T::AccountId, kind: NotificationKind) {
+        if account == actor {
+            return;
+        }
+
+        let notification_id = Self::next_notification_id();
+        let notification = Notification::<T> {
+            id: notification_id,
+            created: WhoAndWhen::<T>::new(actor),
+            kind,
+        };
+
+        let mut notification_ids = Self::notification_ids_by_account(&account);
+        notification_ids.push(notification_id);
+
+        let max_notifications = T::MaxNotificationsPerAccount::get() as usize;
+        if notification_ids.len() > max_notifications {
+            let overflow = notification_ids.len() - max_notifications;
+            for removed_id in notification_ids.drain(..overflow) {
+                <NotificationById<T>>::remove(removed_id);
+            }
+        }
+
+        <NotificationById<T>>::insert(notification_id, notification);
+        <NotificationIdsByAccount<T>>::insert(&account, notification_ids);
+        NextNotificationId::mutate(|n| { *n += 1; });
+    }
+
+    fn notify_post_owner(post_id: PostId, actor: T::AccountId, kind: NotificationKind) {
+        if let Some(post) = Posts::<T>::post_by_id(post_id) {
+            Self::add_notification(post.owner, actor, kind);
+        }
+    }
+}
+
+impl<T: Config> AfterPostCreated<T> for Module<T> {
+    fn after_post_created(account: T::AccountId, post: &Post<T>) {
+        // An account that is notified about a reply or a share is not notified about a mention.
+        let mut notified_owner = None;
+
+        match post.extension {
+            PostExtension::RegularPost | PostExtension::Poll(_) => (),
+            PostExtension::Comment(comment_ext) => {
+                let replied_post_id = comment_ext.parent_id.unwrap_or(comment_ext.root_post_id);
+                if let Some(replied_post) = Posts::<T>::post_by_id(replied_post_id) {
+                    notified_owner = Some(replied_post.owner.clone());
+                    Self::add_notification(
+                        replied_post.owner, account.clone(), NotificationKind::Reply(post.id, replied_post_id)
+                    );
+                }
+            },
+            PostExtension::SharedPost(original_post_id) => {
+                if let Some(original_post) = Posts::<T>::post_by_id(original_post_id) {
+                    notified_owner = Some(original_post.owner.clone());
+                    Self::add_notification(
+                        original_post.owner, account.clone(), NotificationKind::Share(post.id, original_post_id)
+                    );
+                }
+            },
+        }
+
+        for mentioned_account in Posts::<T>::mentioned_accounts_by_post_id(post.id) {
+            if notified_owner.as_ref() != Some(&mentioned_account) {
+                Self::add_notification(mentioned_account, account.clone(), NotificationKind::Mention(post.id));
+            }
+        }
+    }
+}
+
+impl<T: Config> AfterReactionCreated<T> for Module<T> {
+    fn after_reaction_created(account: T::AccountId, target: &ReactionTarget<T::AccountId>, reaction_id: ReactionId) {
+        if let ReactionTarget::Post(post_id) = target {
+            Self::notify_post_owner(*post_id, account, NotificationKind::Reaction(reaction_id, *post_id));
+        }
+    }
+}
+
+impl<T: Config> AfterAccountFollowed<T> for Module<T> {
+    fn after_account_followed(follower: T::AccountId, following: T::AccountId) {
+        Self::add_notification(following, follower, NotificationKind::Follow);
+    }
+}
@@ -0,0 +1,87 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::*;
+
+use pallet_posts::Module as Posts;
+use pallet_utils::{PostId, rpc::FlatWhoAndWhen};
+
+use crate::{Config, Module, Notification, NotificationId, NotificationKind};
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FlatNotification<AccountId, BlockNumber> {
+    pub id: NotificationId,
+
+    #[cfg_attr(feature = "std", serde(flatten))]
+    pub who_and_when: FlatWhoAndWhen<AccountId, BlockNumber>,
+
+    pub kind: NotificationKind,
+
+    pub is_read: bool,
+}
+
+impl<T: Config> Module<T> {
+    fn flatten_notification(
+        notification: Notification<T>,
+        last_read_id: NotificationId,
+    ) -> FlatNotification<T::AccountId, T::BlockNumber> {
+        let Notification { id, created, kind } = notification;
+
+        FlatNotification {
+            id,
+            who_and_when: created.into(),
+            kind,
+            is_read: id <= last_read_id,
+        }
+    }
+
+    /// Notifications are not removed when the posts they refer to are deleted,
+    /// so such notifications are skipped when an inbox is read.
+    fn refers_to_existing_posts(kind: &NotificationKind) -> bool {
+        let post_exists = |post_id: &PostId| Posts::<T>::ensure_post_exists(*post_id).is_ok();
+
+        match kind {
+            NotificationKind::Mention(post_id) | NotificationKind::Reaction(_, post_id) =>
+                post_exists(post_id),
+            NotificationKind::Reply(post_id, replied_post_id) =>
+                post_exists(post_id) && post_exists(replied_post_id),
+            NotificationKind::Share(post_id, original_post_id) =>
+                post_exists(post_id) && post_exists(original_post_id),
+            NotificationKind::Follow => true,
+        }
+    }
+
+    /// Get the notifications of a given account, starting from the latest ones.
+    /// Notifications about deleted posts are skipped.
+    pub fn get_notifications(
+        account: T::AccountId,
+        offset: u64,
+        limit: u16,
+    ) -> Vec<FlatNotification<T::AccountId, T::BlockNumber>> {
+        let last_read_id = Self::last_read_notification_id_by_account(&account);
+
+        Self::notification_ids_by_account(&account)
+            .into_iter()
+            .rev()
+            .filter_map(Self::notification_by_id)
+            .filter(|notification| Self::refers_to_existing_posts(&notification.kind))
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|notification| Self::flatten_notification(notification, last_read_id))
+            .collect()
+    }
+
+    /// Get the number of unread notifications of a given account, except the ones about deleted posts.
+    pub fn get_unread_notifications_count(account: T::AccountId) -> u32 {
+        let last_read_id = Self::last_read_notification_id_by_account(&account);
+
+        Self::notification_ids_by_account(&account)
+            .into_iter()
+            .filter(|id| *id > last_read_id)
+            .filter_map(Self::notification_by_id)
+            .filter(|notification| Self::refers_to_existing_posts(&notification.kind))
+            .count() as u32
+    }
+}
@@ -26,6 +26,8 @@ parameter_types! {
       SP::Upvote,
       SP::Downvote,
       SP::Share,
+      SP::React,
+      SP::VoteInPolls,
     ].into_iter().collect()),
 
     // Followers can do everything that everyone else can.
@@ -56,6 +58,11 @@ parameter_types! {
       SP::UpdateEntityStatus,
 
       SP::UpdateSpaceSettings,
+
+      SP::PinPosts,
     ].into_iter().collect()),
+
+    // No permissions gated by reputation by default
+    reputation_thresholds: None,
   };
 }
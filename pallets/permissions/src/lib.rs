@@ -10,7 +10,7 @@ use frame_support::{
 };
 use sp_runtime::RuntimeDebug;
 use sp_std::{
-  collections::btree_set::BTreeSet,
+  collections::{btree_map::BTreeMap, btree_set::BTreeSet},
   prelude::*
 };
 use frame_system::{self as system};
@@ -18,6 +18,7 @@ use frame_system::{self as system};
 use pallet_utils::SpaceId;
 
 pub mod default_permissions;
+pub mod migrations;
 
 #[derive(Encode, Decode, Ord, PartialOrd, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -86,10 +87,22 @@ pub enum SpacePermission {
 
   /// Allows to update space settings across different pallets.
   UpdateSpaceSettings,
+
+  /// React with emojis on any post or comment in this space.
+  React,
+
+  /// Pin and unpin root posts in this space.
+  PinPosts,
+
+  /// Vote in polls in this space.
+  VoteInPolls,
 }
 
 pub type SpacePermissionSet = BTreeSet<SpacePermission>;
 
+/// A minimum reputation an account should have to be capable of performing a given permission.
+pub type ReputationThresholds = BTreeMap<SpacePermission, u32>;
+
 /// These are a set of built-in roles which can be given different permissions within a given space.
 /// For example: everyone can comment (`CreateComments`), but only followers can post
 /// (`CreatePosts`).
@@ -113,6 +126,11 @@ pub struct SpacePermissions {
   /// Space owner represents a set of permissions which are capable of being performed by an account
   /// that is a current owner of a given space.
   pub space_owner: Option<SpacePermissionSet>,
+
+  /// Reputation thresholds represent permissions which are capable of being performed by every
+  /// account whose reputation is greater than or equal to a given threshold. For example, this
+  /// allows only accounts with a reputation of at least 100 to `CreatePosts` in a given space.
+  pub reputation_thresholds: Option<ReputationThresholds>,
 }
 
 impl Default for SpacePermissions {
@@ -122,6 +140,7 @@ impl Default for SpacePermissions {
       everyone: None,
       follower: None,
       space_owner: None,
+      reputation_thresholds: None,
     }
   }
 }
@@ -131,7 +150,10 @@ pub struct SpacePermissionsContext {
   pub space_id: SpaceId,
   pub is_space_owner: bool,
   pub is_space_follower: bool,
-  pub space_perms: Option<SpacePermissions>
+  pub space_perms: Option<SpacePermissions>,
+  /// Permission overrides of a particular post in this space (if any).
+  /// They take precedence over the space permissions.
+  pub post_perms: Option<SpacePermissions>,
 }
 
 /// The pallet's configuration trait.
@@ -158,10 +180,10 @@ impl SpacePermission {
 
 impl<T: Config> Module<T> {
 
-  fn get_overrides_or_defaults(
-    overrides: Option<SpacePermissionSet>,
-    defaults: Option<SpacePermissionSet>
-  ) -> Option<SpacePermissionSet> {
+  fn get_overrides_or_defaults<Perms>(
+    overrides: Option<Perms>,
+    defaults: Option<Perms>
+  ) -> Option<Perms> {
 
     if overrides.is_some() {
       overrides
@@ -181,7 +203,30 @@ impl<T: Config> Module<T> {
       none: Self::get_overrides_or_defaults(overrides.none, defaults.none),
       everyone: Self::get_overrides_or_defaults(overrides.everyone, defaults.everyone),
       follower: Self::get_overrides_or_defaults(overrides.follower, defaults.follower),
-      space_owner: Self::get_overrides_or_defaults(overrides.space_owner, defaults.space_owner)
+      space_owner: Self::get_overrides_or_defaults(overrides.space_owner, defaults.space_owner),
+      reputation_thresholds: Self::get_overrides_or_defaults(
+        overrides.reputation_thresholds,
+        defaults.reputation_thresholds
+      ),
+    }
+  }
+
+  fn resolve_post_perms(
+    space_perms: SpacePermissions,
+    post_perms: Option<SpacePermissions>,
+  ) -> SpacePermissions {
+
+    let overrides = post_perms.unwrap_or_default();
+
+    SpacePermissions {
+      none: Self::get_overrides_or_defaults(overrides.none, space_perms.none),
+      everyone: Self::get_overrides_or_defaults(overrides.everyone, space_perms.everyone),
+      follower: Self::get_overrides_or_defaults(overrides.follower, space_perms.follower),
+      space_owner: Self::get_overrides_or_defaults(overrides.space_owner, space_perms.space_owner),
+      reputation_thresholds: Self::get_overrides_or_defaults(
+        overrides.reputation_thresholds,
+        space_perms.reputation_thresholds
+      ),
     }
   }
 
@@ -190,7 +235,10 @@ impl<T: Config> Module<T> {
     permission: SpacePermission,
   ) -> Option<bool> {
 
-    let perms_by_role = Self::resolve_space_perms(ctx.space_perms);
+    let perms_by_role = Self::resolve_post_perms(
+      Self::resolve_space_perms(ctx.space_perms),
+      ctx.post_perms
+    );
 
     // Check if this permission is forbidden:
     if permission.is_present_in_role(perms_by_role.none) {
@@ -211,6 +259,22 @@ impl<T: Config> Module<T> {
     None
   }
 
+  /// Get a minimum reputation an account should have to be capable of performing
+  /// a given permission, if it's set in the resolved space (or post) permissions.
+  pub fn get_reputation_threshold(
+    ctx: SpacePermissionsContext,
+    permission: &SpacePermission,
+  ) -> Option<u32> {
+
+    let perms_by_role = Self::resolve_post_perms(
+      Self::resolve_space_perms(ctx.space_perms),
+      ctx.post_perms
+    );
+
+    perms_by_role.reputation_thresholds
+      .and_then(|thresholds| thresholds.get(permission).copied())
+  }
+
   pub fn override_permissions(mut overrides: SpacePermissions) -> SpacePermissions {
     overrides.none = overrides.none.map(
       |mut none_permissions_set| {
@@ -0,0 +1,23 @@
+use super::*;
+
+/// `SpacePermissions` as they were stored before the `reputation_thresholds` field was added.
+/// It's used by the pallets that store `SpacePermissions` to migrate their storages.
+#[derive(Decode)]
+pub struct SpacePermissionsWithoutReputation {
+  none: Option<SpacePermissionSet>,
+  everyone: Option<SpacePermissionSet>,
+  follower: Option<SpacePermissionSet>,
+  space_owner: Option<SpacePermissionSet>,
+}
+
+impl From<SpacePermissionsWithoutReputation> for SpacePermissions {
+  fn from(old_perms: SpacePermissionsWithoutReputation) -> Self {
+    SpacePermissions {
+      none: old_perms.none,
+      everyone: old_perms.everyone,
+      follower: old_perms.follower,
+      space_owner: old_perms.space_owner,
+      reputation_thresholds: None,
+    }
+  }
+}
@@ -10,6 +10,8 @@ use frame_system::{self as system};
 use pallet_posts::{Post, PostUpdate, AfterPostUpdated};
 use pallet_utils::{WhoAndWhen, PostId};
 
+pub mod migrations;
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct PostHistoryRecord<T: Config> {
@@ -28,11 +30,31 @@ decl_storage! {
     trait Store for Module<T: Config> as PostHistoryModule {
         pub EditHistory get(fn edit_history):
             map hasher(twox_64_concat) PostId => Vec<PostHistoryRecord<T>>;
+
+        /// True if `EditHistory` storage is already migrated to records with the `tags` field in `old_data`.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub EditHistoryTagsMigrated: bool = false;
+    }
+    add_extra_genesis {
+      build(|_| {
+        EditHistoryTagsMigrated::put(true);
+      })
     }
 }
 
 decl_module! {
-  pub struct Module<T: Config> for enum Call where origin: T::Origin {}
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+      let mut final_weight = 0;
+
+      if !EditHistoryTagsMigrated::get() {
+        final_weight = migrations::add_tags_to_edit_history::<T>();
+      }
+
+      final_weight
+    }
+  }
 }
 
 impl<T: Config> PostHistoryRecord<T> {
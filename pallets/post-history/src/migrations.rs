@@ -0,0 +1,45 @@
+use frame_support::{storage::IterableStorageMap, traits::Get};
+use pallet_utils::{Content, SpaceId};
+
+use super::*;
+
+/// A `PostUpdate` struct as it was stored in the edit history before the `tags` field was added.
+#[derive(Decode)]
+struct PostUpdateWithoutTags {
+    space_id: Option<SpaceId>,
+    content: Option<Content>,
+    hidden: Option<bool>,
+}
+
+#[derive(Decode)]
+struct PostHistoryRecordWithoutTags<T: Config> {
+    edited: WhoAndWhen<T>,
+    old_data: PostUpdateWithoutTags,
+}
+
+pub fn add_tags_to_edit_history<T: Config>() -> frame_support::weights::Weight {
+    let mut posts_migrated = 0;
+
+    EditHistory::<T>::translate::<Vec<PostHistoryRecordWithoutTags<T>>, _>(|_, old_records| {
+        posts_migrated += 1;
+
+        Some(
+            old_records
+                .into_iter()
+                .map(|old_record| PostHistoryRecord {
+                    edited: old_record.edited,
+                    old_data: PostUpdate {
+                        space_id: old_record.old_data.space_id,
+                        content: old_record.old_data.content,
+                        hidden: old_record.old_data.hidden,
+                        tags: None,
+                    },
+                })
+                .collect()
+        )
+    });
+
+    EditHistoryTagsMigrated::put(true);
+
+    T::DbWeight::get().reads_writes(posts_migrated + 1, posts_migrated + 1)
+}
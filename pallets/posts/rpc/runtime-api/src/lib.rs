@@ -4,10 +4,11 @@ use codec::Codec;
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::vec::Vec;
 
-use pallet_posts::rpc::{FlatPost, FlatPostKind, RepliesByPostId};
+use pallet_posts::{Tag, rpc::{FlatPost, FlatPostKind, FlatTimeline, RepliesByPostId, TimelineCursor}};
 use pallet_utils::{PostId, SpaceId};
 
 sp_api::decl_runtime_apis! {
+    #[api_version(2)]
     pub trait PostsApi<AccountId, BlockNumber> where
         AccountId: Codec,
         BlockNumber: Codec
@@ -18,8 +19,16 @@ sp_api::decl_runtime_apis! {
 
         fn get_public_posts(kind_filter: Vec<FlatPostKind>, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>>;
 
+        #[changed_in(2)]
         fn get_public_posts_by_space_id(space_id: SpaceId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>>;
-    
+
+        fn get_public_posts_by_space_id(
+            space_id: SpaceId,
+            offset: u64,
+            limit: u16,
+            pinned_first: bool,
+        ) -> Vec<FlatPost<AccountId, BlockNumber>>;
+
         fn get_unlisted_posts_by_space_id(space_id: SpaceId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>>;
 
         fn get_public_post_ids_by_space_id(space_id: SpaceId) -> Vec<PostId>;
@@ -34,6 +43,33 @@ sp_api::decl_runtime_apis! {
 
         fn get_replies_by_parent_ids(parent_ids: Vec<PostId>, offset: u64, limit: u16) -> RepliesByPostId<AccountId, BlockNumber>;
 
+        fn get_post_ids_by_account(
+            account: AccountId,
+            kind_filter: Vec<FlatPostKind>,
+            offset: u64,
+            limit: u16,
+        ) -> Vec<PostId>;
+
+        fn get_posts_by_account(
+            account: AccountId,
+            kind_filter: Vec<FlatPostKind>,
+            offset: u64,
+            limit: u16,
+        ) -> Vec<FlatPost<AccountId, BlockNumber>>;
+
+        fn get_sharing_post_ids(post_id: PostId, offset: u64, limit: u16) -> Vec<PostId>;
+
+        fn get_sharing_posts(post_id: PostId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>>;
+
+        #[changed_in(2)]
         fn get_feed(account: AccountId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>>;
+
+        fn get_feed(account: AccountId, kind_filter: Vec<FlatPostKind>, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>>;
+
+        fn get_timeline(account: AccountId, cursor: Option<TimelineCursor<AccountId>>, limit: u16) -> FlatTimeline<AccountId, BlockNumber>;
+
+        fn get_post_ids_by_tag(tag: Tag, offset: u64, limit: u16) -> Vec<PostId>;
+
+        fn get_posts_by_tag(tag: Tag, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>>;
     }
 }
@@ -4,9 +4,9 @@ use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 
-use pallet_posts::rpc::{FlatPost, FlatPostKind, RepliesByPostId};
+use pallet_posts::{Tag, rpc::{FlatPost, FlatPostKind, FlatTimeline, RepliesByPostId, TimelineCursor}};
 use pallet_utils::{PostId, SpaceId, rpc::map_rpc_error};
 pub use posts_runtime_api::PostsApi as PostsRuntimeApi;
 
@@ -37,6 +37,7 @@ pub trait PostsApi<BlockHash, AccountId, BlockNumber> {
         space_id: SpaceId,
         offset: u64,
         limit: u16,
+        pinned_first: bool,
     ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>>;
 
     #[rpc(name = "posts_getUnlistedPostsBySpaceId")]
@@ -97,11 +98,77 @@ pub trait PostsApi<BlockHash, AccountId, BlockNumber> {
     #[rpc(name = "posts_nextPostId")]
     fn get_next_post_id(&self, at: Option<BlockHash>) -> Result<PostId>;
 
+    #[rpc(name = "posts_getPostIdsByAccount")]
+    fn get_post_ids_by_account(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+        kind_filter: Vec<FlatPostKind>,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<PostId>>;
+
+    #[rpc(name = "posts_getPostsByAccount")]
+    fn get_posts_by_account(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+        kind_filter: Vec<FlatPostKind>,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>>;
+
+    #[rpc(name = "posts_getSharingPostIds")]
+    fn get_sharing_post_ids(
+        &self,
+        at: Option<BlockHash>,
+        post_id: PostId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<PostId>>;
+
+    #[rpc(name = "posts_getSharingPosts")]
+    fn get_sharing_posts(
+        &self,
+        at: Option<BlockHash>,
+        post_id: PostId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>>;
+
     #[rpc(name = "posts_getFeed")]
     fn get_feed(
         &self,
         at: Option<BlockHash>,
         account: AccountId,
+        kind_filter: Vec<FlatPostKind>,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>>;
+
+    #[rpc(name = "posts_getTimeline")]
+    fn get_timeline(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+        cursor: Option<TimelineCursor<AccountId>>,
+        limit: u16,
+    ) -> Result<FlatTimeline<AccountId, BlockNumber>>;
+
+    #[rpc(name = "posts_getPostIdsByTag")]
+    fn get_post_ids_by_tag(
+        &self,
+        at: Option<BlockHash>,
+        tag: Tag,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<PostId>>;
+
+    #[rpc(name = "posts_getPostsByTag")]
+    fn get_posts_by_tag(
+        &self,
+        at: Option<BlockHash>,
+        tag: Tag,
         offset: u64,
         limit: u16,
     ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>>;
@@ -164,11 +231,22 @@ where
         space_id: u64,
         offset: u64,
         limit: u16,
+        pinned_first: bool,
     ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
 
-        let runtime_api_result = api.get_public_posts_by_space_id(&at, space_id, offset, limit);
+        // Runtimes before version 2 of the API don't support pinned posts.
+        let api_version = api.api_version::<dyn PostsRuntimeApi<Block, AccountId, BlockNumber>>(&at)
+            .map_err(map_rpc_error)?
+            .unwrap_or_default();
+        if api_version < 2 {
+            #[allow(deprecated)]
+            let runtime_api_result = api.get_public_posts_by_space_id_before_version_2(&at, space_id, offset, limit);
+            return runtime_api_result.map_err(map_rpc_error);
+        }
+
+        let runtime_api_result = api.get_public_posts_by_space_id(&at, space_id, offset, limit, pinned_first);
         runtime_api_result.map_err(map_rpc_error)
     }
 
@@ -254,17 +332,128 @@ where
         runtime_api_result.map_err(map_rpc_error)
     }
 
+    fn get_post_ids_by_account(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+        kind_filter: Vec<FlatPostKind>,
+        offset: u64,
+        limit: u16
+    ) -> Result<Vec<PostId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_post_ids_by_account(&at, account, kind_filter, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_posts_by_account(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+        kind_filter: Vec<FlatPostKind>,
+        offset: u64,
+        limit: u16
+    ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_posts_by_account(&at, account, kind_filter, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_sharing_post_ids(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        post_id: PostId,
+        offset: u64,
+        limit: u16
+    ) -> Result<Vec<PostId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_sharing_post_ids(&at, post_id, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_sharing_posts(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        post_id: PostId,
+        offset: u64,
+        limit: u16
+    ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_sharing_posts(&at, post_id, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
     fn get_feed(
         &self,
         at: Option<<Block as BlockT>::Hash>,
         account: AccountId,
+        kind_filter: Vec<FlatPostKind>,
+        offset: u64,
+        limit: u16
+    ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        // Runtimes before version 2 of the API can't filter the feed by post kind.
+        let api_version = api.api_version::<dyn PostsRuntimeApi<Block, AccountId, BlockNumber>>(&at)
+            .map_err(map_rpc_error)?
+            .unwrap_or_default();
+        if api_version < 2 {
+            #[allow(deprecated)]
+            let runtime_api_result = api.get_feed_before_version_2(&at, account, offset, limit);
+            return runtime_api_result.map_err(map_rpc_error);
+        }
+
+        let runtime_api_result = api.get_feed(&at, account, kind_filter, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_timeline(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+        cursor: Option<TimelineCursor<AccountId>>,
+        limit: u16
+    ) -> Result<FlatTimeline<AccountId, BlockNumber>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_timeline(&at, account, cursor, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_post_ids_by_tag(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        tag: Tag,
+        offset: u64,
+        limit: u16
+    ) -> Result<Vec<PostId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_post_ids_by_tag(&at, tag, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_posts_by_tag(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        tag: Tag,
         offset: u64,
         limit: u16
     ) -> Result<Vec<FlatPost<AccountId, BlockNumber>>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
 
-        let runtime_api_result = api.get_feed(&at, account, offset, limit);
+        let runtime_api_result = api.get_posts_by_tag(&at, tag, offset, limit);
         runtime_api_result.map_err(map_rpc_error)
     }
 }
@@ -1,4 +1,10 @@
-use frame_support::dispatch::DispatchResult;
+use frame_support::{
+    dispatch::DispatchResult,
+    storage::{with_transaction, IterableStorageDoubleMap, TransactionOutcome},
+    weights::Weight,
+};
+use sp_runtime::traits::One;
+use sp_std::vec;
 
 use pallet_utils::{SpaceId, remove_from_vec};
 
@@ -10,7 +16,7 @@ impl<T: Config> Post<T> {
         id: PostId,
         created_by: T::AccountId,
         space_id_opt: Option<SpaceId>,
-        extension: PostExtension,
+        extension: PostExtension<T::BlockNumber>,
         content: Content
     ) -> Self {
         Post {
@@ -27,7 +33,10 @@ impl<T: Config> Post<T> {
             shares_count: 0,
             upvotes_count: 0,
             downvotes_count: 0,
-            score: 0
+            score: 0,
+            permissions: None,
+            expires_at: None,
+            quotes_count: 0,
         }
     }
 
@@ -56,6 +65,20 @@ impl<T: Config> Post<T> {
         matches!(self.extension, PostExtension::SharedPost(_))
     }
 
+    /// A sharing post with its own content, e.g. a commentary on the shared post.
+    pub fn is_quote(&self) -> bool {
+        self.is_sharing_post() && self.content.is_some()
+    }
+
+    /// A sharing post without its own content.
+    pub fn is_repost(&self) -> bool {
+        self.is_sharing_post() && self.content.is_none()
+    }
+
+    pub fn is_poll(&self) -> bool {
+        matches!(self.extension, PostExtension::Poll(_))
+    }
+
     pub fn get_comment_ext(&self) -> Result<Comment, DispatchError> {
         match self.extension {
             PostExtension::Comment(comment_ext) => Ok(comment_ext),
@@ -70,9 +93,16 @@ impl<T: Config> Post<T> {
         }
     }
 
+    pub fn get_poll(&self) -> Result<Poll<T::BlockNumber>, DispatchError> {
+        match self.extension {
+            PostExtension::Poll(poll) => Ok(poll),
+            _ => Err(Error::<T>::NotAPoll.into())
+        }
+    }
+
     pub fn get_root_post(&self) -> Result<Post<T>, DispatchError> {
         match self.extension {
-            PostExtension::RegularPost | PostExtension::SharedPost(_) =>
+            PostExtension::RegularPost | PostExtension::SharedPost(_) | PostExtension::Poll(_) =>
                 Ok(self.clone()),
             PostExtension::Comment(comment) =>
                 Module::require_post(comment.root_post_id),
@@ -131,6 +161,14 @@ impl<T: Config> Post<T> {
         self.shares_count = self.shares_count.saturating_sub(1);
     }
 
+    pub fn inc_quotes(&mut self) {
+        self.quotes_count = self.quotes_count.saturating_add(1);
+    }
+
+    pub fn dec_quotes(&mut self) {
+        self.quotes_count = self.quotes_count.saturating_sub(1);
+    }
+
     pub fn inc_upvotes(&mut self) {
         self.upvotes_count = self.upvotes_count.saturating_add(1);
     }
@@ -147,6 +185,10 @@ impl<T: Config> Post<T> {
         self.downvotes_count = self.downvotes_count.saturating_sub(1);
     }
 
+    pub fn change_score(&mut self, diff: i16) {
+        self.score = self.score.saturating_add(diff.into());
+    }
+
     pub fn is_public(&self) -> bool {
         !self.hidden && self.content.is_some()
     }
@@ -154,6 +196,11 @@ impl<T: Config> Post<T> {
     pub fn is_unlisted(&self) -> bool {
         !self.is_public()
     }
+
+    /// Whether a post should be listed in `PostIdsByTag` index under its tags.
+    pub fn is_indexed_by_tags(&self) -> bool {
+        self.is_root_post() && !self.hidden && self.space_id.is_some()
+    }
 }
 
 impl Default for PostUpdate {
@@ -161,7 +208,8 @@ impl Default for PostUpdate {
         PostUpdate {
             space_id: None,
             content: None,
-            hidden: None
+            hidden: None,
+            tags: None,
         }
     }
 }
@@ -198,14 +246,144 @@ impl<T: Config> Module<T> {
           }
         }
 
-        Spaces::ensure_account_has_space_permission(
+        Self::ensure_account_has_post_permission(
           editor.clone(),
+          post,
           space,
           permission_to_check,
           permission_error
         )
     }
 
+    pub fn ensure_account_can_delete_post(
+        who: &T::AccountId,
+        post: &Post<T>,
+        space: &Space<T>
+    ) -> DispatchResult {
+        ensure!(T::IsAccountBlocked::is_allowed_account(who.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+        let is_owner = post.is_owner(&who);
+
+        let (permission_to_check, permission_error) = if post.is_comment() {
+            // It's not possible to delete comments of other accounts, they can be hidden instead.
+            ensure!(is_owner, Error::<T>::NotACommentAuthor);
+            (SpacePermission::DeleteOwnComments, Error::<T>::NoPermissionToDeleteOwnComments)
+        } else if is_owner {
+            (SpacePermission::DeleteOwnPosts, Error::<T>::NoPermissionToDeleteOwnPosts)
+        } else {
+            (SpacePermission::DeleteAnyPost, Error::<T>::NoPermissionToDeleteAnyPost)
+        };
+
+        Self::ensure_account_has_post_permission(
+            who.clone(),
+            post,
+            space,
+            permission_to_check,
+            permission_error.into()
+        )
+    }
+
+    /// Check a permission in the space of a post, honoring the permission overrides
+    /// of its root post.
+    pub fn ensure_account_has_post_permission(
+        account: T::AccountId,
+        post: &Post<T>,
+        space: &Space<T>,
+        permission: SpacePermission,
+        error: DispatchError,
+    ) -> DispatchResult {
+        let root_post = post.get_root_post()?;
+
+        Spaces::ensure_account_has_space_permission_with_post_perms(
+            account,
+            space,
+            root_post.permissions,
+            permission,
+            error
+        )
+    }
+
+    pub(crate) fn ensure_account_can_pin_posts(account: T::AccountId, space: &Space<T>) -> DispatchResult {
+        ensure!(T::IsAccountBlocked::is_allowed_account(account.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+        Spaces::ensure_account_has_space_permission(
+            account,
+            space,
+            SpacePermission::PinPosts,
+            Error::<T>::NoPermissionToPinPosts.into()
+        )
+    }
+
+    /// Remove a post from the pinned posts of a space.
+    /// Returns `false` if this post was not pinned in the space.
+    pub(crate) fn unpin_post_in_space(space_id: SpaceId, post_id: PostId) -> bool {
+        let mut pinned_post_ids = Self::pinned_post_ids_by_space_id(space_id);
+        if !pinned_post_ids.contains(&post_id) {
+            return false;
+        }
+
+        // Keep the order in which the remaining posts were pinned.
+        pinned_post_ids.retain(|id| *id != post_id);
+        if pinned_post_ids.is_empty() {
+            PinnedPostIdsBySpaceId::remove(space_id);
+        } else {
+            PinnedPostIdsBySpaceId::insert(space_id, pinned_post_ids);
+        }
+        true
+    }
+
+    /// This function does the next:
+    /// - Check if the number of tags fits into `MaxTagsPerPost`.
+    /// - Strip a leading `#` and lowercase each tag.
+    /// - Check if each tag is not empty, fits into `MaxTagLen`
+    ///   and contains only valid chars: 0-9, a-z, _.
+    /// - Remove duplicate tags.
+    pub fn lowercase_and_validate_tags(tags: Vec<Tag>) -> Result<Vec<Tag>, DispatchError> {
+        ensure!(tags.len() <= T::MaxTagsPerPost::get() as usize, Error::<T>::TooManyTags);
+
+        let mut unique_tags = Vec::with_capacity(tags.len());
+        for tag in tags {
+            let tag = Self::normalize_tag(tag);
+
+            ensure!(!tag.is_empty(), Error::<T>::TagIsEmpty);
+            ensure!(tag.len() <= T::MaxTagLen::get() as usize, Error::<T>::TagIsTooLong);
+
+            let is_only_valid_chars = tag.iter().all(|&x| Utils::<T>::is_valid_handle_char(x));
+            ensure!(is_only_valid_chars, Error::<T>::TagContainsInvalidChars);
+
+            if !unique_tags.contains(&tag) {
+                unique_tags.push(tag);
+            }
+        }
+
+        Ok(unique_tags)
+    }
+
+    /// Strip a leading `#` from a tag and lowercase it, so that `#Rust` and `rust` are the same tag.
+    pub fn normalize_tag(tag: Tag) -> Tag {
+        let tag = tag.strip_prefix(b"#").map(|t| t.to_vec()).unwrap_or(tag);
+        Utils::<T>::lowercase_handle(tag)
+    }
+
+    /// Remove a post from `PostIdsByTag` index under `old_tags` that are not in `new_tags`,
+    /// and add it under `new_tags` that are not in `old_tags`.
+    pub(crate) fn update_tags_index(post_id: PostId, old_tags: &[Tag], new_tags: &[Tag]) {
+        for tag in old_tags.iter().filter(|tag| !new_tags.contains(tag)) {
+            let mut post_ids = Self::post_ids_by_tag(tag);
+            post_ids.retain(|id| *id != post_id);
+
+            if post_ids.is_empty() {
+                PostIdsByTag::remove(tag);
+            } else {
+                PostIdsByTag::insert(tag, post_ids);
+            }
+        }
+
+        for tag in new_tags.iter().filter(|tag| !old_tags.contains(tag)) {
+            PostIdsByTag::mutate(tag, |post_ids| post_ids.push(post_id));
+        }
+    }
+
     /// Check that there is a `Post` with such `post_id` in the storage
     /// or return`PostNotFound` error.
     pub fn ensure_post_exists(post_id: PostId) -> DispatchResult {
@@ -221,9 +399,14 @@ impl<T: Config> Module<T> {
     fn share_post(
         account: T::AccountId,
         original_post: &mut Post<T>,
-        shared_post_id: PostId
+        sharing_post: &Post<T>
     ) -> DispatchResult {
         original_post.inc_shares();
+        if sharing_post.is_quote() {
+            original_post.inc_quotes();
+        }
+
+        let shared_post_id = sharing_post.id;
 
         let original_post_id = original_post.id;
         PostById::insert(original_post_id, original_post.clone());
@@ -317,11 +500,189 @@ impl<T: Config> Module<T> {
     }
     // TODO: maybe add for_each_reply?
 
+    /// Create a post on behalf of `creator`. Returns the id of the new post.
+    pub(crate) fn create_post_by_account(
+        creator: T::AccountId,
+        space_id_opt: Option<SpaceId>,
+        extension: PostExtension<T::BlockNumber>,
+        content: Content,
+        tags: Vec<Tag>,
+        mentions: Vec<T::AccountId>,
+    ) -> Result<PostId, DispatchError> {
+
+        Utils::<T>::is_valid_content(content.clone())?;
+        let tags = Self::lowercase_and_validate_tags(tags)?;
+
+        let mentioned_accounts = Self::dedup_mentions(mentions)?;
+
+        let new_post_id = Self::next_post_id();
+        let new_post: Post<T> = Post::new(new_post_id, creator.clone(), space_id_opt, extension, content.clone());
+        ensure!(tags.is_empty() || new_post.is_root_post(), Error::<T>::CannotTagComment);
+
+        // Get space from either space_id_opt or Comment if a comment provided
+        let space = &mut new_post.get_space()?;
+        ensure!(!space.hidden, Error::<T>::CannotCreateInHiddenScope);
+
+        ensure!(T::IsAccountBlocked::is_allowed_account(creator.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+        ensure!(T::IsContentBlocked::is_allowed_content(content, space.id), UtilsError::<T>::ContentIsBlocked);
+
+        let root_post = &mut new_post.get_root_post()?;
+        ensure!(!root_post.hidden, Error::<T>::CannotCreateInHiddenScope);
+
+        // Check whether account has permission to create a root post.
+        // Permission to create a comment is checked in `create_comment()`.
+        if !new_post.is_comment() {
+            Spaces::ensure_account_has_space_permission(
+                creator.clone(),
+                &space,
+                SpacePermission::CreatePosts,
+                Error::<T>::NoPermissionToCreatePosts.into()
+            )?;
+        }
+
+        match extension {
+            PostExtension::RegularPost => space.inc_posts(),
+            PostExtension::SharedPost(post_id) => Self::create_sharing_post(&creator, &new_post, post_id, space)?,
+            PostExtension::Comment(comment_ext) => Self::create_comment(&creator, new_post_id, comment_ext, root_post, space)?,
+            PostExtension::Poll(poll) => {
+                Self::ensure_valid_poll(&poll)?;
+                space.inc_posts();
+                PollResultsByPostId::insert(new_post_id, PollResults {
+                    voters_count: 0,
+                    votes: vec![0; poll.options_count as usize],
+                });
+            },
+        }
+
+        if new_post.is_root_post() {
+            SpaceById::insert(space.id, space.clone());
+            PostIdsBySpaceId::mutate(space.id, |ids| ids.push(new_post_id));
+            Self::link_post_in_space_timeline(space.id, new_post_id);
+        }
+
+        if !tags.is_empty() {
+            Self::update_tags_index(new_post_id, &[], &tags);
+            TagsByPostId::insert(new_post_id, tags);
+        }
+
+        if !mentioned_accounts.is_empty() {
+            <MentionedAccountsByPostId<T>>::insert(new_post_id, mentioned_accounts);
+        }
+
+        Self::add_post_to_owner_index(&new_post);
+        PostById::insert(new_post_id, new_post.clone());
+        NextPostId::mutate(|n| { *n += 1; });
+
+        // Scores are updated after the space and posts are stored, so that they are not overwritten.
+        match extension {
+            PostExtension::RegularPost | PostExtension::Poll(_) => (),
+            PostExtension::SharedPost(original_post_id) =>
+                T::PostScores::score_post_on_new_share(creator.clone(), new_post_id, original_post_id)?,
+            PostExtension::Comment(comment_ext) =>
+                T::PostScores::score_root_post_on_new_comment(creator.clone(), new_post_id, comment_ext.root_post_id)?,
+        }
+
+        T::AfterPostCreated::after_post_created(creator.clone(), &new_post);
+
+        Self::deposit_event(RawEvent::PostCreated(creator, new_post_id));
+        Ok(new_post_id)
+
+    }
+
+    /// Remove duplicate mentions, keeping the order in which accounts were mentioned.
+    pub(crate) fn dedup_mentions(mentions: Vec<T::AccountId>) -> Result<Vec<T::AccountId>, DispatchError> {
+        ensure!(mentions.len() <= T::MaxMentionsPerPost::get() as usize, Error::<T>::TooManyMentions);
+
+        let mut mentioned_accounts = Vec::with_capacity(mentions.len());
+        for account in mentions {
+            if !mentioned_accounts.contains(&account) {
+                mentioned_accounts.push(account);
+            }
+        }
+        Ok(mentioned_accounts)
+    }
+
+    pub(crate) fn ensure_valid_poll(poll: &Poll<T::BlockNumber>) -> DispatchResult {
+        ensure!(
+            poll.options_count >= 2 && poll.options_count <= T::MaxPollOptions::get(),
+            Error::<T>::InvalidPollOptionsCount
+        );
+        ensure!(poll.ends_at > <system::Pallet<T>>::block_number(), Error::<T>::PollEndsInPast);
+        Ok(())
+    }
+
+    /// Check that the options chosen by a voter exist in a poll and conform to its kind.
+    /// Returns the chosen option indices sorted in an ascending order.
+    pub(crate) fn validate_poll_choice(
+        poll: &Poll<T::BlockNumber>,
+        mut option_indices: Vec<PollOptionIndex>,
+    ) -> Result<Vec<PollOptionIndex>, DispatchError> {
+        ensure!(!option_indices.is_empty(), Error::<T>::NoPollOptionsChosen);
+        if poll.kind == PollKind::SingleChoice {
+            ensure!(option_indices.len() == 1, Error::<T>::TooManyPollOptionsChosen);
+        }
+
+        option_indices.sort_unstable();
+        let chosen_options_count = option_indices.len();
+        option_indices.dedup();
+
+        ensure!(
+            option_indices.len() == chosen_options_count
+                && option_indices.iter().all(|index| *index < poll.options_count),
+            Error::<T>::InvalidPollOption
+        );
+        Ok(option_indices)
+    }
+
+    pub fn require_scheduled_post(scheduled_post_id: ScheduledPostId) -> Result<ScheduledPost<T>, DispatchError> {
+        Ok(Self::scheduled_post_by_id(scheduled_post_id).ok_or(Error::<T>::ScheduledPostNotFound)?)
+    }
+
+    pub(crate) fn scheduled_post_task_name(scheduled_post_id: ScheduledPostId) -> Vec<u8> {
+        (SCHEDULED_POST_TASK_PREFIX, scheduled_post_id).encode()
+    }
+
+    /// Schedule a call on behalf of `author` that publishes a given scheduled post at `publish_at` block.
+    pub(crate) fn schedule_publishing(
+        scheduled_post_id: ScheduledPostId,
+        author: &T::AccountId,
+        publish_at: T::BlockNumber
+    ) -> DispatchResult {
+        T::Scheduler::schedule_named(
+            Self::scheduled_post_task_name(scheduled_post_id),
+            DispatchTime::At(publish_at),
+            None,
+            LOWEST_PRIORITY,
+            system::RawOrigin::Signed(author.clone()).into(),
+            Call::<T>::publish_scheduled_post(scheduled_post_id).into(),
+        ).map_err(|_| Error::<T>::FailedToSchedulePost)?;
+
+        Ok(())
+    }
+
+    /// Remove a published or canceled scheduled post and return the deposit to its author.
+    pub(crate) fn remove_scheduled_post(scheduled_post_id: ScheduledPostId, author: &T::AccountId) {
+        <T as pallet_spaces::Config>::Currency::unreserve(author, T::ScheduledPostDeposit::get());
+        <ScheduledPostById<T>>::remove(scheduled_post_id);
+        <ScheduledPostsCountByAccount<T>>::mutate(author, |count| *count = count.saturating_sub(1));
+    }
+
     pub(crate) fn create_comment(
+        creator: &T::AccountId,
         new_post_id: PostId,
         comment_ext: Comment,
-        root_post: &mut Post<T>
+        root_post: &mut Post<T>,
+        space: &Space<T>
     ) -> DispatchResult {
+        // Permission overrides of the root post (e.g. locked comments) are applied here.
+        Spaces::ensure_account_has_space_permission_with_post_perms(
+            creator.clone(),
+            space,
+            root_post.permissions.clone(),
+            SpacePermission::CreateComments,
+            Error::<T>::NoPermissionToCreateComments.into()
+        )?;
+
         let mut commented_post_id = root_post.id;
 
         if let Some(parent_id) = comment_ext.parent_id {
@@ -331,9 +692,24 @@ impl<T: Config> Module<T> {
             let ancestors = Self::get_post_ancestors(parent_id);
             ensure!(ancestors.len() < T::MaxCommentDepth::get() as usize, Error::<T>::MaxCommentDepthReached);
 
+            ensure!(
+                !T::IsAccountBlockedBy::is_account_blocked_by(creator.clone(), parent_comment.owner),
+                Error::<T>::BlockedByPostOwner
+            );
+
             commented_post_id = parent_id;
         }
 
+        ensure!(
+            !T::IsAccountBlockedBy::is_account_blocked_by(creator.clone(), root_post.owner.clone()),
+            Error::<T>::BlockedByPostOwner
+        );
+
+        ensure!(T::IsPostBlocked::is_allowed_post(root_post.id, space.id), UtilsError::<T>::PostIsBlocked);
+        if commented_post_id != root_post.id {
+            ensure!(T::IsPostBlocked::is_allowed_post(commented_post_id, space.id), UtilsError::<T>::PostIsBlocked);
+        }
+
         root_post.inc_replies();
 
         Self::for_each_post_ancestor(commented_post_id, |post| post.inc_replies())?;
@@ -345,26 +721,59 @@ impl<T: Config> Module<T> {
 
     pub(crate) fn create_sharing_post(
         creator: &T::AccountId,
-        new_post_id: PostId,
+        sharing_post: &Post<T>,
         original_post_id: PostId,
         space: &mut Space<T>
     ) -> DispatchResult {
         let original_post = &mut Self::post_by_id(original_post_id)
             .ok_or(Error::<T>::OriginalPostNotFound)?;
 
-        ensure!(!original_post.is_sharing_post(), Error::<T>::CannotShareSharingPost);
+        if original_post.is_sharing_post() {
+            // A repost adds nothing to the post it shares, so that post should be shared instead.
+            ensure!(original_post.is_quote(), Error::<T>::CannotShareSharingPost);
+            ensure!(
+                Self::get_share_depth(original_post) < T::MaxQuoteDepth::get(),
+                Error::<T>::MaxQuoteDepthReached
+            );
+        }
+
+        let original_space = original_post.get_space()?;
+
+        // A blocked post can be shared neither from the space where it was blocked,
+        // nor to the space where it is blocked.
+        ensure!(
+            T::IsPostBlocked::is_allowed_post(original_post_id, original_space.id) &&
+            T::IsPostBlocked::is_allowed_post(original_post_id, space.id),
+            UtilsError::<T>::PostIsBlocked
+        );
 
         // Check if it's allowed to share a post from the space of original post.
         Spaces::ensure_account_has_space_permission(
             creator.clone(),
-            &original_post.get_space()?,
+            &original_space,
             SpacePermission::Share,
             Error::<T>::NoPermissionToShare.into()
         )?;
 
         space.inc_posts();
 
-        Self::share_post(creator.clone(), original_post, new_post_id)
+        Self::share_post(creator.clone(), original_post, sharing_post)
+    }
+
+    /// Get the number of sharing posts in a chain from a given post to the original (not sharing) post.
+    /// The length of such chains is limited by `MaxQuoteDepth`.
+    pub(crate) fn get_share_depth(post: &Post<T>) -> u32 {
+        let mut depth = 0;
+        let mut shared_post_id_opt = post.get_shared_post_id().ok();
+
+        while let Some(shared_post_id) = shared_post_id_opt {
+            depth += 1;
+            // The shared post could have been deleted.
+            shared_post_id_opt = Self::post_by_id(shared_post_id)
+                .and_then(|shared_post| shared_post.get_shared_post_id().ok());
+        }
+
+        depth
     }
 
     fn mutate_posts_count_on_space<F: FnMut(&mut u32) + Copy> (
@@ -408,7 +817,7 @@ impl<T: Config> Module<T> {
         );
 
         match post.extension {
-            PostExtension::RegularPost | PostExtension::SharedPost(_) => {
+            PostExtension::RegularPost | PostExtension::SharedPost(_) | PostExtension::Poll(_) => {
 
                 if let Some(old_space_id) = old_space_id_opt {
 
@@ -420,6 +829,8 @@ impl<T: Config> Module<T> {
                     )?;
 
                     PostIdsBySpaceId::mutate(old_space_id, |post_ids| remove_from_vec(post_ids, post.id));
+                    Self::unlink_post_from_space_timeline(old_space_id, post.id);
+                    Self::unpin_post_in_space(old_space_id, post.id);
                 }
 
                 // Increase the number of posts on the new space
@@ -430,8 +841,14 @@ impl<T: Config> Module<T> {
                 )?;
 
                 PostIdsBySpaceId::mutate(new_space_id, |post_ids| post_ids.push(post.id));
+                Self::link_post_in_space_timeline(new_space_id, post.id);
 
+                let was_indexed_by_tags = post.is_indexed_by_tags();
                 post.space_id = Some(new_space_id);
+                if !was_indexed_by_tags && post.is_indexed_by_tags() {
+                    Self::update_tags_index(post.id, &[], &Self::tags_by_post_id(post.id));
+                }
+
                 PostById::<T>::insert(post.id, post);
 
                 Ok(())
@@ -444,7 +861,10 @@ impl<T: Config> Module<T> {
         let mut post = Self::require_post(post_id)?;
 
         if let PostExtension::Comment(comment_ext) = post.extension {
+            // The comment becomes a regular post, so move it between the owner's indexes.
+            Self::remove_post_from_owner_index(&post);
             post.extension = PostExtension::RegularPost;
+            Self::add_post_to_owner_index(&post);
 
             let root_post = &mut Self::require_post(comment_ext.root_post_id)?;
             let parent_id = comment_ext.parent_id.unwrap_or(root_post.id);
@@ -471,8 +891,14 @@ impl<T: Config> Module<T> {
                 |counter| *counter = counter.saturating_sub(1)
             )?;
 
+            if post.is_indexed_by_tags() {
+                Self::update_tags_index(post_id, &Self::tags_by_post_id(post_id), &[]);
+            }
+
             post.space_id = None;
             PostIdsBySpaceId::mutate(space_id, |post_ids| remove_from_vec(post_ids, post_id));
+            Self::unlink_post_from_space_timeline(space_id, post_id);
+            Self::unpin_post_in_space(space_id, post_id);
         }
 
         PostById::<T>::insert(post.id, post);
@@ -501,4 +927,354 @@ impl<T: Config> Module<T> {
 
         Ok(())
     }
+
+    /// Add a post to the index of posts of the same kind created by the post owner.
+    /// The ids in the index are kept sorted, even if the post is not the newest one.
+    pub(crate) fn add_post_to_owner_index(post: &Post<T>) {
+        let push_post_id = |post_ids: &mut Vec<PostId>| {
+            if let Err(index) = post_ids.binary_search(&post.id) {
+                post_ids.insert(index, post.id);
+            }
+        };
+
+        match post.extension {
+            // A poll is a regular post with options to vote for.
+            PostExtension::RegularPost | PostExtension::Poll(_) =>
+                RegularPostIdsByOwner::<T>::mutate(&post.owner, push_post_id),
+            PostExtension::SharedPost(_) => SharingPostIdsByOwner::<T>::mutate(&post.owner, push_post_id),
+            PostExtension::Comment(_) => CommentIdsByOwner::<T>::mutate(&post.owner, push_post_id),
+        }
+
+        if !post.is_comment() {
+            Self::link_post_in_timeline(
+                TimelineSource::Owner(post.owner.clone()),
+                post.id,
+                || Self::timeline_post_ids_by_owner(&post.owner),
+            );
+        }
+    }
+
+    pub(crate) fn remove_post_from_owner_index(post: &Post<T>) {
+        // Keep the ids of the remaining posts sorted.
+        let remove_post_id = |post_ids: &mut Vec<PostId>| post_ids.retain(|id| *id != post.id);
+
+        match post.extension {
+            PostExtension::RegularPost | PostExtension::Poll(_) =>
+                RegularPostIdsByOwner::<T>::mutate(&post.owner, remove_post_id),
+            PostExtension::SharedPost(_) => SharingPostIdsByOwner::<T>::mutate(&post.owner, remove_post_id),
+            PostExtension::Comment(_) => CommentIdsByOwner::<T>::mutate(&post.owner, remove_post_id),
+        }
+
+        if !post.is_comment() {
+            Self::unlink_post_from_timeline(
+                TimelineSource::Owner(post.owner.clone()),
+                post.id,
+                || Self::timeline_post_ids_by_owner(&post.owner),
+            );
+        }
+    }
+
+    /// The ids of regular and sharing posts created by a given account, i.e. its timeline posts.
+    fn timeline_post_ids_by_owner(owner: &T::AccountId) -> Vec<PostId> {
+        let mut post_ids = Self::regular_post_ids_by_owner(owner);
+        post_ids.extend(Self::sharing_post_ids_by_owner(owner));
+        post_ids
+    }
+
+    fn link_post_in_space_timeline(space_id: SpaceId, post_id: PostId) {
+        Self::link_post_in_timeline(
+            TimelineSource::Space(space_id),
+            post_id,
+            || Self::post_ids_by_space_id(space_id),
+        );
+    }
+
+    fn unlink_post_from_space_timeline(space_id: SpaceId, post_id: PostId) {
+        Self::unlink_post_from_timeline(
+            TimelineSource::Space(space_id),
+            post_id,
+            || Self::post_ids_by_space_id(space_id),
+        );
+    }
+
+    /// Link a post between the nearest older and newer posts of a timeline source.
+    /// `source_post_ids` returns the ids of the posts in the source, in any order. It is only called
+    /// if the post is older than the latest post of the source, e.g. when a post is moved to a space.
+    pub(crate) fn link_post_in_timeline<F>(source: TimelineSource<T::AccountId>, post_id: PostId, source_post_ids: F)
+        where F: FnOnce() -> Vec<PostId>
+    {
+        let latest_post_id = Self::latest_post_id_by_source(&source);
+
+        let (older_post_id, newer_post_id) = match latest_post_id {
+            // The post is already linked.
+            Some(latest_post_id) if latest_post_id == post_id => return,
+            Some(latest_post_id) if latest_post_id > post_id => {
+                let post_ids = source_post_ids();
+                (
+                    post_ids.iter().copied().filter(|id| *id < post_id).max(),
+                    post_ids.iter().copied().filter(|id| *id > post_id).min(),
+                )
+            },
+            _ => (latest_post_id, None),
+        };
+
+        PreviousPostIdBySource::<T>::mutate(&source, post_id, |previous| *previous = older_post_id);
+        match newer_post_id {
+            Some(newer_post_id) => PreviousPostIdBySource::<T>::insert(&source, newer_post_id, post_id),
+            None => LatestPostIdBySource::<T>::insert(&source, post_id),
+        }
+    }
+
+    /// Unlink a post from a timeline source, so that the next newer post of the source
+    /// is linked to the next older one. `source_post_ids` is called like in `link_post_in_timeline`.
+    pub(crate) fn unlink_post_from_timeline<F>(source: TimelineSource<T::AccountId>, post_id: PostId, source_post_ids: F)
+        where F: FnOnce() -> Vec<PostId>
+    {
+        let older_post_id = PreviousPostIdBySource::<T>::take(&source, post_id);
+
+        if Self::latest_post_id_by_source(&source) == Some(post_id) {
+            LatestPostIdBySource::<T>::mutate(&source, |latest| *latest = older_post_id);
+        } else if let Some(newer_post_id) = source_post_ids().into_iter().filter(|id| *id > post_id).min() {
+            PreviousPostIdBySource::<T>::mutate(&source, newer_post_id, |previous| *previous = older_post_id);
+        }
+    }
+
+    /// Remove a post and all its nested replies from the storage.
+    /// Counters of the post's ancestors, its space and its original post (if it's a sharing post)
+    /// are decreased accordingly.
+    pub(crate) fn remove_post_with_replies(post: &Post<T>) -> DispatchResult {
+        // This list includes the post itself.
+        let removed_posts = Self::try_get_post_replies(post.id);
+
+        match post.extension {
+            PostExtension::Comment(comment_ext) => {
+                let removed_replies = removed_posts.len() as u32;
+                let removed_hidden_replies = removed_posts.iter().filter(|p| p.hidden).count() as u32;
+
+                let dec_replies_count = move |p: &mut Post<T>| {
+                    p.replies_count = p.replies_count.saturating_sub(removed_replies);
+                    p.hidden_replies_count = p.hidden_replies_count.saturating_sub(removed_hidden_replies);
+                };
+
+                if let Some(parent_id) = comment_ext.parent_id {
+                    Self::for_each_post_ancestor(parent_id, dec_replies_count)?;
+                }
+                Self::mutate_post_by_id(comment_ext.root_post_id, dec_replies_count)?;
+
+                let commented_post_id = comment_ext.parent_id.unwrap_or(comment_ext.root_post_id);
+                ReplyIdsByPostId::mutate(commented_post_id, |reply_ids| remove_from_vec(reply_ids, post.id));
+            },
+            PostExtension::RegularPost | PostExtension::SharedPost(_) | PostExtension::Poll(_) => {
+                if let Some(space_id) = post.space_id {
+                    // The space could have been deleted, but its posts are not detached yet.
+                    if SpaceById::<T>::contains_key(space_id) {
+                        // Decrease the number of posts on the space
+                        Self::mutate_posts_count_on_space(
+                            space_id,
+                            post,
+                            |counter| *counter = counter.saturating_sub(1)
+                        )?;
+                    }
+
+                    PostIdsBySpaceId::mutate(space_id, |post_ids| remove_from_vec(post_ids, post.id));
+                    Self::unlink_post_from_space_timeline(space_id, post.id);
+                    Self::unpin_post_in_space(space_id, post.id);
+                }
+
+                if post.is_indexed_by_tags() {
+                    Self::update_tags_index(post.id, &Self::tags_by_post_id(post.id), &[]);
+                }
+
+                if let PostExtension::SharedPost(original_post_id) = post.extension {
+                    // The original post could have been deleted already.
+                    if PostById::<T>::contains_key(original_post_id) {
+                        let is_quote = post.is_quote();
+                        Self::mutate_post_by_id(original_post_id, |original_post| {
+                            original_post.dec_shares();
+                            if is_quote {
+                                original_post.dec_quotes();
+                            }
+                        })?;
+                    }
+                    SharedPostIdsByOriginalPostId::mutate(
+                        original_post_id,
+                        |shared_post_ids| remove_from_vec(shared_post_ids, post.id)
+                    );
+                }
+            },
+        }
+
+        // Replies are reverted first, while the posts they scored are still in the storage.
+        for removed_post in removed_posts.iter().rev() {
+            T::PostScores::revert_scores_on_post_deleted(removed_post)?;
+        }
+
+        for removed_post in removed_posts.iter() {
+            PostById::<T>::remove(removed_post.id);
+            ReplyIdsByPostId::remove(removed_post.id);
+            SharedPostIdsByOriginalPostId::remove(removed_post.id);
+            TagsByPostId::remove(removed_post.id);
+            MentionedAccountsByPostId::<T>::remove(removed_post.id);
+            // Post ids are never reused, so the votes of accounts in a removed poll are kept.
+            PollResultsByPostId::remove(removed_post.id);
+            Self::remove_post_from_owner_index(removed_post);
+        }
+
+        Ok(())
+    }
+
+    /// Hide or delete the posts that expired at or before block `now`, processing at most
+    /// `MaxExpiredPostsPerBlock` items per call. Every deleted reply counts as an item too.
+    /// Returns the consumed weight.
+    pub(crate) fn expire_posts(now: T::BlockNumber) -> Weight {
+        let mut block = match Self::next_post_expiry_block() {
+            Some(block) => block,
+            None => return T::DbWeight::get().reads(1),
+        };
+
+        let limit = T::MaxExpiredPostsPerBlock::get();
+        let mut processed: u32 = 0;
+
+        'blocks: while block <= now && processed < limit {
+            // Visiting a block counts as one item too, so that this loop is always bounded.
+            processed += 1;
+
+            while let Some((post_id, action)) = PostsExpiringAt::<T>::iter_prefix(block).next() {
+                let budget = limit.saturating_sub(processed);
+                match Self::expire_post(post_id, block, action, budget) {
+                    Some(items) => processed = processed.saturating_add(items),
+                    // The post will be processed at the next call with a whole budget.
+                    None => break 'blocks,
+                }
+                PostsExpiringAt::<T>::remove(block, post_id);
+
+                if processed >= limit {
+                    break 'blocks;
+                }
+            }
+
+            block += One::one();
+        }
+
+        NextPostExpiryBlock::<T>::put(block);
+
+        T::DbWeight::get().reads_writes(
+            1 + 4 * processed as u64,
+            1 + 8 * processed as u64,
+        )
+    }
+
+    /// Hide or delete an expired post in a storage transaction, so that a failed expiry
+    /// does not leave the post half-processed. Returns the number of processed items,
+    /// or `None` if the post and its replies do not fit into `budget` items.
+    fn expire_post(post_id: PostId, block: T::BlockNumber, action: PostExpiryAction, budget: u32) -> Option<u32> {
+        let post = match Self::post_by_id(post_id) {
+            Some(post) => post,
+            None => return Some(1),
+        };
+
+        // The post could have been deleted and its expiry changed since the expiry was queued.
+        if post.expires_at != Some(block) {
+            return Some(1);
+        }
+
+        // Besides the post itself, one item of a whole budget is taken by visiting a block.
+        let max_replies_to_delete = T::MaxRepliesToDelete::get()
+            .min(T::MaxExpiredPostsPerBlock::get().saturating_sub(2));
+
+        let action = match action {
+            PostExpiryAction::Delete if post.replies_count > max_replies_to_delete => PostExpiryAction::Hide,
+            action => action,
+        };
+
+        let items = match action {
+            PostExpiryAction::Hide => 1,
+            PostExpiryAction::Delete => post.replies_count.saturating_add(1),
+        };
+
+        if items > budget {
+            return None;
+        }
+
+        let result = with_transaction(|| {
+            let result = match action {
+                PostExpiryAction::Hide => Self::hide_expired_post(post),
+                PostExpiryAction::Delete => Self::remove_post_with_replies(&post),
+            };
+
+            if result.is_ok() {
+                TransactionOutcome::Commit(result)
+            } else {
+                TransactionOutcome::Rollback(result)
+            }
+        });
+
+        if result.is_ok() {
+            Self::deposit_event(RawEvent::PostExpired(post_id, action));
+        }
+
+        Some(items)
+    }
+
+    fn hide_expired_post(mut post: Post<T>) -> DispatchResult {
+        post.expires_at = None;
+
+        if !post.hidden {
+            if let PostExtension::Comment(comment_ext) = post.extension {
+                Self::update_counters_on_comment_hidden_change(&comment_ext, true)?;
+            }
+
+            if let Some(mut space) = post.try_get_space() {
+                space.inc_hidden_posts();
+                <SpaceById<T>>::insert(space.id, space);
+            }
+
+            if post.is_indexed_by_tags() {
+                Self::update_tags_index(post.id, &Self::tags_by_post_id(post.id), &[]);
+            }
+
+            post.hidden = true;
+        }
+
+        <PostById<T>>::insert(post.id, post);
+        Ok(())
+    }
+}
+
+impl<T: Config> SpaceCleanupHandler for Module<T> {
+    /// Detach posts from a deleted space, so their owners can move them to another space.
+    fn cleanup_deleted_space(space_id: SpaceId, limit: u32) -> u32 {
+        let mut post_ids = Self::post_ids_by_space_id(space_id);
+        let mut detached = 0;
+
+        // Detached posts are not walked by the timeline anymore, so their links are removed one by one.
+        let source = TimelineSource::Space(space_id);
+        LatestPostIdBySource::<T>::remove(&source);
+
+        while detached < limit {
+            if let Some(post_id) = post_ids.pop() {
+                PreviousPostIdBySource::<T>::remove(&source, post_id);
+                PostById::<T>::mutate(post_id, |post_opt| {
+                    if let Some(post) = post_opt {
+                        if post.is_indexed_by_tags() {
+                            Self::update_tags_index(post_id, &Self::tags_by_post_id(post_id), &[]);
+                        }
+                        post.space_id = None;
+                    }
+                });
+                detached += 1;
+            } else {
+                break;
+            }
+        }
+
+        if post_ids.is_empty() {
+            PostIdsBySpaceId::remove(space_id);
+            PinnedPostIdsBySpaceId::remove(space_id);
+        } else {
+            PostIdsBySpaceId::insert(space_id, post_ids);
+        }
+
+        detached
+    }
 }
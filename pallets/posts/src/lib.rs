@@ -1,7 +1,7 @@
 //! # Posts Module
 //!
 //! Posts are the second crucial component of Subsocial after Spaces. This module allows you to
-//! create, update, move (between spaces), and hide posts as well as manage owner(s).
+//! create, update, move (between spaces), hide, and delete posts as well as manage owner(s).
 //!
 //! Posts can be compared to existing entities on web 2.0 platforms such as:
 //! - Posts on Facebook,
@@ -19,14 +19,19 @@ use scale_info::TypeInfo;
 use serde::{Serialize, Deserialize};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, fail,
-    dispatch::{DispatchError, DispatchResult}, ensure, traits::Get,
+    dispatch::{DispatchError, DispatchResult}, ensure,
+    traits::{Currency, Get, ReservableCurrency, schedule::{Named as ScheduleNamed, DispatchTime, LOWEST_PRIORITY}},
+    transactional,
 };
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
-use df_traits::moderation::{IsAccountBlocked, IsContentBlocked, IsPostBlocked};
-use pallet_permissions::SpacePermission;
+use df_traits::{
+    SpaceCleanupHandler,
+    moderation::{IsAccountBlocked, IsAccountBlockedBy, IsContentBlocked, IsPostBlocked},
+};
+use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissions};
 use pallet_spaces::{Module as Spaces, Space, SpaceById};
 use pallet_utils::{
     Module as Utils, Error as UtilsError,
@@ -34,6 +39,7 @@ use pallet_utils::{
 };
 
 pub mod functions;
+pub mod migrations;
 
 pub mod rpc;
 
@@ -52,8 +58,8 @@ pub struct Post<T: Config> {
     pub owner: T::AccountId,
 
     /// Through post extension you can provide specific information necessary for different kinds
-    /// of posts such as regular posts, comments, and shared posts.
-    pub extension: PostExtension,
+    /// of posts such as regular posts, comments, shared posts, and polls.
+    pub extension: PostExtension<T::BlockNumber>,
 
     /// An id of a space which contains a given post.
     pub space_id: Option<SpaceId>,
@@ -65,23 +71,72 @@ pub struct Post<T: Config> {
     pub hidden: bool,
 
     /// The total number of replies for a given post.
-    pub replies_count: u16,
+    pub replies_count: u32,
 
     /// The number of hidden replies for a given post.
-    pub hidden_replies_count: u16,
+    pub hidden_replies_count: u32,
 
     /// The number of times a given post has been shared.
-    pub shares_count: u16,
+    pub shares_count: u32,
 
     /// The number of times a given post has been upvoted.
-    pub upvotes_count: u16,
+    pub upvotes_count: u32,
 
     /// The number of times a given post has been downvoted.
-    pub downvotes_count: u16,
+    pub downvotes_count: u32,
 
     pub score: i32,
+
+    /// This allows you to override the permissions of a post's space for this particular post,
+    /// e.g. to forbid commenting on it. Only root posts can have overrides,
+    /// they are applied to all comments of a post as well.
+    pub permissions: Option<SpacePermissions>,
+
+    /// An optional block number at which this post will expire. If `expires_at` is `Some`,
+    /// the post is hidden or deleted (see `PostExpiryAction`) once this block is reached.
+    pub expires_at: Option<T::BlockNumber>,
+
+    /// The number of times a given post has been quoted, i.e. shared by a sharing post
+    /// with its own content. Quotes are included in `shares_count` as well.
+    pub quotes_count: u32,
+}
+
+/// What happens to a post when it expires.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PostExpiryAction {
+    /// Hide the post, so that its owner can still unhide it later.
+    Hide,
+    /// Delete the post together with all its replies.
+    Delete,
 }
 
+pub type ScheduledPostId = u64;
+
+type BalanceOf<T> =
+  <<T as pallet_spaces::Config>::Currency as Currency<<T as system::Config>::AccountId>>::Balance;
+
+/// A draft of a regular post that will be published in a space at a given block.
+/// Until then it is not visible in the space and is not counted in its `posts_count`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ScheduledPost<T: Config> {
+    pub id: ScheduledPostId,
+    pub created: WhoAndWhen<T>,
+    pub updated: Option<WhoAndWhen<T>>,
+    pub space_id: SpaceId,
+    pub content: Content,
+    pub tags: Vec<Tag>,
+    pub mentions: Vec<T::AccountId>,
+
+    /// The block at which the post will be published.
+    pub publish_at: T::BlockNumber,
+}
+
+/// A lower-cased topic of a post, e.g. `rust` or `web3_news`.
+/// Tags are validated like handles: only 0-9, a-z and _ are allowed.
+pub type Tag = Vec<u8>;
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct PostUpdate {
     /// Deprecated: This field has no effect in `fn update_post()` extrinsic.
@@ -90,17 +145,19 @@ pub struct PostUpdate {
 
     pub content: Option<Content>,
     pub hidden: Option<bool>,
+    pub tags: Option<Vec<Tag>>,
 }
 
 /// Post extension provides specific information necessary for different kinds
-/// of posts such as regular posts, comments, and shared posts.
+/// of posts such as regular posts, comments, shared posts, and polls.
 #[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(untagged))]
-pub enum PostExtension {
+pub enum PostExtension<BlockNumber> {
     RegularPost,
     Comment(Comment),
     SharedPost(PostId),
+    Poll(Poll<BlockNumber>),
 }
 
 #[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
@@ -110,15 +167,62 @@ pub struct Comment {
     pub root_post_id: PostId,
 }
 
-impl Default for PostExtension {
+impl<BlockNumber> Default for PostExtension<BlockNumber> {
     fn default() -> Self {
         PostExtension::RegularPost
     }
 }
 
+/// An index of a poll option, starting from 0.
+pub type PollOptionIndex = u8;
+
+/// Whether a voter can choose only one or several options of a poll.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PollKind {
+    SingleChoice,
+    MultipleChoice,
+}
+
+/// A poll is a root post with a set of options to vote for.
+/// The titles of options are stored in the post content in the order of their indices,
+/// while the votes are counted on chain.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Poll<BlockNumber> {
+    /// The number of options in this poll, from 2 to `MaxPollOptions`.
+    pub options_count: PollOptionIndex,
+    pub kind: PollKind,
+
+    /// The last block at which accounts can vote in this poll.
+    pub ends_at: BlockNumber,
+}
+
+/// The on-chain tallies of a poll.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct PollResults {
+    /// The number of accounts that voted in a poll.
+    pub voters_count: u32,
+
+    /// The number of votes per option, indexed by `PollOptionIndex`.
+    pub votes: Vec<u32>,
+}
+
+/// A source of posts in the home timeline of an account: a followed space or a followed account.
+/// A space source includes the root posts of the space, while an account source includes
+/// the regular and sharing posts created by the account.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum TimelineSource<AccountId> {
+    Space(SpaceId),
+    Owner(AccountId),
+}
+
 /// The pallet's configuration trait.
 pub trait Config: system::Config
     + pallet_utils::Config
+    + pallet_profile_follows::Config
     + pallet_space_follows::Config
     + pallet_spaces::Config
 {
@@ -128,9 +232,61 @@ pub trait Config: system::Config
     /// Max comments depth
     type MaxCommentDepth: Get<u32>;
 
+    /// The max number of nested replies a post can have to be deleted in a single transaction.
+    type MaxRepliesToDelete: Get<u32>;
+
+    /// The max number of posts that can be pinned in a single space.
+    type MaxPinnedPostsPerSpace: Get<u32>;
+
+    /// The max number of tags a single post can have.
+    type MaxTagsPerPost: Get<u32>;
+
+    /// The max length of a post tag in bytes.
+    type MaxTagLen: Get<u32>;
+
+    /// The max number of accounts that can be mentioned in a single post.
+    type MaxMentionsPerPost: Get<u32>;
+
+    /// The max number of expired posts to hide or delete per block.
+    type MaxExpiredPostsPerBlock: Get<u32>;
+
+    /// The max number of options a single poll can have.
+    type MaxPollOptions: Get<PollOptionIndex>;
+
+    /// The max number of sharing posts in a chain of quotes, e.g. `2` allows to quote
+    /// a quote of a post, but not to quote it once again.
+    type MaxQuoteDepth: Get<u32>;
+
+    /// The amount reserved from an author per scheduled post, until the post is published or canceled.
+    type ScheduledPostDeposit: Get<BalanceOf<Self>>;
+
+    /// The max number of posts a single account can have scheduled at the same time.
+    type MaxScheduledPostsPerAccount: Get<u32>;
+
+    type AfterPostCreated: AfterPostCreated<Self>;
+
     type AfterPostUpdated: AfterPostUpdated<Self>;
 
     type IsPostBlocked: IsPostBlocked<PostId>;
+
+    /// Personal block lists: an account cannot reply to posts of accounts that blocked it.
+    type IsAccountBlockedBy: IsAccountBlockedBy<Self::AccountId>;
+
+    type PostScores: PostScores<Self>;
+
+    /// The overarching call type, a call to publish a scheduled post is converted into it.
+    type ScheduledCall: From<Call<Self>>;
+
+    /// The scheduler that publishes scheduled posts at their target blocks.
+    type Scheduler: ScheduleNamed<Self::BlockNumber, Self::ScheduledCall, Self::PalletsOrigin>;
+
+    /// The overarching origin type of all pallets, scheduled posts are published with a signed origin of their authors.
+    type PalletsOrigin: From<system::RawOrigin<Self::AccountId>>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait AfterPostCreated<T: Config> {
+    fn after_post_created(account: T::AccountId, post: &Post<T>);
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(10)]
@@ -138,7 +294,34 @@ pub trait AfterPostUpdated<T: Config> {
     fn after_post_updated(account: T::AccountId, post: &Post<T>, old_data: PostUpdate);
 }
 
+/// Handler that updates the scores of posts when they are shared or commented on.
+pub trait PostScores<T: Config> {
+    fn score_post_on_new_share(account: T::AccountId, sharing_post_id: PostId, original_post_id: PostId) -> DispatchResult;
+    fn score_root_post_on_new_comment(account: T::AccountId, comment_id: PostId, root_post_id: PostId) -> DispatchResult;
+
+    /// Revert the score changes made when a given post (e.g. a comment or a sharing post) was created.
+    fn revert_scores_on_post_deleted(post: &Post<T>) -> DispatchResult;
+}
+
+impl<T: Config> PostScores<T> for () {
+    fn score_post_on_new_share(_account: T::AccountId, _sharing_post_id: PostId, _original_post_id: PostId) -> DispatchResult {
+        Ok(())
+    }
+
+    fn score_root_post_on_new_comment(_account: T::AccountId, _comment_id: PostId, _root_post_id: PostId) -> DispatchResult {
+        Ok(())
+    }
+
+    fn revert_scores_on_post_deleted(_post: &Post<T>) -> DispatchResult {
+        Ok(())
+    }
+}
+
 pub const FIRST_POST_ID: u64 = 1;
+pub const FIRST_SCHEDULED_POST_ID: u64 = 1;
+
+/// A prefix of the scheduler task names used to publish scheduled posts.
+pub const SCHEDULED_POST_TASK_PREFIX: [u8; 8] = *b"df/posts";
 
 // This pallet's storage items.
 decl_storage! {
@@ -163,18 +346,122 @@ decl_storage! {
         /// Get the ids of all posts that have shared a given original post id.
         pub SharedPostIdsByOriginalPostId get(fn shared_post_ids_by_original_post_id):
             map hasher(twox_64_concat) PostId => Vec<PostId>;
+
+        /// Get the ids of posts pinned in a given space, in the order they were pinned.
+        pub PinnedPostIdsBySpaceId get(fn pinned_post_ids_by_space_id):
+            map hasher(twox_64_concat) SpaceId => Vec<PostId>;
+
+        /// Get the tags of a given post.
+        pub TagsByPostId get(fn tags_by_post_id):
+            map hasher(twox_64_concat) PostId => Vec<Tag>;
+
+        /// Get the accounts mentioned in a given post.
+        pub MentionedAccountsByPostId get(fn mentioned_accounts_by_post_id):
+            map hasher(twox_64_concat) PostId => Vec<T::AccountId>;
+
+        /// Get the ids of regular posts created by a given account, from the oldest to the latest one.
+        pub RegularPostIdsByOwner get(fn regular_post_ids_by_owner):
+            map hasher(blake2_128_concat) T::AccountId => Vec<PostId>;
+
+        /// Get the ids of sharing posts created by a given account, from the oldest to the latest one.
+        pub SharingPostIdsByOwner get(fn sharing_post_ids_by_owner):
+            map hasher(blake2_128_concat) T::AccountId => Vec<PostId>;
+
+        /// Get the ids of comments created by a given account, from the oldest to the latest one.
+        pub CommentIdsByOwner get(fn comment_ids_by_owner):
+            map hasher(blake2_128_concat) T::AccountId => Vec<PostId>;
+
+        /// The next scheduled post id.
+        pub NextScheduledPostId get(fn next_scheduled_post_id): ScheduledPostId = FIRST_SCHEDULED_POST_ID;
+
+        /// Get the details of a post that is scheduled to be published, by its id.
+        /// A scheduled post is removed from here once it's published or canceled.
+        pub ScheduledPostById get(fn scheduled_post_by_id):
+            map hasher(twox_64_concat) ScheduledPostId => Option<ScheduledPost<T>>;
+
+        /// The number of posts scheduled by a given account, that are not published or canceled yet.
+        pub ScheduledPostsCountByAccount get(fn scheduled_posts_count_by_account):
+            map hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// Get the action to take on a post that expires at a given block.
+        /// Expiring posts are stored one per key, so that the number of posts expiring
+        /// at the same block is not limited by the size of a single storage value.
+        pub PostsExpiringAt get(fn post_expiry_action):
+            double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) PostId
+            => Option<PostExpiryAction>;
+
+        /// The earliest block whose expiring posts are not processed yet.
+        /// `None` until the first post expiry is set.
+        pub NextPostExpiryBlock get(fn next_post_expiry_block): Option<T::BlockNumber>;
+
+        /// Vote tallies of a poll by the poll's post id.
+        pub PollResultsByPostId get(fn poll_results_by_post_id):
+            map hasher(twox_64_concat) PostId => PollResults;
+
+        /// Options chosen by an account in a poll.
+        pub PollVoteByAccount get(fn poll_vote_by_account):
+            double_map hasher(twox_64_concat) PostId, hasher(blake2_128_concat) T::AccountId
+            => Option<Vec<PollOptionIndex>>;
+
+        /// Get the id of the latest post in a given timeline source.
+        pub LatestPostIdBySource get(fn latest_post_id_by_source):
+            map hasher(blake2_128_concat) TimelineSource<T::AccountId> => Option<PostId>;
+
+        /// Get the id of the post that precedes a given post in a given timeline source.
+        /// Posts of a source are linked from the latest to the oldest one, so that they can be
+        /// walked without reading the whole index of the source.
+        pub PreviousPostIdBySource get(fn previous_post_id_by_source):
+            double_map hasher(blake2_128_concat) TimelineSource<T::AccountId>, hasher(twox_64_concat) PostId
+            => Option<PostId>;
+
+        /// Get the ids of visible root posts that have a given tag.
+        /// Hidden posts and posts without a space are not indexed.
+        pub PostIdsByTag get(fn post_ids_by_tag):
+            map hasher(blake2_128_concat) Tag => Vec<PostId>;
+
+        /// True if `PostById` storage is already migrated to posts with the `permissions`,
+        /// `expires_at` and `quotes_count` fields and `u32` counters.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub PostByIdMigrated: bool = false;
+
+        /// True if the posts created before the per-owner indexes (`RegularPostIdsByOwner`,
+        /// `SharingPostIdsByOwner` and `CommentIdsByOwner`) were introduced are already indexed.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub PostsByOwnerMigrated: bool = false;
+
+        /// True if the existing posts of spaces and accounts are already linked
+        /// in `LatestPostIdBySource` and `PreviousPostIdBySource`.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub TimelinePostsLinked: bool = false;
+    }
+    add_extra_genesis {
+      build(|_| {
+        PostByIdMigrated::put(true);
+        PostsByOwnerMigrated::put(true);
+        TimelinePostsLinked::put(true);
+      })
     }
 }
 
 decl_event!(
     pub enum Event<T> where
         <T as system::Config>::AccountId,
+        <T as system::Config>::BlockNumber,
     {
         PostCreated(AccountId, PostId),
         PostUpdated(AccountId, PostId),
         PostDeleted(AccountId, PostId),
         PostShared(AccountId, PostId),
         PostMoved(AccountId, PostId),
+        PostPinned(AccountId, SpaceId, PostId),
+        PostUnpinned(AccountId, SpaceId, PostId),
+        PostScheduled(AccountId, ScheduledPostId, /* publish at */ BlockNumber),
+        PostRescheduled(AccountId, ScheduledPostId, /* publish at */ BlockNumber),
+        ScheduledPostCanceled(AccountId, ScheduledPostId),
+        ScheduledPostPublished(AccountId, ScheduledPostId, PostId),
+        PostExpiryUpdated(AccountId, PostId),
+        PostExpired(PostId, PostExpiryAction),
+        VotedInPoll(AccountId, PostId),
     }
 );
 
@@ -197,13 +484,46 @@ decl_error! {
         NoRepliesOnPost,
         /// Cannot move a post to the same space.
         CannotMoveToSameSpace,
+        /// Permissions can be overridden only on root posts, not on comments.
+        CannotOverrideCommentPermissions,
+
+        // Pinning related errors:
+
+        /// Only root posts can be pinned, not comments.
+        CannotPinComment,
+        /// Post is already pinned in its space.
+        PostIsAlreadyPinned,
+        /// Post is not pinned in its space.
+        PostIsNotPinned,
+        /// Space cannot have more than `MaxPinnedPostsPerSpace` pinned posts.
+        TooManyPinnedPosts,
+
+        // Tags related errors:
+
+        /// Only root posts can have tags, not comments.
+        CannotTagComment,
+        /// Post cannot have more than `MaxTagsPerPost` tags.
+        TooManyTags,
+        /// Post tag is empty.
+        TagIsEmpty,
+        /// Post tag is longer than `MaxTagLen`.
+        TagIsTooLong,
+        /// Post tag contains invalid characters.
+        TagContainsInvalidChars,
+
+        /// Post cannot mention more than `MaxMentionsPerPost` accounts.
+        TooManyMentions,
 
         // Sharing related errors:
 
         /// Original post not found when sharing.
         OriginalPostNotFound,
-        /// Cannot share a post that that is sharing another post.
+        /// Cannot share a post that that is sharing another post without its own content (a repost).
         CannotShareSharingPost,
+        /// Cannot quote a post that is already at the end of a chain of `MaxQuoteDepth` quotes.
+        MaxQuoteDepthReached,
+        /// A repost cannot be turned into a quote by updating its content, and vice versa.
+        CannotConvertSharingPost,
         /// This post's extension is not a `SharedPost`.
         NotASharingPost,
 
@@ -221,6 +541,8 @@ decl_error! {
         NotACommentAuthor,
         /// This post's extension is not a `Comment`.
         NotComment,
+        /// Cannot delete a post that has more than `MaxRepliesToDelete` nested replies.
+        TooManyRepliesToDelete,
 
         // Permissions related errors:
 
@@ -236,6 +558,67 @@ decl_error! {
         NoPermissionToUpdateOwnPosts,
         /// A comment owner is not allowed to update their own comments in this space.
         NoPermissionToUpdateOwnComments,
+        /// User has no permission to delete any posts in this space.
+        NoPermissionToDeleteAnyPost,
+        /// A post owner is not allowed to delete their own posts in this space.
+        NoPermissionToDeleteOwnPosts,
+        /// A comment owner is not allowed to delete their own comments in this space.
+        NoPermissionToDeleteOwnComments,
+        /// User has no permission to override permissions of posts in this space.
+        NoPermissionToOverridePostPermissions,
+        /// User has no permission to pin or unpin posts in this space.
+        NoPermissionToPinPosts,
+        /// User is blocked by the owner of the post (or comment) they try to reply to.
+        BlockedByPostOwner,
+
+        // Scheduling related errors:
+
+        /// Scheduled post was not found by id.
+        ScheduledPostNotFound,
+        /// An account is not an owner of the scheduled post.
+        NotAScheduledPostOwner,
+        /// A post can be scheduled only to a future block.
+        CannotScheduleInPast,
+        /// The scheduled post is already going to be published at this block.
+        NoUpdatesForScheduledPost,
+        /// Scheduler failed to schedule publishing of a post.
+        FailedToSchedulePost,
+        /// An account has reached the max number of scheduled posts.
+        TooManyScheduledPosts,
+        /// A scheduled post cannot be published before its target block.
+        ScheduledPostNotReady,
+
+        // Expiry related errors:
+
+        /// A post expiry can be set only to a future block.
+        CannotExpireInPast,
+
+        // Poll related errors:
+
+        /// This post's extension is not a `Poll`.
+        NotAPoll,
+        /// Poll should have from 2 to `MaxPollOptions` options.
+        InvalidPollOptionsCount,
+        /// Poll end block should be in the future.
+        PollEndsInPast,
+        /// Voting in this poll has ended.
+        PollEnded,
+        /// At least one option should be chosen to vote in a poll.
+        NoPollOptionsChosen,
+        /// Only one option can be chosen in a single-choice poll.
+        TooManyPollOptionsChosen,
+        /// Chosen poll option does not exist or is chosen more than once.
+        InvalidPollOption,
+        /// Account has already voted in this poll.
+        AlreadyVotedInPoll,
+        /// Not allowed to vote in a poll if its space is hidden.
+        CannotVoteWhenSpaceHidden,
+        /// Not allowed to vote in a poll if the poll is hidden.
+        CannotVoteWhenPollHidden,
+        /// Poll content cannot be updated after someone has voted in it.
+        CannotUpdatePollAfterVoting,
+        /// User has no permission to vote in polls in this space.
+        NoPermissionToVoteInPolls,
     }
 }
 
@@ -244,77 +627,83 @@ decl_module! {
 
     const MaxCommentDepth: u32 = T::MaxCommentDepth::get();
 
-    // Initializing errors
-    type Error = Error<T>;
+    const MaxRepliesToDelete: u32 = T::MaxRepliesToDelete::get();
 
-    // Initializing events
-    fn deposit_event() = default;
+    const MaxPinnedPostsPerSpace: u32 = T::MaxPinnedPostsPerSpace::get();
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(8, 8)]
-    pub fn create_post(
-      origin,
-      space_id_opt: Option<SpaceId>,
-      extension: PostExtension,
-      content: Content
-    ) -> DispatchResult {
-      let creator = ensure_signed(origin)?;
+    const MaxTagsPerPost: u32 = T::MaxTagsPerPost::get();
 
-      Utils::<T>::is_valid_content(content.clone())?;
+    const MaxTagLen: u32 = T::MaxTagLen::get();
 
-      let new_post_id = Self::next_post_id();
-      let new_post: Post<T> = Post::new(new_post_id, creator.clone(), space_id_opt, extension, content.clone());
+    const MaxMentionsPerPost: u32 = T::MaxMentionsPerPost::get();
 
-      // Get space from either space_id_opt or Comment if a comment provided
-      let space = &mut new_post.get_space()?;
-      ensure!(!space.hidden, Error::<T>::CannotCreateInHiddenScope);
+    const MaxExpiredPostsPerBlock: u32 = T::MaxExpiredPostsPerBlock::get();
 
-      ensure!(T::IsAccountBlocked::is_allowed_account(creator.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
-      ensure!(T::IsContentBlocked::is_allowed_content(content, space.id), UtilsError::<T>::ContentIsBlocked);
+    const MaxPollOptions: PollOptionIndex = T::MaxPollOptions::get();
 
-      let root_post = &mut new_post.get_root_post()?;
-      ensure!(!root_post.hidden, Error::<T>::CannotCreateInHiddenScope);
+    const MaxQuoteDepth: u32 = T::MaxQuoteDepth::get();
 
-      // Check whether account has permission to create Post (by extension)
-      let mut permission_to_check = SpacePermission::CreatePosts;
-      let mut error_on_permission_failed = Error::<T>::NoPermissionToCreatePosts;
+    const ScheduledPostDeposit: BalanceOf<T> = T::ScheduledPostDeposit::get();
 
-      if let PostExtension::Comment(_) = extension {
-        permission_to_check = SpacePermission::CreateComments;
-        error_on_permission_failed = Error::<T>::NoPermissionToCreateComments;
-      }
+    const MaxScheduledPostsPerAccount: u32 = T::MaxScheduledPostsPerAccount::get();
 
-      Spaces::ensure_account_has_space_permission(
-        creator.clone(),
-        &space,
-        permission_to_check,
-        error_on_permission_failed.into()
-      )?;
+    // Initializing errors
+    type Error = Error<T>;
+
+    // Initializing events
+    fn deposit_event() = default;
+
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+      let mut final_weight = 0;
+
+      if !PostByIdMigrated::get() {
+        final_weight = migrations::migrate_posts::<T>();
+      }
 
-      match extension {
-        PostExtension::RegularPost => space.inc_posts(),
-        PostExtension::SharedPost(post_id) => Self::create_sharing_post(&creator, new_post_id, post_id, space)?,
-        PostExtension::Comment(comment_ext) => Self::create_comment(new_post_id, comment_ext, root_post)?,
+      // Posts are read with the latest `Post` struct, so they should be migrated before.
+      if !PostsByOwnerMigrated::get() {
+        final_weight = final_weight.saturating_add(migrations::index_posts_by_owner::<T>());
       }
 
-      if new_post.is_root_post() {
-        SpaceById::insert(space.id, space.clone());
-        PostIdsBySpaceId::mutate(space.id, |ids| ids.push(new_post_id));
+      // Posts of owners are linked from the per-owner indexes, so they should be indexed before.
+      if !TimelinePostsLinked::get() {
+        final_weight = final_weight.saturating_add(migrations::link_timeline_posts::<T>());
       }
 
-      PostById::insert(new_post_id, new_post);
-      NextPostId::mutate(|n| { *n += 1; });
+      final_weight
+    }
+
+    fn on_initialize(n: T::BlockNumber) -> frame_support::weights::Weight {
+      Self::expire_posts(n)
+    }
+
+    /// Create a post. Accounts in `mentions` are notified about the new post,
+    /// duplicate mentions are ignored.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(14, 17)
+      + T::DbWeight::get().writes(tags.len() as u64 + mentions.len() as u64)]
+    pub fn create_post(
+      origin,
+      space_id_opt: Option<SpaceId>,
+      extension: PostExtension<T::BlockNumber>,
+      content: Content,
+      tags: Vec<Tag>,
+      mentions: Vec<T::AccountId>
+    ) -> DispatchResult {
+      let creator = ensure_signed(origin)?;
 
-      Self::deposit_event(RawEvent::PostCreated(creator, new_post_id));
+      Self::create_post_by_account(creator, space_id_opt, extension, content, tags, mentions)?;
       Ok(())
     }
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(5, 3)]
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(6, 4)
+      + T::DbWeight::get().writes(T::MaxTagsPerPost::get() as u64)]
     pub fn update_post(origin, post_id: PostId, update: PostUpdate) -> DispatchResult {
       let editor = ensure_signed(origin)?;
 
       let has_updates =
         update.content.is_some() ||
-        update.hidden.is_some();
+        update.hidden.is_some() ||
+        update.tags.is_some();
 
       ensure!(has_updates, Error::<T>::NoUpdatesForPost);
 
@@ -329,10 +718,26 @@ decl_module! {
       let mut is_update_applied = false;
       let mut old_data = PostUpdate::default();
 
+      let was_indexed_by_tags = post.is_indexed_by_tags();
+      let old_tags = Self::tags_by_post_id(post_id);
+      let mut new_tags = old_tags.clone();
+
       if let Some(content) = update.content {
         if content != post.content {
           Utils::<T>::is_valid_content(content.clone())?;
 
+          if post.is_sharing_post() {
+            ensure!(content.is_none() == post.content.is_none(), Error::<T>::CannotConvertSharingPost);
+          }
+
+          // Options of a poll are described in its content, so they should not change after voting.
+          if post.is_poll() {
+            ensure!(
+              Self::poll_results_by_post_id(post_id).voters_count == 0,
+              Error::<T>::CannotUpdatePollAfterVoting
+            );
+          }
+
           if let Some(space) = &space_opt {
             ensure!(
               T::IsContentBlocked::is_allowed_content(content.clone(), space.id),
@@ -346,6 +751,17 @@ decl_module! {
         }
       }
 
+      if let Some(tags) = update.tags {
+        let tags = Self::lowercase_and_validate_tags(tags)?;
+        if tags != old_tags {
+          ensure!(!post.is_comment(), Error::<T>::CannotTagComment);
+
+          old_data.tags = Some(old_tags.clone());
+          new_tags = tags;
+          is_update_applied = true;
+        }
+      }
+
       if let Some(hidden) = update.hidden {
         if hidden != post.hidden {
           space_opt = space_opt.map(|mut space| {
@@ -376,6 +792,16 @@ decl_module! {
           <SpaceById<T>>::insert(space.id, space);
         }
 
+        let old_indexed_tags: &[Tag] = if was_indexed_by_tags { &old_tags } else { &[] };
+        let new_indexed_tags: &[Tag] = if post.is_indexed_by_tags() { &new_tags } else { &[] };
+        Self::update_tags_index(post_id, old_indexed_tags, new_indexed_tags);
+
+        if new_tags.is_empty() {
+          TagsByPostId::remove(post_id);
+        } else {
+          TagsByPostId::insert(post_id, new_tags);
+        }
+
         <PostById<T>>::insert(post.id, post.clone());
         T::AfterPostUpdated::after_post_updated(editor.clone(), &post, old_data);
 
@@ -410,6 +836,7 @@ decl_module! {
         space_id: old_space_id,
         content: None,
         hidden: None,
+        tags: None,
       };
 
       T::AfterPostUpdated::after_post_updated(who.clone(), &post, historical_data);
@@ -417,5 +844,333 @@ decl_module! {
       Self::deposit_event(RawEvent::PostMoved(who, post_id));
       Ok(())
     }
+
+    /// Override space permissions for a given root post and its comments.
+    /// Pass `None` to remove the overrides from a post.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(4, 1)]
+    pub fn update_post_permissions(
+      origin,
+      post_id: PostId,
+      permissions_opt: Option<SpacePermissions>
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let mut post = Self::require_post(post_id)?;
+      ensure!(!post.is_comment(), Error::<T>::CannotOverrideCommentPermissions);
+
+      let space = post.get_space()?;
+      ensure!(T::IsAccountBlocked::is_allowed_account(who.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+      Spaces::ensure_account_has_space_permission(
+        who.clone(),
+        &space,
+        SpacePermission::OverridePostPermissions,
+        Error::<T>::NoPermissionToOverridePostPermissions.into()
+      )?;
+
+      let new_permissions = permissions_opt.map(Permissions::<T>::override_permissions);
+      ensure!(post.permissions != new_permissions, Error::<T>::NoUpdatesForPost);
+
+      post.permissions = new_permissions;
+      post.updated = Some(WhoAndWhen::<T>::new(who.clone()));
+      <PostById<T>>::insert(post_id, post);
+
+      Self::deposit_event(RawEvent::PostUpdated(who, post_id));
+      Ok(())
+    }
+
+    /// Delete a post together with all its nested replies.
+    /// A post with more than `MaxRepliesToDelete` nested replies cannot be deleted.
+    ///
+    /// Reactions (with their emoji counts), bookmarks and notifications of deleted posts are kept,
+    /// so that their owners can still remove them, but RPC readers skip the posts that don't exist.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(14, 15)
+      + T::MaxRepliesToDelete::get() as u64 * (25_000 + T::DbWeight::get().reads_writes(3, 8))]
+    pub fn delete_post(origin, post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let post = Self::require_post(post_id)?;
+      ensure!(post.replies_count <= T::MaxRepliesToDelete::get(), Error::<T>::TooManyRepliesToDelete);
+
+      if let Some(space) = post.try_get_space() {
+        Self::ensure_account_can_delete_post(&who, &post, &space)?;
+      } else {
+        post.ensure_owner(&who)?;
+      }
+
+      Self::remove_post_with_replies(&post)?;
+
+      Self::deposit_event(RawEvent::PostDeleted(who, post_id));
+      Ok(())
+    }
+
+    /// Pin a root post at the top of its space, e.g. a rules thread or an announcement.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(4, 1)]
+    pub fn pin_post(origin, post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let post = Self::require_post(post_id)?;
+      ensure!(!post.is_comment(), Error::<T>::CannotPinComment);
+
+      let space = post.get_space()?;
+      Self::ensure_account_can_pin_posts(who.clone(), &space)?;
+
+      let mut pinned_post_ids = Self::pinned_post_ids_by_space_id(space.id);
+      ensure!(!pinned_post_ids.contains(&post_id), Error::<T>::PostIsAlreadyPinned);
+      ensure!(
+        pinned_post_ids.len() < T::MaxPinnedPostsPerSpace::get() as usize,
+        Error::<T>::TooManyPinnedPosts
+      );
+
+      pinned_post_ids.push(post_id);
+      PinnedPostIdsBySpaceId::insert(space.id, pinned_post_ids);
+
+      Self::deposit_event(RawEvent::PostPinned(who, space.id, post_id));
+      Ok(())
+    }
+
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(4, 1)]
+    pub fn unpin_post(origin, post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let post = Self::require_post(post_id)?;
+      let space = post.get_space()?;
+      Self::ensure_account_can_pin_posts(who.clone(), &space)?;
+
+      ensure!(Self::unpin_post_in_space(space.id, post_id), Error::<T>::PostIsNotPinned);
+
+      Self::deposit_event(RawEvent::PostUnpinned(who, space.id, post_id));
+      Ok(())
+    }
+
+    /// Schedule a regular post to be published in a given space at a future block.
+    /// Until then, the post is a draft that only its author can reschedule or cancel.
+    /// Permissions are checked both now and at the time of publishing.
+    /// `ScheduledPostDeposit` is reserved from the author until the post is published or canceled.
+    // Transactional, because the deposit is reserved after the publishing is scheduled.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(7, 5)]
+    #[transactional]
+    pub fn schedule_post(
+      origin,
+      space_id: SpaceId,
+      content: Content,
+      tags: Vec<Tag>,
+      mentions: Vec<T::AccountId>,
+      publish_at: T::BlockNumber
+    ) -> DispatchResult {
+      let creator = ensure_signed(origin)?;
+
+      ensure!(publish_at > <system::Pallet<T>>::block_number(), Error::<T>::CannotScheduleInPast);
+
+      let scheduled_posts_count = Self::scheduled_posts_count_by_account(&creator);
+      ensure!(
+        scheduled_posts_count < T::MaxScheduledPostsPerAccount::get(),
+        Error::<T>::TooManyScheduledPosts
+      );
+
+      Utils::<T>::is_valid_content(content.clone())?;
+      let tags = Self::lowercase_and_validate_tags(tags)?;
+      let mentions = Self::dedup_mentions(mentions)?;
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      ensure!(!space.hidden, Error::<T>::CannotCreateInHiddenScope);
+
+      ensure!(T::IsAccountBlocked::is_allowed_account(creator.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+      ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), space.id), UtilsError::<T>::ContentIsBlocked);
+
+      Spaces::ensure_account_has_space_permission(
+        creator.clone(),
+        &space,
+        SpacePermission::CreatePosts,
+        Error::<T>::NoPermissionToCreatePosts.into()
+      )?;
+
+      let scheduled_post_id = Self::next_scheduled_post_id();
+      Self::schedule_publishing(scheduled_post_id, &creator, publish_at)?;
+      <T as pallet_spaces::Config>::Currency::reserve(&creator, T::ScheduledPostDeposit::get())?;
+
+      let scheduled_post = ScheduledPost {
+        id: scheduled_post_id,
+        created: WhoAndWhen::<T>::new(creator.clone()),
+        updated: None,
+        space_id,
+        content,
+        tags,
+        mentions,
+        publish_at,
+      };
+
+      <ScheduledPostById<T>>::insert(scheduled_post_id, scheduled_post);
+      NextScheduledPostId::mutate(|n| { *n += 1; });
+      <ScheduledPostsCountByAccount<T>>::insert(&creator, scheduled_posts_count.saturating_add(1));
+
+      Self::deposit_event(RawEvent::PostScheduled(creator, scheduled_post_id, publish_at));
+      Ok(())
+    }
+
+    /// Change the block at which a scheduled post will be published.
+    /// This also works for a post which failed to be published, e.g. due to a lack of permissions.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(3, 3)]
+    pub fn reschedule_post(
+      origin,
+      scheduled_post_id: ScheduledPostId,
+      publish_at: T::BlockNumber
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let mut scheduled_post = Self::require_scheduled_post(scheduled_post_id)?;
+      ensure!(scheduled_post.created.account == who, Error::<T>::NotAScheduledPostOwner);
+      ensure!(publish_at > <system::Pallet<T>>::block_number(), Error::<T>::CannotScheduleInPast);
+      ensure!(publish_at != scheduled_post.publish_at, Error::<T>::NoUpdatesForScheduledPost);
+
+      // The task is already gone if the previous attempt to publish the post failed.
+      let _ = T::Scheduler::cancel_named(Self::scheduled_post_task_name(scheduled_post_id));
+      Self::schedule_publishing(scheduled_post_id, &who, publish_at)?;
+
+      scheduled_post.publish_at = publish_at;
+      scheduled_post.updated = Some(WhoAndWhen::<T>::new(who.clone()));
+      <ScheduledPostById<T>>::insert(scheduled_post_id, scheduled_post);
+
+      Self::deposit_event(RawEvent::PostRescheduled(who, scheduled_post_id, publish_at));
+      Ok(())
+    }
+
+    /// Cancel publishing of a scheduled post, delete its draft and return the deposit.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(5, 5)]
+    pub fn cancel_scheduled_post(origin, scheduled_post_id: ScheduledPostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let scheduled_post = Self::require_scheduled_post(scheduled_post_id)?;
+      ensure!(scheduled_post.created.account == who, Error::<T>::NotAScheduledPostOwner);
+
+      // The task is already gone if the previous attempt to publish the post failed.
+      let _ = T::Scheduler::cancel_named(Self::scheduled_post_task_name(scheduled_post_id));
+      Self::remove_scheduled_post(scheduled_post_id, &who);
+
+      Self::deposit_event(RawEvent::ScheduledPostCanceled(who, scheduled_post_id));
+      Ok(())
+    }
+
+    /// Publish a scheduled post and return the deposit to its author.
+    /// Dispatched by the scheduler with a signed origin of the author at the target block of the post.
+    /// The author can also publish it manually after that block, e.g. if the scheduled attempt failed.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(17, 20)
+      + T::DbWeight::get().writes(T::MaxTagsPerPost::get() as u64 + T::MaxMentionsPerPost::get() as u64)]
+    pub fn publish_scheduled_post(origin, scheduled_post_id: ScheduledPostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let scheduled_post = Self::require_scheduled_post(scheduled_post_id)?;
+      let creator = scheduled_post.created.account;
+      ensure!(creator == who, Error::<T>::NotAScheduledPostOwner);
+      ensure!(
+        scheduled_post.publish_at <= <system::Pallet<T>>::block_number(),
+        Error::<T>::ScheduledPostNotReady
+      );
+
+      let post_id = Self::create_post_by_account(
+        creator.clone(),
+        Some(scheduled_post.space_id),
+        PostExtension::RegularPost,
+        scheduled_post.content,
+        scheduled_post.tags,
+        scheduled_post.mentions,
+      )?;
+
+      Self::remove_scheduled_post(scheduled_post_id, &creator);
+
+      Self::deposit_event(RawEvent::ScheduledPostPublished(creator, scheduled_post_id, post_id));
+      Ok(())
+    }
+
+    /// Set a block at which a post expires and whether it should be hidden or deleted then.
+    /// Pass `None` as `expires_at` to make a post permanent again.
+    /// The `Delete` action requires the same permissions as `delete_post`, and a post that has
+    /// too many replies to be deleted in a single block is hidden instead when it expires.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(8, 4)]
+    pub fn set_post_expiry(
+      origin,
+      post_id: PostId,
+      expires_at: Option<T::BlockNumber>,
+      action: PostExpiryAction
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let mut post = Self::require_post(post_id)?;
+      post.ensure_owner(&who)?;
+
+      // An author should not be able to delete a post with expiry, if they cannot delete it directly.
+      if action == PostExpiryAction::Delete {
+        if let Some(space) = post.try_get_space() {
+          Self::ensure_account_can_delete_post(&who, &post, &space)?;
+        }
+      }
+
+      let current_block = <system::Pallet<T>>::block_number();
+      if let Some(block) = expires_at {
+        ensure!(block > current_block, Error::<T>::CannotExpireInPast);
+      }
+
+      if let Some(old_block) = post.expires_at {
+        PostsExpiringAt::<T>::remove(old_block, post_id);
+      }
+
+      if let Some(block) = expires_at {
+        PostsExpiringAt::<T>::insert(block, post_id, action);
+
+        if Self::next_post_expiry_block().is_none() {
+          NextPostExpiryBlock::<T>::put(current_block);
+        }
+      }
+
+      post.expires_at = expires_at;
+      post.updated = Some(WhoAndWhen::<T>::new(who.clone()));
+      <PostById<T>>::insert(post_id, post);
+
+      Self::deposit_event(RawEvent::PostExpiryUpdated(who, post_id));
+      Ok(())
+    }
+
+    /// Vote for one option of a single-choice poll or for one or more options
+    /// of a multiple-choice poll. An account can vote in a poll only once.
+    #[weight = 50_000 + T::DbWeight::get().reads_writes(6, 2)]
+    pub fn vote_in_poll(origin, post_id: PostId, option_indices: Vec<PollOptionIndex>) -> DispatchResult {
+      let voter = ensure_signed(origin)?;
+
+      let post = Self::require_post(post_id)?;
+      let poll = post.get_poll()?;
+
+      ensure!(<system::Pallet<T>>::block_number() <= poll.ends_at, Error::<T>::PollEnded);
+      ensure!(!PollVoteByAccount::<T>::contains_key(post_id, &voter), Error::<T>::AlreadyVotedInPoll);
+
+      let option_indices = Self::validate_poll_choice(&poll, option_indices)?;
+
+      let space = post.get_space()?;
+      ensure!(!space.hidden, Error::<T>::CannotVoteWhenSpaceHidden);
+      ensure!(!post.hidden, Error::<T>::CannotVoteWhenPollHidden);
+
+      ensure!(T::IsAccountBlocked::is_allowed_account(voter.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+      ensure!(T::IsPostBlocked::is_allowed_post(post_id, space.id), UtilsError::<T>::PostIsBlocked);
+
+      Self::ensure_account_has_post_permission(
+        voter.clone(),
+        &post,
+        &space,
+        SpacePermission::VoteInPolls,
+        Error::<T>::NoPermissionToVoteInPolls.into()
+      )?;
+
+      PollResultsByPostId::mutate(post_id, |results| {
+        results.voters_count = results.voters_count.saturating_add(1);
+        for index in option_indices.iter() {
+          if let Some(votes) = results.votes.get_mut(*index as usize) {
+            *votes = votes.saturating_add(1);
+          }
+        }
+      });
+      PollVoteByAccount::<T>::insert(post_id, &voter, option_indices);
+
+      Self::deposit_event(RawEvent::VotedInPoll(voter, post_id));
+      Ok(())
+    }
   }
 }
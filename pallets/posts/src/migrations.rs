@@ -0,0 +1,167 @@
+use frame_support::storage::{IterableStorageMap, StorageMap};
+use sp_std::collections::btree_map::BTreeMap;
+
+use super::*;
+
+/// A `Post` struct as it was stored before the `permissions`, `expires_at` and `quotes_count` fields
+/// were added and its counters were widened from `u16` to `u32`.
+#[derive(Decode)]
+struct PostWithoutPermissions<T: Config> {
+    id: PostId,
+    created: WhoAndWhen<T>,
+    updated: Option<WhoAndWhen<T>>,
+    owner: T::AccountId,
+    extension: PostExtension<T::BlockNumber>,
+    space_id: Option<SpaceId>,
+    content: Content,
+    hidden: bool,
+    replies_count: u16,
+    hidden_replies_count: u16,
+    shares_count: u16,
+    upvotes_count: u16,
+    downvotes_count: u16,
+    score: i32,
+}
+
+pub fn migrate_posts<T: Config>() -> frame_support::weights::Weight {
+    let mut posts_migrated = 0;
+    let mut quotes_counter = QuotesCounter::default();
+
+    PostById::<T>::translate::<PostWithoutPermissions<T>, _>(|_, old_post| {
+        posts_migrated += 1;
+        quotes_counter.count(&old_post.extension, &old_post.content);
+
+        Some(Post {
+            id: old_post.id,
+            created: old_post.created,
+            updated: old_post.updated,
+            owner: old_post.owner,
+            extension: old_post.extension,
+            space_id: old_post.space_id,
+            content: old_post.content,
+            hidden: old_post.hidden,
+            replies_count: old_post.replies_count.into(),
+            hidden_replies_count: old_post.hidden_replies_count.into(),
+            shares_count: old_post.shares_count.into(),
+            upvotes_count: old_post.upvotes_count.into(),
+            downvotes_count: old_post.downvotes_count.into(),
+            score: old_post.score,
+            permissions: None,
+            expires_at: None,
+            quotes_count: 0,
+        })
+    });
+
+    let quoted_posts = quotes_counter.store::<T>();
+    PostByIdMigrated::put(true);
+
+    T::DbWeight::get().reads_writes(posts_migrated + quoted_posts + 1, posts_migrated + quoted_posts + 1)
+}
+
+fn insert_sorted_post_ids<T: Config, S: StorageMap<T::AccountId, Vec<PostId>>>(
+    post_ids_by_owner: BTreeMap<T::AccountId, Vec<PostId>>,
+) -> u64 {
+    let owners_indexed = post_ids_by_owner.len() as u64;
+
+    for (owner, mut post_ids) in post_ids_by_owner {
+        post_ids.sort_unstable();
+        S::insert(owner, post_ids);
+    }
+
+    owners_indexed
+}
+
+pub fn index_posts_by_owner<T: Config>() -> frame_support::weights::Weight {
+    let mut posts_read = 0;
+    let mut regular_post_ids: BTreeMap<T::AccountId, Vec<PostId>> = BTreeMap::new();
+    let mut sharing_post_ids: BTreeMap<T::AccountId, Vec<PostId>> = BTreeMap::new();
+    let mut comment_ids: BTreeMap<T::AccountId, Vec<PostId>> = BTreeMap::new();
+
+    for post in PostById::<T>::iter_values() {
+        posts_read += 1;
+
+        let post_ids_by_owner = match post.extension {
+            PostExtension::RegularPost | PostExtension::Poll(_) => &mut regular_post_ids,
+            PostExtension::SharedPost(_) => &mut sharing_post_ids,
+            PostExtension::Comment(_) => &mut comment_ids,
+        };
+        post_ids_by_owner.entry(post.owner).or_insert_with(Vec::new).push(post.id);
+    }
+
+    let owners_indexed =
+        insert_sorted_post_ids::<T, RegularPostIdsByOwner<T>>(regular_post_ids)
+        + insert_sorted_post_ids::<T, SharingPostIdsByOwner<T>>(sharing_post_ids)
+        + insert_sorted_post_ids::<T, CommentIdsByOwner<T>>(comment_ids);
+
+    PostsByOwnerMigrated::put(true);
+
+    T::DbWeight::get().reads_writes(posts_read, owners_indexed + 1)
+}
+
+pub fn link_timeline_posts<T: Config>() -> frame_support::weights::Weight {
+    let mut indexes_read = 0;
+    let mut post_ids_by_source: BTreeMap<TimelineSource<T::AccountId>, Vec<PostId>> = BTreeMap::new();
+
+    for (space_id, post_ids) in PostIdsBySpaceId::iter() {
+        indexes_read += 1;
+        post_ids_by_source.insert(TimelineSource::Space(space_id), post_ids);
+    }
+
+    for (owner, post_ids) in RegularPostIdsByOwner::<T>::iter().chain(SharingPostIdsByOwner::<T>::iter()) {
+        indexes_read += 1;
+        post_ids_by_source.entry(TimelineSource::Owner(owner)).or_insert_with(Vec::new).extend(post_ids);
+    }
+
+    let mut links_written = 0;
+
+    for (source, mut post_ids) in post_ids_by_source {
+        post_ids.sort_unstable();
+
+        for pair in post_ids.windows(2) {
+            PreviousPostIdBySource::<T>::insert(&source, pair[1], pair[0]);
+        }
+        if let Some(latest_post_id) = post_ids.last() {
+            LatestPostIdBySource::<T>::insert(&source, latest_post_id);
+        }
+
+        links_written += post_ids.len() as u64;
+    }
+
+    TimelinePostsLinked::put(true);
+
+    T::DbWeight::get().reads_writes(indexes_read, links_written + 1)
+}
+
+/// Counts quotes, i.e. sharing posts with their own content, of posts being migrated
+/// from the struct that had no `quotes_count` field.
+#[derive(Default)]
+struct QuotesCounter {
+    quotes_count_by_post_id: BTreeMap<PostId, u32>,
+}
+
+impl QuotesCounter {
+    fn count<BlockNumber>(&mut self, extension: &PostExtension<BlockNumber>, content: &Content) {
+        if let PostExtension::SharedPost(original_post_id) = extension {
+            if content.is_some() {
+                let quotes_count = self.quotes_count_by_post_id.entry(*original_post_id).or_insert(0);
+                *quotes_count = quotes_count.saturating_add(1);
+            }
+        }
+    }
+
+    /// Store the counted quotes in the quoted posts. Returns the number of quoted posts.
+    fn store<T: Config>(self) -> u64 {
+        let quoted_posts = self.quotes_count_by_post_id.len() as u64;
+
+        for (post_id, quotes_count) in self.quotes_count_by_post_id {
+            // The quoted post could have been deleted.
+            PostById::<T>::mutate(post_id, |post_opt| {
+                if let Some(post) = post_opt {
+                    post.quotes_count = quotes_count;
+                }
+            });
+        }
+
+        quoted_posts
+    }
+}
@@ -4,23 +4,39 @@ use serde::{Deserialize, Serialize};
 use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 use sp_std::{vec, prelude::*};
 
+use pallet_profile_follows::Module as ProfileFollows;
 use pallet_space_follows::Module as SpaceFollows;
 use pallet_spaces::Module as Spaces;
 use pallet_utils::{bool_to_option, PostId, rpc::{FlatContent, FlatWhoAndWhen, ShouldSkip}, SpaceId};
 
-use crate::{Module, Post, PostExtension, FIRST_POST_ID, Config};
+use crate::{Module, Post, PostExtension, PollKind, PollOptionIndex, Tag, TimelineSource, FIRST_POST_ID, Config};
 pub type RepliesByPostId<AccountId, BlockNumber> = BTreeMap<PostId, Vec<FlatPost<AccountId, BlockNumber>>>;
 
+/// The max number of posts that can be examined by a single timeline request.
+pub const MAX_TIMELINE_SCAN_DEPTH: u32 = 1_000;
+
+/// A position in the home timeline of an account: the id of the latest post
+/// that is not examined yet, for every followed source that has older posts.
+pub type TimelineCursor<AccountId> = Vec<(TimelineSource<AccountId>, PostId)>;
+
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub struct FlatPostExtension {
+pub struct FlatPostExtension<BlockNumber> {
     #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
     pub is_regular_post: Option<bool>,
     #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
     pub is_shared_post: Option<bool>,
+    /// Whether a sharing post has no content of its own.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub is_repost: Option<bool>,
+    /// Whether a sharing post has its own content, e.g. a commentary on the shared post.
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub is_quote: Option<bool>,
     #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
     pub is_comment: Option<bool>,
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub is_poll: Option<bool>,
 
     #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
     pub root_post_id: Option<PostId>,
@@ -28,10 +44,25 @@ pub struct FlatPostExtension {
     pub parent_post_id: Option<PostId>,
     #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
     pub shared_post_id: Option<PostId>,
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub poll: Option<FlatPoll<BlockNumber>>,
 }
 
-impl From<PostExtension> for FlatPostExtension {
-    fn from(from: PostExtension) -> Self {
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FlatPoll<BlockNumber> {
+    pub options_count: PollOptionIndex,
+    pub kind: PollKind,
+    pub ends_at: BlockNumber,
+
+    pub voters_count: u32,
+    /// The number of votes per option, indexed by `PollOptionIndex`.
+    pub votes: Vec<u32>,
+}
+
+impl<BlockNumber> From<PostExtension<BlockNumber>> for FlatPostExtension<BlockNumber> {
+    fn from(from: PostExtension<BlockNumber>) -> Self {
         let mut flat_ext = Self::default();
 
         match from {
@@ -47,6 +78,17 @@ impl From<PostExtension> for FlatPostExtension {
                 flat_ext.is_shared_post = Some(true);
                 flat_ext.shared_post_id = Some(shared_post_id);
             }
+            PostExtension::Poll(poll) => {
+                flat_ext.is_poll = Some(true);
+                // Votes are stored separately from a post, see `From<Post<T>> for FlatPost`.
+                flat_ext.poll = Some(FlatPoll {
+                    options_count: poll.options_count,
+                    kind: poll.kind,
+                    ends_at: poll.ends_at,
+                    voters_count: 0,
+                    votes: Vec::new(),
+                });
+            }
         }
 
         flat_ext
@@ -74,15 +116,42 @@ pub struct FlatPost<AccountId, BlockNumber> {
     pub is_hidden: Option<bool>,
 
     #[cfg_attr(feature = "std", serde(flatten))]
-    pub extension: FlatPostExtension,
+    pub extension: FlatPostExtension<BlockNumber>,
+
+    pub replies_count: u32,
+    pub hidden_replies_count: u32,
+    pub visible_replies_count: u32,
+
+    pub shares_count: u32,
+    pub reposts_count: u32,
+    pub quotes_count: u32,
+
+    pub upvotes_count: u32,
+    pub downvotes_count: u32,
+
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "Vec::is_empty", serialize_with = "tags_to_strings"))]
+    pub tags: Vec<Tag>,
 
-    pub replies_count: u16,
-    pub hidden_replies_count: u16,
-    pub visible_replies_count: u16,
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip"))]
+    pub expires_at: Option<BlockNumber>,
+}
 
-    pub shares_count: u16,
-    pub upvotes_count: u16,
-    pub downvotes_count: u16,
+#[cfg(feature = "std")]
+fn tags_to_strings<S>(tags: &[Tag], serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+    // Tags are validated on-chain, so they are always valid UTF-8 strings
+    serializer.collect_seq(tags.iter().map(|tag| std::str::from_utf8(tag).unwrap_or_default()))
+}
+
+/// A page of the home timeline of an account.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FlatTimeline<AccountId, BlockNumber> {
+    pub posts: Vec<FlatPost<AccountId, BlockNumber>>,
+
+    /// Pass this cursor to get the next (older) page of a timeline.
+    /// `None` if there are no older posts.
+    pub next_cursor: Option<TimelineCursor<AccountId>>,
 }
 
 #[derive(Encode, Decode, Ord, PartialOrd, Clone, Eq, PartialEq)]
@@ -96,7 +165,8 @@ pub enum FlatPostKind {
 impl<T: Config> From<Post<T>> for FlatPostKind {
     fn from(from: Post<T>) -> Self {
         match from.extension {
-            PostExtension::RegularPost => { Self::RegularPost }
+            // A poll is a regular post with options to vote for.
+            PostExtension::RegularPost | PostExtension::Poll(_) => { Self::RegularPost }
             PostExtension::Comment(_) => { Self::Comment }
             PostExtension::SharedPost(_) => { Self::SharedPost }
         }
@@ -108,9 +178,21 @@ impl<T: Config> From<Post<T>> for FlatPost<T::AccountId, T::BlockNumber> {
         let Post {
             id, created, updated, owner,
             extension, space_id, content, hidden, replies_count,
-            hidden_replies_count, shares_count, upvotes_count, downvotes_count, ..
+            hidden_replies_count, shares_count, upvotes_count, downvotes_count, expires_at,
+            quotes_count, ..
         } = from;
 
+        let mut extension: FlatPostExtension<T::BlockNumber> = extension.into();
+        if extension.is_shared_post.is_some() {
+            extension.is_repost = bool_to_option(content.is_none());
+            extension.is_quote = bool_to_option(content.is_some());
+        }
+        if let Some(poll) = &mut extension.poll {
+            let results = Module::<T>::poll_results_by_post_id(id);
+            poll.voters_count = results.voters_count;
+            poll.votes = results.votes;
+        }
+
         Self {
             id,
             who_and_when: (created, updated).into(),
@@ -118,13 +200,17 @@ impl<T: Config> From<Post<T>> for FlatPost<T::AccountId, T::BlockNumber> {
             space_id,
             content: content.into(),
             is_hidden: bool_to_option(hidden),
-            extension: extension.into(),
+            extension,
             replies_count,
             hidden_replies_count,
             visible_replies_count: replies_count.saturating_sub(hidden_replies_count),
             shares_count,
+            reposts_count: shares_count.saturating_sub(quotes_count),
+            quotes_count,
             upvotes_count,
             downvotes_count,
+            tags: Module::<T>::tags_by_post_id(id),
+            expires_at,
         }
     }
 }
@@ -138,9 +224,8 @@ impl<T: Config> Module<T> {
     ) -> Vec<FlatPost<T::AccountId, T::BlockNumber>> {
         let mut posts = Vec::new();
 
-        let (_, posts_ids) = all_post_ids.split_at(offset as usize);
-
-        for post_id in posts_ids.iter() {
+        // An offset past the end of the list results in an empty page.
+        for post_id in all_post_ids.iter().skip(offset as usize) {
             if let Ok(post) = Self::require_post(*post_id) {
                 if filter(&post) {
                     posts.push(post.into());
@@ -181,12 +266,29 @@ impl<T: Config> Module<T> {
         Self::get_posts_by_ids_with_filter(post_ids, offset, limit, filter)
     }
 
+    /// Get public posts of a space, starting from the latest ones.
+    /// If `pinned_first` is `true`, pinned posts go first, in the order they were pinned.
     pub fn get_public_posts_by_space_id(
         space_id: SpaceId,
         offset: u64,
         limit: u16,
+        pinned_first: bool,
     ) -> Vec<FlatPost<T::AccountId, T::BlockNumber>> {
         if let Ok(space) = Spaces::<T>::require_space(space_id) {
+            if pinned_first {
+                let pinned_post_ids = Self::pinned_post_ids_by_space_id(space.id);
+
+                let mut post_ids = pinned_post_ids.clone();
+                post_ids.extend(
+                    Self::post_ids_by_space_id(space.id)
+                        .into_iter()
+                        .rev()
+                        .filter(|post_id| !pinned_post_ids.contains(post_id))
+                );
+
+                return Self::get_posts_by_ids_with_filter(post_ids, offset, limit, |post| post.is_public());
+            }
+
             return Self::get_posts_slice_by_space_id(space.id, offset, limit, |post| post.is_public());
         }
 
@@ -299,15 +401,241 @@ impl<T: Config> Module<T> {
         Self::next_post_id()
     }
 
-    pub fn get_feed(account: T::AccountId, offset: u64, limit: u16) -> Vec<FlatPost<T::AccountId, T::BlockNumber>> {
-        let mut post_ids: Vec<PostId> = SpaceFollows::<T>::spaces_followed_by_account(account)
-            .iter()
-            .flat_map(Self::post_ids_by_space_id)
-            .collect();
+    /// Get the ids of visible root posts with a given tag, starting from the latest ones.
+    pub fn get_post_ids_by_tag(tag: Tag, offset: u64, limit: u16) -> Vec<PostId> {
+        let mut post_ids = Self::post_ids_by_tag(Self::normalize_tag(tag));
+
+        // Sort post ids in a descending order
+        post_ids.sort_by(|a, b| b.cmp(a));
+
+        post_ids.into_iter().skip(offset as usize).take(limit as usize).collect()
+    }
+
+    /// Get public root posts with a given tag, starting from the latest ones.
+    pub fn get_posts_by_tag(tag: Tag, offset: u64, limit: u16) -> Vec<FlatPost<T::AccountId, T::BlockNumber>> {
+        let mut post_ids = Self::post_ids_by_tag(Self::normalize_tag(tag));
+
+        // Sort post ids in a descending order
+        post_ids.sort_by(|a, b| b.cmp(a));
+
+        Self::get_posts_by_ids_with_filter(post_ids, offset, limit, |post| post.is_public())
+    }
+
+    fn get_post_ids_by_account_with_kinds(account: &T::AccountId, kind_filter: Vec<FlatPostKind>) -> Vec<PostId> {
+        let no_filter = kind_filter.is_empty();
+        let kind_filter_set: BTreeSet<_> = kind_filter.into_iter().collect();
+        let is_requested = |kind: FlatPostKind| no_filter || kind_filter_set.contains(&kind);
+
+        let mut post_ids = Vec::new();
+        if is_requested(FlatPostKind::RegularPost) {
+            post_ids.extend(Self::regular_post_ids_by_owner(account));
+        }
+        if is_requested(FlatPostKind::SharedPost) {
+            post_ids.extend(Self::sharing_post_ids_by_owner(account));
+        }
+        if is_requested(FlatPostKind::Comment) {
+            post_ids.extend(Self::comment_ids_by_owner(account));
+        }
+
+        // Sort post ids in a descending order
+        post_ids.sort_by(|a, b| b.cmp(a));
+        post_ids
+    }
+
+    /// Get the ids of posts created by a given account, starting from the latest ones.
+    /// If `kind_filter` is empty, posts of all kinds are returned. Hidden posts are included.
+    pub fn get_post_ids_by_account(
+        account: T::AccountId,
+        kind_filter: Vec<FlatPostKind>,
+        offset: u64,
+        limit: u16,
+    ) -> Vec<PostId> {
+        Self::get_post_ids_by_account_with_kinds(&account, kind_filter)
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Get public posts created by a given account, starting from the latest ones.
+    /// If `kind_filter` is empty, posts of all kinds are returned.
+    pub fn get_posts_by_account(
+        account: T::AccountId,
+        kind_filter: Vec<FlatPostKind>,
+        offset: u64,
+        limit: u16,
+    ) -> Vec<FlatPost<T::AccountId, T::BlockNumber>> {
+        let post_ids = Self::get_post_ids_by_account_with_kinds(&account, kind_filter);
+        Self::get_posts_by_ids_with_filter(post_ids, offset, limit, |post| post.is_public())
+    }
+
+    /// Get the ids of posts that share a given post, starting from the latest ones.
+    pub fn get_sharing_post_ids(post_id: PostId, offset: u64, limit: u16) -> Vec<PostId> {
+        let mut post_ids = Self::shared_post_ids_by_original_post_id(post_id);
+
+        // Sort post ids in a descending order
+        post_ids.sort_by(|a, b| b.cmp(a));
+
+        post_ids.into_iter().skip(offset as usize).take(limit as usize).collect()
+    }
+
+    /// Get public posts that share a given post (both reposts and quotes), starting from the latest ones.
+    pub fn get_sharing_posts(
+        post_id: PostId,
+        offset: u64,
+        limit: u16,
+    ) -> Vec<FlatPost<T::AccountId, T::BlockNumber>> {
+        let mut post_ids = Self::shared_post_ids_by_original_post_id(post_id);
 
         // Sort post ids in a descending order
         post_ids.sort_by(|a, b| b.cmp(a));
 
-        Self::get_posts_by_ids_with_filter(post_ids, offset, limit, |post| post.is_public() && !post.is_comment())
+        Self::get_posts_by_ids_with_filter(post_ids, offset, limit, |post| post.is_public())
+    }
+
+    /// Get public root posts and sharing posts from the spaces and the accounts followed by `account`,
+    /// starting from the latest ones. Comments are never included in a feed.
+    ///
+    /// Prefer `get_timeline`: this function reads every post of every followed space and account,
+    /// and offset pagination skips or repeats posts when new ones are created.
+    pub fn get_feed(
+        account: T::AccountId,
+        kind_filter: Vec<FlatPostKind>,
+        offset: u64,
+        limit: u16,
+    ) -> Vec<FlatPost<T::AccountId, T::BlockNumber>> {
+
+        let no_filter = kind_filter.is_empty();
+        let kind_filter_set: BTreeSet<_> = kind_filter.into_iter().collect();
+
+        let space_post_ids = SpaceFollows::<T>::spaces_followed_by_account(&account)
+            .iter()
+            .flat_map(Self::post_ids_by_space_id);
+
+        let owner_post_ids = ProfileFollows::<T>::accounts_followed_by_account(&account)
+            .iter()
+            .flat_map(|followed| {
+                Self::regular_post_ids_by_owner(followed).into_iter()
+                    .chain(Self::sharing_post_ids_by_owner(followed))
+            });
+
+        // A post can be both in a followed space and created by a followed account.
+        let post_ids: BTreeSet<PostId> = space_post_ids.chain(owner_post_ids).collect();
+
+        // Sort post ids in a descending order
+        let post_ids: Vec<PostId> = post_ids.into_iter().rev().collect();
+
+        Self::get_posts_by_ids_with_filter(post_ids, offset, limit, |post| {
+            let kind: FlatPostKind = post.clone().into();
+            post.is_public() && !post.is_comment() && (no_filter || kind_filter_set.contains(&kind))
+        })
+    }
+
+    /// Get public root posts and shares from the spaces and the accounts followed by `account`,
+    /// starting from the latest ones. Pass `next_cursor` of a page as `cursor` to get the next page.
+    ///
+    /// Posts of every followed space and account are linked from the latest to the oldest one,
+    /// so each of them is walked down from its position in the cursor, without reading whole indexes.
+    /// At most `MAX_TIMELINE_SCAN_DEPTH` posts are examined per request, so a page can contain less
+    /// than `limit` posts even if `next_cursor` is present. Spaces and accounts followed after
+    /// the first page was requested are included only when the timeline is requested without a cursor.
+    pub fn get_timeline(
+        account: T::AccountId,
+        cursor: Option<TimelineCursor<T::AccountId>>,
+        limit: u16,
+    ) -> FlatTimeline<T::AccountId, T::BlockNumber> {
+
+        let followed_sources = SpaceFollows::<T>::spaces_followed_by_account(&account)
+            .into_iter()
+            .map(TimelineSource::Space)
+            .chain(
+                ProfileFollows::<T>::accounts_followed_by_account(&account)
+                    .into_iter()
+                    .map(TimelineSource::Owner)
+            );
+
+        let mut scanned: u32 = 0;
+
+        // The id of the next post to examine in every source, `None` if the source has no older posts.
+        let mut sources: Vec<(TimelineSource<T::AccountId>, Option<PostId>)> = followed_sources
+            .map(|source| {
+                let next_post_id = match &cursor {
+                    None => Self::latest_post_id_by_source(&source),
+                    Some(cursor) => cursor.iter()
+                        .find(|(cursor_source, _)| *cursor_source == source)
+                        .and_then(|(_, post_id)| Self::find_timeline_post_id(&source, *post_id, &mut scanned)),
+                };
+                (source, next_post_id)
+            })
+            .collect();
+
+        let mut timeline = FlatTimeline {
+            posts: Vec::new(),
+            next_cursor: None,
+        };
+
+        while timeline.posts.len() < limit as usize && scanned < MAX_TIMELINE_SCAN_DEPTH {
+            let latest_post_id = match sources.iter().filter_map(|(_, post_id)| *post_id).max() {
+                Some(post_id) => post_id,
+                None => break,
+            };
+            scanned += 1;
+
+            // A post can be both in a followed space and created by a followed account.
+            for (source, next_post_id) in sources.iter_mut() {
+                if *next_post_id == Some(latest_post_id) {
+                    *next_post_id = Self::previous_post_id_by_source(&*source, latest_post_id);
+                }
+            }
+
+            if let Some(post) = Self::post_by_id(latest_post_id) {
+                if post.is_public() {
+                    timeline.posts.push(post.into());
+                }
+            }
+        }
+
+        let next_cursor: TimelineCursor<T::AccountId> = sources.into_iter()
+            .filter_map(|(source, next_post_id)| next_post_id.map(|post_id| (source, post_id)))
+            .collect();
+
+        if !next_cursor.is_empty() {
+            timeline.next_cursor = Some(next_cursor);
+        }
+        timeline
+    }
+
+    /// Find the post of a source to continue the timeline from, given the post from a cursor.
+    /// The cursor post could have been moved or deleted since the previous page was requested,
+    /// then the source is walked down from its latest post to the first post older than the cursor one.
+    fn find_timeline_post_id(
+        source: &TimelineSource<T::AccountId>,
+        cursor_post_id: PostId,
+        scanned: &mut u32,
+    ) -> Option<PostId> {
+        let is_in_source = Self::post_by_id(cursor_post_id)
+            .filter(|post| !post.is_comment())
+            .map_or(false, |post| match source {
+                TimelineSource::Space(space_id) => post.space_id == Some(*space_id),
+                TimelineSource::Owner(owner) => post.owner == *owner,
+            });
+
+        if is_in_source {
+            return Some(cursor_post_id);
+        }
+
+        let mut post_id_opt = Self::latest_post_id_by_source(source);
+        while let Some(post_id) = post_id_opt {
+            if post_id < cursor_post_id {
+                break;
+            }
+            if *scanned >= MAX_TIMELINE_SCAN_DEPTH {
+                // Keep the source at the cursor post, so that the next request continues from there.
+                return Some(cursor_post_id);
+            }
+            *scanned += 1;
+            post_id_opt = Self::previous_post_id_by_source(source, post_id);
+        }
+        post_id_opt
     }
 }
@@ -3,10 +3,21 @@
 use codec::Codec;
 use sp_std::vec::Vec;
 
+use pallet_profiles::rpc::FlatSocialAccount;
+
 sp_api::decl_runtime_apis! {
-    pub trait ProfileFollowsApi<AccountId> where
-        AccountId: Codec
+    pub trait ProfileFollowsApi<AccountId, BlockNumber> where
+        AccountId: Codec,
+        BlockNumber: Codec
     {
         fn filter_followed_accounts(account: AccountId, maybe_following: Vec<AccountId>) -> Vec<AccountId>;
+
+        fn get_account_followers(account: AccountId, offset: u64, limit: u16) -> Vec<FlatSocialAccount<AccountId, BlockNumber>>;
+
+        fn get_accounts_followed_by_account(
+            account: AccountId,
+            offset: u64,
+            limit: u16,
+        ) -> Vec<FlatSocialAccount<AccountId, BlockNumber>>;
     }
 }
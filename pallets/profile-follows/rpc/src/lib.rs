@@ -6,11 +6,12 @@ use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
 
+use pallet_profiles::rpc::FlatSocialAccount;
 use pallet_utils::rpc::map_rpc_error;
 pub use profile_follows_runtime_api::ProfileFollowsApi as ProfileFollowsRuntimeApi;
 
 #[rpc]
-pub trait ProfileFollowsApi<BlockHash, AccountId> {
+pub trait ProfileFollowsApi<BlockHash, AccountId, BlockNumber> {
     #[rpc(name = "profileFollows_filterFollowedAccounts")]
     fn filter_followed_accounts(
         &self,
@@ -18,6 +19,24 @@ pub trait ProfileFollowsApi<BlockHash, AccountId> {
         account: AccountId,
         maybe_following: Vec<AccountId>,
     ) -> Result<Vec<AccountId>>;
+
+    #[rpc(name = "profileFollows_getAccountFollowers")]
+    fn get_account_followers(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatSocialAccount<AccountId, BlockNumber>>>;
+
+    #[rpc(name = "profileFollows_getAccountsFollowedByAccount")]
+    fn get_accounts_followed_by_account(
+        &self,
+        at: Option<BlockHash>,
+        account: AccountId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatSocialAccount<AccountId, BlockNumber>>>;
 }
 
 pub struct ProfileFollows<C, M> {
@@ -34,13 +53,14 @@ impl<C, M> ProfileFollows<C, M> {
     }
 }
 
-impl<C, Block, AccountId> ProfileFollowsApi<<Block as BlockT>::Hash, AccountId>
+impl<C, Block, AccountId, BlockNumber> ProfileFollowsApi<<Block as BlockT>::Hash, AccountId, BlockNumber>
     for ProfileFollows<C, Block>
 where
     Block: BlockT,
     AccountId: Codec,
+    BlockNumber: Codec,
     C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: ProfileFollowsRuntimeApi<Block, AccountId>,
+    C::Api: ProfileFollowsRuntimeApi<Block, AccountId, BlockNumber>,
 {
     fn filter_followed_accounts(
         &self, at:
@@ -54,4 +74,32 @@ where
         let runtime_api_result = api.filter_followed_accounts(&at, account, maybe_following);
         runtime_api_result.map_err(map_rpc_error)
     }
+
+    fn get_account_followers(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatSocialAccount<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_account_followers(&at, account, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_accounts_followed_by_account(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: AccountId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatSocialAccount<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_accounts_followed_by_account(&at, account, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
 }
@@ -3,7 +3,8 @@
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::DispatchResult,
-    traits::Get
+    traits::Get,
+    transactional,
 };
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
@@ -11,6 +12,7 @@ use frame_system::{self as system, ensure_signed};
 use pallet_profiles::{Module as Profiles, SocialAccountById};
 use pallet_utils::remove_from_vec;
 
+pub mod migrations;
 pub mod rpc;
 
 /// The pallet's configuration trait.
@@ -24,19 +26,37 @@ pub trait Config: system::Config
     type BeforeAccountFollowed: BeforeAccountFollowed<Self>;
 
     type BeforeAccountUnfollowed: BeforeAccountUnfollowed<Self>;
+
+    type AfterAccountFollowed: AfterAccountFollowed<Self>;
+
+    /// The max number of accounts a single account can follow.
+    type MaxFollowedAccounts: Get<u32>;
 }
 
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Config> as ProfileFollowsModule {
+        /// Followers of a given account, one per key, so that a follow does not
+        /// read and write the whole list of followers of a popular account.
         pub AccountFollowers get(fn account_followers):
-            map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
 
         pub AccountFollowedByAccount get(fn account_followed_by_account):
             map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => bool;
 
+        /// Accounts followed by a given account, in the order they were followed.
+        /// The list is read and written as a whole, so its size is limited by `MaxFollowedAccounts`.
         pub AccountsFollowedByAccount get(fn accounts_followed_by_account):
             map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+
+        /// True if `AccountFollowers` storage is already migrated from a vector of followers per account.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub AccountFollowersMigrated: bool = false;
+    }
+    add_extra_genesis {
+      build(|_| {
+        AccountFollowersMigrated::put(true);
+      })
     }
 }
 
@@ -65,19 +85,36 @@ decl_error! {
         AlreadyAccountFollower,
         /// Account (Alice) is not a follower of another account (Bob).
         NotAccountFollower,
+        /// Account has reached the max number of accounts it can follow.
+        TooManyFollowedAccounts,
     }
 }
 
 decl_module! {
   pub struct Module<T: Config> for enum Call where origin: T::Origin {
 
+    const MaxFollowedAccounts: u32 = T::MaxFollowedAccounts::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+      let mut final_weight = 0;
+
+      if !AccountFollowersMigrated::get() {
+        final_weight = migrations::split_account_followers::<T>();
+      }
+
+      final_weight
+    }
+
+    // Transactional, because the social accounts are stored before `BeforeAccountFollowed`
+    // is called, and the handler may refuse the follow, e.g. if the follower is blocked.
     #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+    #[transactional]
     pub fn follow_account(origin, account: T::AccountId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
@@ -85,63 +122,96 @@ decl_module! {
       ensure!(!<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())),
         Error::<T>::AlreadyAccountFollower);
 
+      let mut followed_accounts = Self::accounts_followed_by_account(&follower);
+      ensure!(
+        followed_accounts.len() < T::MaxFollowedAccounts::get() as usize,
+        Error::<T>::TooManyFollowedAccounts
+      );
+
       let mut follower_account = Profiles::get_or_new_social_account(follower.clone());
       let mut followed_account = Profiles::get_or_new_social_account(account.clone());
 
       follower_account.inc_following_accounts();
       followed_account.inc_followers();
 
-      T::BeforeAccountFollowed::before_account_followed(
-        follower.clone(), follower_account.reputation, account.clone())?;
+      let follower_reputation = follower_account.reputation;
 
       <SocialAccountById<T>>::insert(follower.clone(), follower_account);
       <SocialAccountById<T>>::insert(account.clone(), followed_account);
-      <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |ids| ids.push(account.clone()));
-      <AccountFollowers<T>>::mutate(account.clone(), |ids| ids.push(follower.clone()));
+
+      // Called after the social accounts are stored, so that the handler could change them.
+      T::BeforeAccountFollowed::before_account_followed(
+        follower.clone(), follower_reputation, account.clone())?;
+
+      followed_accounts.push(account.clone());
+      <AccountsFollowedByAccount<T>>::insert(follower.clone(), followed_accounts);
+      <AccountFollowers<T>>::insert(account.clone(), follower.clone(), true);
       <AccountFollowedByAccount<T>>::insert((follower.clone(), account.clone()), true);
 
+      T::AfterAccountFollowed::after_account_followed(follower.clone(), account.clone());
+
       Self::deposit_event(RawEvent::AccountFollowed(follower, account));
       Ok(())
     }
 
+    // Transactional for the same reason as `follow_account`, but with `BeforeAccountUnfollowed`.
     #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+    #[transactional]
     pub fn unfollow_account(origin, account: T::AccountId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
       ensure!(follower != account, Error::<T>::AccountCannotUnfollowItself);
       ensure!(<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())), Error::<T>::NotAccountFollower);
 
-      let mut follower_account = Profiles::social_account_by_id(follower.clone()).ok_or(Error::<T>::FollowerAccountNotFound)?;
-      let mut followed_account = Profiles::social_account_by_id(account.clone()).ok_or(Error::<T>::FollowedAccountNotFound)?;
+      Self::unfollow_account_by_account(follower, account)
+    }
+  }
+}
 
-      follower_account.dec_following_accounts();
-      followed_account.dec_followers();
+impl<T: Config> Module<T> {
+    pub fn unfollow_account_by_account(follower: T::AccountId, account: T::AccountId) -> DispatchResult {
+        let mut follower_account = Profiles::social_account_by_id(follower.clone()).ok_or(Error::<T>::FollowerAccountNotFound)?;
+        let mut followed_account = Profiles::social_account_by_id(account.clone()).ok_or(Error::<T>::FollowedAccountNotFound)?;
 
-      T::BeforeAccountUnfollowed::before_account_unfollowed(follower.clone(), account.clone())?;
+        follower_account.dec_following_accounts();
+        followed_account.dec_followers();
 
-      <SocialAccountById<T>>::insert(follower.clone(), follower_account);
-      <SocialAccountById<T>>::insert(account.clone(), followed_account);
-      <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |account_ids| remove_from_vec(account_ids, account.clone()));
-      <AccountFollowers<T>>::mutate(account.clone(), |account_ids| remove_from_vec(account_ids, follower.clone()));
-      <AccountFollowedByAccount<T>>::remove((follower.clone(), account.clone()));
+        <SocialAccountById<T>>::insert(follower.clone(), follower_account);
+        <SocialAccountById<T>>::insert(account.clone(), followed_account);
 
-      Self::deposit_event(RawEvent::AccountUnfollowed(follower, account));
-      Ok(())
+        // Called after the social accounts are stored, so that the handler could change them.
+        T::BeforeAccountUnfollowed::before_account_unfollowed(follower.clone(), account.clone())?;
+        <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |account_ids| remove_from_vec(account_ids, account.clone()));
+        <AccountFollowers<T>>::remove(account.clone(), follower.clone());
+        <AccountFollowedByAccount<T>>::remove((follower.clone(), account.clone()));
+
+        Self::deposit_event(RawEvent::AccountUnfollowed(follower, account));
+        Ok(())
     }
-  }
 }
 
 /// Handler that will be called right before the account is followed.
+/// If any of the handlers returns an error, the account is not followed.
 pub trait BeforeAccountFollowed<T: Config> {
     fn before_account_followed(follower: T::AccountId, follower_reputation: u32, following: T::AccountId) -> DispatchResult;
 }
 
-impl<T: Config> BeforeAccountFollowed<T> for () {
-    fn before_account_followed(_follower: T::AccountId, _follower_reputation: u32, _following: T::AccountId) -> DispatchResult {
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl<T: Config> BeforeAccountFollowed<T> for Tuple {
+    // Variables are unused when the handler is an empty tuple `()`.
+    #[allow(unused_variables)]
+    fn before_account_followed(follower: T::AccountId, follower_reputation: u32, following: T::AccountId) -> DispatchResult {
+        for_tuples!( #( Tuple::before_account_followed(follower.clone(), follower_reputation, following.clone())?; )* );
         Ok(())
     }
 }
 
+/// Handler that will be called after the account is followed.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait AfterAccountFollowed<T: Config> {
+    fn after_account_followed(follower: T::AccountId, following: T::AccountId);
+}
+
 /// Handler that will be called right before the account is unfollowed.
 pub trait BeforeAccountUnfollowed<T: Config> {
     fn before_account_unfollowed(follower: T::AccountId, following: T::AccountId) -> DispatchResult;
@@ -0,0 +1,30 @@
+use frame_support::{
+    Blake2_128Concat,
+    storage::{StoragePrefixedMap, migration::{remove_storage_prefix, storage_key_iter}},
+};
+
+use super::*;
+
+/// Move the followers of every account from a single vector to separate `AccountFollowers` keys.
+pub fn split_account_followers<T: Config>() -> frame_support::weights::Weight {
+    let module = AccountFollowers::<T>::module_prefix();
+    let item = AccountFollowers::<T>::storage_prefix();
+
+    // Before this migration `AccountFollowers` was a map of an account to a vector of its followers.
+    let old_followers: Vec<(T::AccountId, Vec<T::AccountId>)> =
+        storage_key_iter::<T::AccountId, Vec<T::AccountId>, Blake2_128Concat>(module, item).collect();
+
+    remove_storage_prefix(module, item, &[]);
+
+    let mut followers_migrated = 0;
+    for (account, followers) in old_followers.iter() {
+        for follower in followers {
+            AccountFollowers::<T>::insert(account, follower, true);
+            followers_migrated += 1;
+        }
+    }
+
+    AccountFollowersMigrated::put(true);
+
+    T::DbWeight::get().reads_writes(old_followers.len() as u64, followers_migrated + 2)
+}
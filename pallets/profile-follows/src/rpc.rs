@@ -1,6 +1,9 @@
+use frame_support::storage::IterableStorageDoubleMap;
 use sp_std::prelude::*;
 
-use crate::{Module, Config};
+use pallet_profiles::{Module as Profiles, rpc::FlatSocialAccount};
+
+use crate::{Module, Config, AccountFollowers};
 
 impl<T: Config> Module<T> {
     pub fn filter_followed_accounts(account: T::AccountId, maybe_following: Vec<T::AccountId>) -> Vec<T::AccountId> {
@@ -8,4 +11,37 @@ impl<T: Config> Module<T> {
             .filter(|maybe_following| Self::account_followed_by_account((&account, maybe_following)))
             .cloned().collect()
     }
-}
\ No newline at end of file
+
+    /// Get the social accounts of the followers of a given account.
+    /// Followers are not sorted by the time they followed the account, but their order is stable
+    /// as long as nobody follows or unfollows the account between requests.
+    /// Followers before `offset` are iterated over too, so the cost of a page grows with its offset.
+    pub fn get_account_followers(
+        account: T::AccountId,
+        offset: u64,
+        limit: u16,
+    ) -> Vec<FlatSocialAccount<T::AccountId, T::BlockNumber>> {
+        let followers = AccountFollowers::<T>::iter_key_prefix(account)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+
+        Profiles::<T>::get_social_accounts_by_ids(followers)
+    }
+
+    /// Get the social accounts followed by a given account, in the order they were followed.
+    /// There are at most `MaxFollowedAccounts` of them, so the whole list is read at once.
+    pub fn get_accounts_followed_by_account(
+        account: T::AccountId,
+        offset: u64,
+        limit: u16,
+    ) -> Vec<FlatSocialAccount<T::AccountId, T::BlockNumber>> {
+        let followed_accounts = Self::accounts_followed_by_account(account)
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+
+        Profiles::<T>::get_social_accounts_by_ids(followed_accounts)
+    }
+}
@@ -13,5 +13,9 @@ sp_api::decl_runtime_apis! {
         fn get_social_accounts_by_ids(
             account_ids: Vec<AccountId>
         ) -> Vec<FlatSocialAccount<AccountId, BlockNumber>>;
+
+        fn get_account_id_by_handle(handle: Vec<u8>) -> Option<AccountId>;
+
+        fn get_social_account_by_handle(handle: Vec<u8>) -> Option<FlatSocialAccount<AccountId, BlockNumber>>;
     }
 }
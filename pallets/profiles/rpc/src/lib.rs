@@ -18,6 +18,20 @@ pub trait ProfilesApi<BlockHash, AccountId, BlockNumber> {
         at: Option<BlockHash>,
         account_ids: Vec<AccountId>,
     ) -> Result<Vec<FlatSocialAccount<AccountId, BlockNumber>>>;
+
+    #[rpc(name = "profiles_getAccountIdByHandle")]
+    fn get_account_id_by_handle(
+        &self,
+        at: Option<BlockHash>,
+        handle: Vec<u8>,
+    ) -> Result<Option<AccountId>>;
+
+    #[rpc(name = "profiles_getSocialAccountByHandle")]
+    fn get_social_account_by_handle(
+        &self,
+        at: Option<BlockHash>,
+        handle: Vec<u8>,
+    ) -> Result<Option<FlatSocialAccount<AccountId, BlockNumber>>>;
 }
 
 pub struct Profiles<C, M> {
@@ -50,4 +64,24 @@ where
         let runtime_api_result = api.get_social_accounts_by_ids(&at, account_ids);
         runtime_api_result.map_err(map_rpc_error)
     }
+
+    fn get_account_id_by_handle(&self, at: Option<<Block as BlockT>::Hash>, handle: Vec<u8>) -> Result<Option<AccountId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_account_id_by_handle(&at, handle);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_social_account_by_handle(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        handle: Vec<u8>,
+    ) -> Result<Option<FlatSocialAccount<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_social_account_by_handle(&at, handle);
+        runtime_api_result.map_err(map_rpc_error)
+    }
 }
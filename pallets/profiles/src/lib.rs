@@ -4,23 +4,25 @@ use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
-    dispatch::DispatchResult,
-    traits::Get
+    dispatch::{DispatchError, DispatchResult},
+    traits::{Currency, Get, ReservableCurrency}
 };
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
+use df_traits::{IsHandleReserved, ReputationProvider};
 use pallet_utils::{Module as Utils, WhoAndWhen, Content};
 
+pub mod migrations;
 pub mod rpc;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct SocialAccount<T: Config> {
     pub followers_count: u32,
-    pub following_accounts_count: u16,
-    pub following_spaces_count: u16,
+    pub following_accounts_count: u32,
+    pub following_spaces_count: u32,
     pub reputation: u32,
     pub profile: Option<Profile<T>>,
 }
@@ -30,6 +32,12 @@ pub struct SocialAccount<T: Config> {
 pub struct Profile<T: Config> {
     pub created: WhoAndWhen<T>,
     pub updated: Option<WhoAndWhen<T>>,
+
+    /// Unique alpha-numeric identifier that can be used in a profile's URL.
+    /// Profiles and spaces share one namespace of handles.
+    /// Handle can only contain numbers, letter and underscore: `0`-`9`, `a`-`z`, `_`.
+    pub handle: Option<Vec<u8>>,
+
     pub content: Content
 }
 
@@ -38,6 +46,9 @@ pub struct ProfileUpdate {
     pub content: Option<Content>,
 }
 
+type BalanceOf<T> =
+  <<T as Config>::Currency as Currency<<T as system::Config>::AccountId>>::Balance;
+
 /// The pallet's configuration trait.
 pub trait Config: system::Config
     + pallet_utils::Config
@@ -45,6 +56,14 @@ pub trait Config: system::Config
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
 
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// The amount of tokens reserved from an account that registers a handle for its profile.
+    type HandleDeposit: Get<BalanceOf<Self>>;
+
+    /// Used to check that a profile handle is not reserved by a space.
+    type SpaceHandles: IsHandleReserved;
+
     type AfterProfileUpdated: AfterProfileUpdated<Self>;
 }
 
@@ -53,6 +72,27 @@ decl_storage! {
     trait Store for Module<T: Config> as ProfilesModule {
         pub SocialAccountById get(fn social_account_by_id):
             map hasher(blake2_128_concat) T::AccountId => Option<SocialAccount<T>>;
+
+        /// Find a given account id by its' profile's unique handle.
+        /// If a handle is not registered, nothing will be returned (`None`).
+        pub AccountIdByHandle get(fn account_id_by_handle):
+            map hasher(blake2_128_concat) Vec<u8> => Option<T::AccountId>;
+
+        /// True if profiles in `SocialAccountById` storage are already migrated to profiles
+        /// with the `handle` field.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub ProfileHandlesMigrated: bool = false;
+
+        /// True if `SocialAccountById` storage is already migrated to social accounts
+        /// with `u32` following counters.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub FollowingCountersWidened: bool = false;
+    }
+    add_extra_genesis {
+      build(|_| {
+        ProfileHandlesMigrated::put(true);
+        FollowingCountersWidened::put(true);
+      })
     }
 }
 
@@ -75,20 +115,38 @@ decl_error! {
         NoUpdatesForProfile,
         /// Account has no profile yet.
         AccountHasNoProfile,
+        /// Profile handle is not unique, it's already reserved by another profile or space.
+        ProfileHandleIsNotUnique,
     }
 }
 
 decl_module! {
   pub struct Module<T: Config> for enum Call where origin: T::Origin {
 
+    const HandleDeposit: BalanceOf<T> = T::HandleDeposit::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(1, 2)]
-    pub fn create_profile(origin, content: Content) -> DispatchResult {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+      let mut final_weight = 0;
+
+      // Both migrations translate social accounts straight to the latest `SocialAccount` struct,
+      // so only the migration from the oldest version is run.
+      if !ProfileHandlesMigrated::get() {
+        final_weight = migrations::add_handles_to_profiles::<T>();
+      } else if !FollowingCountersWidened::get() {
+        final_weight = migrations::widen_following_counters::<T>();
+      }
+
+      final_weight
+    }
+
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(4, 4)]
+    pub fn create_profile(origin, handle_opt: Option<Vec<u8>>, content: Content) -> DispatchResult {
       let owner = ensure_signed(origin)?;
 
       Utils::<T>::is_valid_content(content.clone())?;
@@ -96,10 +154,15 @@ decl_module! {
       let mut social_account = Self::get_or_new_social_account(owner.clone());
       ensure!(social_account.profile.is_none(), Error::<T>::ProfileAlreadyCreated);
 
+      if let Some(handle) = handle_opt.clone() {
+        Self::reserve_handle(&owner, handle)?;
+      }
+
       social_account.profile = Some(
         Profile {
           created: WhoAndWhen::<T>::new(owner.clone()),
           updated: None,
+          handle: handle_opt,
           content
         }
       );
@@ -142,6 +205,39 @@ decl_module! {
       }
       Ok(())
     }
+
+    /// Set, change or remove a handle of the caller's profile.
+    /// A handle deposit is reserved when a handle is set and unreserved when it's removed.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(4, 4)]
+    pub fn update_profile_handle(origin, new_handle_opt: Option<Vec<u8>>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      let mut social_account = Self::social_account_by_id(owner.clone()).ok_or(Error::<T>::SocialAccountNotFound)?;
+      let mut profile = social_account.profile.ok_or(Error::<T>::AccountHasNoProfile)?;
+
+      ensure!(new_handle_opt != profile.handle, Error::<T>::NoUpdatesForProfile);
+
+      match (profile.handle.clone(), new_handle_opt.clone()) {
+        (Some(old_handle), Some(new_handle)) => {
+          // Change the current handle to a new one, keeping the reserved deposit
+          let new_handle_lc = Self::lowercase_and_ensure_unique_handle(new_handle)?;
+          AccountIdByHandle::<T>::remove(Utils::<T>::lowercase_handle(old_handle));
+          AccountIdByHandle::<T>::insert(new_handle_lc, owner.clone());
+        },
+        (Some(old_handle), None) => Self::unreserve_handle(&owner, old_handle),
+        (None, Some(new_handle)) => Self::reserve_handle(&owner, new_handle)?,
+        (None, None) => (/* Unreachable, as the handles are compared above. */),
+      }
+
+      profile.handle = new_handle_opt;
+      profile.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+      social_account.profile = Some(profile);
+
+      <SocialAccountById<T>>::insert(owner.clone(), social_account);
+
+      Self::deposit_event(RawEvent::ProfileUpdated(owner));
+      Ok(())
+    }
   }
 }
 
@@ -202,6 +298,46 @@ impl<T: Config> Module<T> {
             }
         )
     }
+
+    /// Lowercase a handle and ensure that it's unique, i.e. no profile or space reserved it yet.
+    fn lowercase_and_ensure_unique_handle(handle: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
+        let handle_in_lowercase = Utils::<T>::lowercase_and_validate_a_handle(handle)?;
+
+        // Check if a handle is unique across all profiles' and spaces' handles:
+        ensure!(
+            Self::account_id_by_handle(handle_in_lowercase.clone()).is_none()
+                && !T::SpaceHandles::is_handle_reserved(handle_in_lowercase.clone()),
+            Error::<T>::ProfileHandleIsNotUnique
+        );
+
+        Ok(handle_in_lowercase)
+    }
+
+    fn reserve_handle(account: &T::AccountId, handle: Vec<u8>) -> DispatchResult {
+        let handle_in_lowercase = Self::lowercase_and_ensure_unique_handle(handle)?;
+        <T as Config>::Currency::reserve(account, T::HandleDeposit::get())?;
+        AccountIdByHandle::<T>::insert(handle_in_lowercase, account.clone());
+        Ok(())
+    }
+
+    fn unreserve_handle(account: &T::AccountId, handle: Vec<u8>) {
+        <T as Config>::Currency::unreserve(account, T::HandleDeposit::get());
+        AccountIdByHandle::<T>::remove(Utils::<T>::lowercase_handle(handle));
+    }
+}
+
+impl<T: Config> IsHandleReserved for Module<T> {
+    fn is_handle_reserved(handle_in_lowercase: Vec<u8>) -> bool {
+        Self::account_id_by_handle(handle_in_lowercase).is_some()
+    }
+}
+
+impl<T: Config> ReputationProvider for Module<T> {
+    type AccountId = T::AccountId;
+
+    fn get_reputation(account: Self::AccountId) -> u32 {
+        Self::get_or_new_social_account(account).reputation
+    }
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(10)]
@@ -0,0 +1,79 @@
+use frame_support::storage::IterableStorageMap;
+
+use super::*;
+
+/// A `Profile` struct as it was stored before the `handle` field was added.
+#[derive(Decode)]
+struct ProfileWithoutHandle<T: Config> {
+    created: WhoAndWhen<T>,
+    updated: Option<WhoAndWhen<T>>,
+    content: Content,
+}
+
+/// A `SocialAccount` struct as it was stored before the `handle` field was added to `Profile`.
+#[derive(Decode)]
+struct SocialAccountWithoutProfileHandle<T: Config> {
+    followers_count: u32,
+    following_accounts_count: u16,
+    following_spaces_count: u16,
+    reputation: u32,
+    profile: Option<ProfileWithoutHandle<T>>,
+}
+
+pub fn add_handles_to_profiles<T: Config>() -> frame_support::weights::Weight {
+    let mut accounts_migrated = 0;
+
+    SocialAccountById::<T>::translate::<SocialAccountWithoutProfileHandle<T>, _>(|_, old_account| {
+        accounts_migrated += 1;
+
+        Some(SocialAccount {
+            followers_count: old_account.followers_count,
+            following_accounts_count: old_account.following_accounts_count.into(),
+            following_spaces_count: old_account.following_spaces_count.into(),
+            reputation: old_account.reputation,
+            profile: old_account.profile.map(|old_profile| Profile {
+                created: old_profile.created,
+                updated: old_profile.updated,
+                handle: None,
+                content: old_profile.content,
+            }),
+        })
+    });
+
+    ProfileHandlesMigrated::put(true);
+    // Social accounts are translated straight to the struct with `u32` following counters.
+    FollowingCountersWidened::put(true);
+
+    T::DbWeight::get().reads_writes(accounts_migrated + 1, accounts_migrated + 2)
+}
+
+/// A `SocialAccount` struct as it was stored before its following counters were widened
+/// from `u16` to `u32`.
+#[derive(Decode)]
+struct SocialAccountWithU16FollowingCounters<T: Config> {
+    followers_count: u32,
+    following_accounts_count: u16,
+    following_spaces_count: u16,
+    reputation: u32,
+    profile: Option<Profile<T>>,
+}
+
+pub fn widen_following_counters<T: Config>() -> frame_support::weights::Weight {
+    let mut accounts_migrated = 0;
+
+    SocialAccountById::<T>::translate::<SocialAccountWithU16FollowingCounters<T>, _>(|_, old_account| {
+        accounts_migrated += 1;
+
+        Some(SocialAccount {
+            followers_count: old_account.followers_count,
+            following_accounts_count: old_account.following_accounts_count.into(),
+            following_spaces_count: old_account.following_spaces_count.into(),
+            reputation: old_account.reputation,
+            profile: old_account.profile,
+        })
+    });
+
+    FollowingCountersWidened::put(true);
+
+    T::DbWeight::get().reads_writes(accounts_migrated + 1, accounts_migrated + 1)
+}
@@ -3,7 +3,9 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 use sp_std::prelude::*;
 
-use pallet_utils::rpc::{FlatContent, FlatWhoAndWhen};
+#[cfg(feature = "std")]
+use pallet_utils::rpc::bytes_to_string;
+use pallet_utils::rpc::{FlatContent, FlatWhoAndWhen, ShouldSkip};
 
 use frame_system::Pallet as SystemPallet;
 
@@ -15,6 +17,8 @@ use crate::{Module, Profile, SocialAccount, Config};
 pub struct FlatProfile<AccountId, BlockNumber> {
     #[cfg_attr(feature = "std", serde(flatten))]
     pub who_and_when: FlatWhoAndWhen<AccountId, BlockNumber>,
+    #[cfg_attr(feature = "std", serde(skip_serializing_if = "ShouldSkip::should_skip", serialize_with = "bytes_to_string"))]
+    pub handle: Option<Vec<u8>>,
     #[cfg_attr(feature = "std", serde(flatten))]
     pub content: FlatContent,
 }
@@ -25,18 +29,19 @@ pub struct FlatProfile<AccountId, BlockNumber> {
 pub struct FlatSocialAccount<AccountId, BlockNumber> {
     pub id: AccountId,
     pub followers_count: u32,
-    pub following_accounts_count: u16,
-    pub following_spaces_count: u16,
+    pub following_accounts_count: u32,
+    pub following_spaces_count: u32,
     pub reputation: u32,
     pub profile: Option<FlatProfile<AccountId, BlockNumber>>,
 }
 
 impl<T: Config> From<Profile<T>> for FlatProfile<T::AccountId, T::BlockNumber> {
     fn from(from: Profile<T>) -> Self {
-        let Profile { created, updated, content } = from;
+        let Profile { created, updated, handle, content } = from;
 
         Self {
             who_and_when: (created, updated).into(),
+            handle,
             content: content.into(),
         }
     }
@@ -75,6 +80,23 @@ impl<T: Config> Module<T> {
                    .collect()
     }
 
+    pub fn get_account_id_by_handle(handle: Vec<u8>) -> Option<T::AccountId> {
+        Self::account_id_by_handle(handle)
+    }
+
+    pub fn get_social_account_by_handle(
+        handle: Vec<u8>
+    ) -> Option<FlatSocialAccount<T::AccountId, T::BlockNumber>> {
+        Self::account_id_by_handle(handle)
+            .and_then(|account| {
+                Self::social_account_by_id(&account).map(|social_account| {
+                    let mut flat_social_account: FlatSocialAccount<T::AccountId, T::BlockNumber> = social_account.into();
+                    flat_social_account.id = account;
+                    flat_social_account
+                })
+            })
+    }
+
     pub fn get_account_data(account: T::AccountId) -> T::AccountData {
         SystemPallet::<T>::account(&account).data
     }
@@ -7,6 +7,7 @@ use sp_std::collections::btree_map::BTreeMap;
 use pallet_reactions::{
     ReactionId,
     ReactionKind,
+    ReactionTarget,
     rpc::FlatReaction,
 };
 use pallet_utils::PostId;
@@ -24,9 +25,24 @@ sp_api::decl_runtime_apis! {
             offset: u64
         ) -> Vec<FlatReaction<AccountId, BlockNumber>>;
 
+        fn get_reactions_by_target(
+            target: ReactionTarget<AccountId>,
+            limit: u64,
+            offset: u64
+        ) -> Vec<FlatReaction<AccountId, BlockNumber>>;
+
         fn get_reaction_kinds_by_post_ids_and_reactor(
             post_ids: Vec<PostId>,
             reactor: AccountId,
         ) -> BTreeMap<PostId, ReactionKind>;
+
+        fn get_reaction_kinds_by_targets_and_reactor(
+            targets: Vec<ReactionTarget<AccountId>>,
+            reactor: AccountId,
+        ) -> Vec<(ReactionTarget<AccountId>, ReactionKind)>;
+
+        fn get_reaction_counts_by_post_ids(
+            post_ids: Vec<PostId>,
+        ) -> BTreeMap<PostId, Vec<(ReactionKind, u32)>>;
     }
 }
@@ -6,7 +6,7 @@ use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
 
-use pallet_reactions::{ReactionId, ReactionKind, rpc::FlatReaction};
+use pallet_reactions::{ReactionId, ReactionKind, ReactionTarget, rpc::FlatReaction};
 use pallet_utils::{PostId, rpc::map_rpc_error};
 pub use reactions_runtime_api::ReactionsApi as ReactionsRuntimeApi;
 
@@ -28,6 +28,15 @@ pub trait ReactionsApi<BlockHash, AccountId, BlockNumber> {
         offset: u64,
     ) -> Result<Vec<FlatReaction<AccountId, BlockNumber>>>;
 
+    #[rpc(name = "reactions_getReactionsByTarget")]
+    fn get_reactions_by_target(
+        &self,
+        at: Option<BlockHash>,
+        target: ReactionTarget<AccountId>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<FlatReaction<AccountId, BlockNumber>>>;
+
     #[rpc(name = "reactions_getReactionKindsByPostIdsAndReactor")]
     fn get_reaction_kinds_by_post_ids_and_reactor(
         &self,
@@ -35,6 +44,21 @@ pub trait ReactionsApi<BlockHash, AccountId, BlockNumber> {
         post_ids: Vec<PostId>,
         reactor: AccountId,
     ) -> Result<BTreeMap<PostId, ReactionKind>>;
+
+    #[rpc(name = "reactions_getReactionKindsByTargetsAndReactor")]
+    fn get_reaction_kinds_by_targets_and_reactor(
+        &self,
+        at: Option<BlockHash>,
+        targets: Vec<ReactionTarget<AccountId>>,
+        reactor: AccountId,
+    ) -> Result<Vec<(ReactionTarget<AccountId>, ReactionKind)>>;
+
+    #[rpc(name = "reactions_getReactionCountsByPostIds")]
+    fn get_reaction_counts_by_post_ids(
+        &self,
+        at: Option<BlockHash>,
+        post_ids: Vec<PostId>,
+    ) -> Result<BTreeMap<PostId, Vec<(ReactionKind, u32)>>>;
 }
 
 pub struct Reactions<C, M> {
@@ -86,6 +110,20 @@ where
         runtime_api_result.map_err(map_rpc_error)
     }
 
+    fn get_reactions_by_target(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        target: ReactionTarget<AccountId>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<FlatReaction<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_reactions_by_target(&at, target, limit, offset);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
     fn get_reaction_kinds_by_post_ids_and_reactor(
         &self,
         at: Option<<Block as BlockT>::Hash>,
@@ -98,4 +136,29 @@ where
         let runtime_api_result = api.get_reaction_kinds_by_post_ids_and_reactor(&at, post_ids, reactor);
         runtime_api_result.map_err(map_rpc_error)
     }
+
+    fn get_reaction_kinds_by_targets_and_reactor(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        targets: Vec<ReactionTarget<AccountId>>,
+        reactor: AccountId,
+    ) -> Result<Vec<(ReactionTarget<AccountId>, ReactionKind)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_reaction_kinds_by_targets_and_reactor(&at, targets, reactor);
+        runtime_api_result.map_err(map_rpc_error)
+    }
+
+    fn get_reaction_counts_by_post_ids(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        post_ids: Vec<PostId>,
+    ) -> Result<BTreeMap<PostId, Vec<(ReactionKind, u32)>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_reaction_counts_by_post_ids(&at, post_ids);
+        runtime_api_result.map_err(map_rpc_error)
+    }
 }
@@ -10,26 +10,34 @@ use frame_support::{
 use frame_system::{self as system, ensure_signed};
 
 #[cfg(feature = "std")]
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sp_runtime::{RuntimeDebug, DispatchError};
 use sp_std::prelude::*;
 
-use df_traits::moderation::IsAccountBlocked;
+use df_traits::{
+    SpaceCleanupHandler,
+    moderation::{IsAccountBlocked, IsAccountBlockedBy, IsPostBlocked},
+};
 use pallet_permissions::SpacePermission;
-use pallet_posts::{Module as Posts, PostById};
-use pallet_spaces::Module as Spaces;
-use pallet_utils::{Error as UtilsError, remove_from_vec, WhoAndWhen, PostId};
+use pallet_posts::{Module as Posts, Post, PostById};
+use pallet_spaces::{Module as Spaces, Space, SpaceById};
+use pallet_utils::{Error as UtilsError, remove_from_vec, WhoAndWhen, PostId, SpaceId};
 
+pub mod migrations;
 pub mod rpc;
 
 pub type ReactionId = u64;
 
-#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Deserialize))]
 #[cfg_attr(feature = "std", serde(untagged))]
 pub enum ReactionKind {
     Upvote,
     Downvote,
+    /// A UTF-8 encoded emoji (e.g. `🎉`) or its shortcode (e.g. `:tada:`).
+    /// Plain ASCII text is not an emoji, so it cannot be confused with votes in RPC output.
+    /// Unlike upvotes and downvotes, emoji reactions don't affect the scores of posts.
+    Emoji(Vec<u8>),
 }
 
 impl Default for ReactionKind {
@@ -38,6 +46,17 @@ impl Default for ReactionKind {
     }
 }
 
+/// An entity that can be reacted to.
+/// Posts can be reacted to with any kind of reaction, spaces and accounts only with votes.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ReactionTarget<AccountId> {
+    Post(PostId),
+    Space(SpaceId),
+    Account(AccountId),
+}
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct Reaction<T: Config> {
@@ -59,6 +78,65 @@ pub trait Config: system::Config
 {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+
+    type ReactionScores: ReactionScores<Self>;
+
+    type AfterReactionCreated: AfterReactionCreated<Self>;
+
+    /// The max length of an emoji (or its shortcode) in bytes.
+    type MaxEmojiLen: Get<u32>;
+
+    /// The max number of emojis a space can allow to react with.
+    type MaxAllowedEmojisPerSpace: Get<u32>;
+}
+
+/// Handler that updates the scores of posts and spaces and the reputation of accounts
+/// when they are reacted to.
+pub trait ReactionScores<T: Config> {
+    fn score_post_on_reaction(actor: T::AccountId, post: &mut Post<T>, reaction_kind: &ReactionKind) -> DispatchResult;
+
+    /// Revert the score changes made when a given reaction was created.
+    fn revert_post_score_on_reaction(actor: T::AccountId, post: &mut Post<T>, reaction_kind: &ReactionKind) -> DispatchResult;
+
+    fn score_space_on_reaction(actor: T::AccountId, space: &mut Space<T>, reaction_kind: &ReactionKind) -> DispatchResult;
+
+    fn revert_space_score_on_reaction(actor: T::AccountId, space: &mut Space<T>, reaction_kind: &ReactionKind) -> DispatchResult;
+
+    fn score_account_on_reaction(actor: T::AccountId, account: T::AccountId, reaction_kind: &ReactionKind) -> DispatchResult;
+
+    fn revert_account_score_on_reaction(actor: T::AccountId, account: T::AccountId, reaction_kind: &ReactionKind) -> DispatchResult;
+}
+
+impl<T: Config> ReactionScores<T> for () {
+    fn score_post_on_reaction(_actor: T::AccountId, _post: &mut Post<T>, _reaction_kind: &ReactionKind) -> DispatchResult {
+        Ok(())
+    }
+
+    fn revert_post_score_on_reaction(_actor: T::AccountId, _post: &mut Post<T>, _reaction_kind: &ReactionKind) -> DispatchResult {
+        Ok(())
+    }
+
+    fn score_space_on_reaction(_actor: T::AccountId, _space: &mut Space<T>, _reaction_kind: &ReactionKind) -> DispatchResult {
+        Ok(())
+    }
+
+    fn revert_space_score_on_reaction(_actor: T::AccountId, _space: &mut Space<T>, _reaction_kind: &ReactionKind) -> DispatchResult {
+        Ok(())
+    }
+
+    fn score_account_on_reaction(_actor: T::AccountId, _account: T::AccountId, _reaction_kind: &ReactionKind) -> DispatchResult {
+        Ok(())
+    }
+
+    fn revert_account_score_on_reaction(_actor: T::AccountId, _account: T::AccountId, _reaction_kind: &ReactionKind) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// Handler that will be called after a reaction is created.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait AfterReactionCreated<T: Config> {
+    fn after_reaction_created(account: T::AccountId, target: &ReactionTarget<T::AccountId>, reaction_id: ReactionId);
 }
 
 pub const FIRST_REACTION_ID: u64 = 1;
@@ -73,11 +151,34 @@ decl_storage! {
         pub ReactionById get(fn reaction_by_id):
             map hasher(twox_64_concat) ReactionId => Option<Reaction<T>>;
 
-        pub ReactionIdsByPostId get(fn reaction_ids_by_post_id):
-            map hasher(twox_64_concat) PostId => Vec<ReactionId>;
-
-        pub PostReactionIdByAccount get(fn post_reaction_id_by_account):
-            map hasher(twox_64_concat) (T::AccountId, PostId) => ReactionId;
+        /// Ids of all reactions on a given post, space or account.
+        pub ReactionIdsByTarget get(fn reaction_ids_by_target):
+            map hasher(blake2_128_concat) ReactionTarget<T::AccountId> => Vec<ReactionId>;
+
+        /// The id of a reaction made by an account (key 1) on a given post, space or account (key 2).
+        pub ReactionIdByAccountAndTarget get(fn reaction_id_by_account_and_target):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) ReactionTarget<T::AccountId>
+            => Option<ReactionId>;
+
+        /// The number of reactions with a given emoji on a given post.
+        /// The numbers of upvotes and downvotes are stored in a `Post` itself.
+        pub EmojiReactionsCount get(fn emoji_reactions_count):
+            double_map hasher(twox_64_concat) PostId, hasher(blake2_128_concat) Vec<u8> => u32;
+
+        /// Emojis that are allowed to react with on posts in a given space.
+        /// If a space has no such list, any emoji is allowed.
+        pub AllowedEmojisBySpaceId get(fn allowed_emojis_by_space_id):
+            map hasher(twox_64_concat) SpaceId => Option<Vec<Vec<u8>>>;
+
+        // TODO delete this storage item once the migration is done on all networks.
+        /// Whether post reactions were moved from the storages keyed by post ids
+        /// to the storages keyed by reaction targets.
+        pub ReactionTargetsMigrated: bool = false;
+    }
+    add_extra_genesis {
+      build(|_| {
+        ReactionTargetsMigrated::put(true);
+      })
     }
 }
 
@@ -88,6 +189,11 @@ decl_event!(
         PostReactionCreated(AccountId, PostId, ReactionId, ReactionKind),
         PostReactionUpdated(AccountId, PostId, ReactionId, ReactionKind),
         PostReactionDeleted(AccountId, PostId, ReactionId, ReactionKind),
+        AllowedEmojisUpdated(AccountId, SpaceId),
+        /// A reaction on a space or an account was created. Reactions on posts emit `PostReaction*` events.
+        ReactionCreated(AccountId, ReactionTarget<AccountId>, ReactionId, ReactionKind),
+        ReactionUpdated(AccountId, ReactionTarget<AccountId>, ReactionId, ReactionKind),
+        ReactionDeleted(AccountId, ReactionTarget<AccountId>, ReactionId, ReactionKind),
     }
 );
 
@@ -95,9 +201,9 @@ decl_error! {
     pub enum Error for Module<T: Config> {
         /// Reaction was not found by id.
         ReactionNotFound,
-        /// Account has already reacted to this post/comment.
+        /// Account has already reacted to this post/comment, space or account.
         AccountAlreadyReacted,
-        /// There is no reaction by account on this post/comment.
+        /// There is no reaction by account on this post/comment, space or account.
         ReactionByAccountNotFound,
         /// Only reaction owner can update their reaction.
         NotReactionOwner,
@@ -108,138 +214,129 @@ decl_error! {
         CannotReactWhenSpaceHidden,
         /// Not allowed to react on a post/comment if a root post is hidden.
         CannotReactWhenPostHidden,
+        /// Account cannot react on itself.
+        CannotReactOnOwnAccount,
+        /// Spaces and accounts can only be upvoted or downvoted.
+        OnlyVotesAllowedOnTarget,
 
-        /// User has no permission to upvote posts/comments in this space.
+        /// User has no permission to upvote this space or posts/comments in it.
         NoPermissionToUpvote,
-        /// User has no permission to downvote posts/comments in this space.
+        /// User has no permission to downvote this space or posts/comments in it.
         NoPermissionToDownvote,
+        /// User has no permission to react with emojis on posts/comments in this space.
+        NoPermissionToReact,
+        /// User has no permission to update the emojis allowed in this space.
+        NoPermissionToUpdateAllowedEmojis,
+
+        /// Emoji is empty, too long, is not a valid UTF-8 string, or is plain ASCII text
+        /// other than a shortcode like `:tada:`.
+        InvalidEmoji,
+        /// This emoji is not allowed to react with in this space.
+        EmojiIsNotAllowed,
+        /// The number of allowed emojis exceeds `MaxAllowedEmojisPerSpace`.
+        TooManyAllowedEmojis,
+        /// User is blocked by the owner of the post/comment, space or account they try to react to.
+        BlockedByContentOwner,
     }
 }
 
 decl_module! {
   pub struct Module<T: Config> for enum Call where origin: T::Origin {
 
+    const MaxEmojiLen: u32 = T::MaxEmojiLen::get();
+
+    const MaxAllowedEmojisPerSpace: u32 = T::MaxAllowedEmojisPerSpace::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(6, 5)]
-    pub fn create_post_reaction(origin, post_id: PostId, kind: ReactionKind) -> DispatchResult {
-      let owner = ensure_signed(origin)?;
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+      let mut final_weight = 0;
 
-      let post = &mut Posts::require_post(post_id)?;
-      ensure!(
-        !<PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
-        Error::<T>::AccountAlreadyReacted
-      );
-
-      let space = post.get_space()?;
-      ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
-      ensure!(Posts::<T>::is_root_post_visible(post_id)?, Error::<T>::CannotReactWhenPostHidden);
-
-      ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
-
-      match kind {
-        ReactionKind::Upvote => {
-          Spaces::ensure_account_has_space_permission(
-            owner.clone(),
-            &post.get_space()?,
-            SpacePermission::Upvote,
-            Error::<T>::NoPermissionToUpvote.into()
-          )?;
-          post.inc_upvotes();
-        },
-        ReactionKind::Downvote => {
-          Spaces::ensure_account_has_space_permission(
-            owner.clone(),
-            &post.get_space()?,
-            SpacePermission::Downvote,
-            Error::<T>::NoPermissionToDownvote.into()
-          )?;
-          post.inc_downvotes();
-        }
+      if !ReactionTargetsMigrated::get() {
+        final_weight = migrations::move_post_reactions_to_targets::<T>();
       }
 
-      <PostById<T>>::insert(post_id, post.clone());
-      let reaction_id = Self::insert_new_reaction(owner.clone(), kind);
-      ReactionIdsByPostId::mutate(post.id, |ids| ids.push(reaction_id));
-      <PostReactionIdByAccount<T>>::insert((owner.clone(), post_id), reaction_id);
+      final_weight
+    }
 
-      Self::deposit_event(RawEvent::PostReactionCreated(owner, post_id, reaction_id, kind));
-      Ok(())
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(9, 8)]
+    pub fn create_post_reaction(origin, post_id: PostId, kind: ReactionKind) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+      Self::do_create_reaction(owner, ReactionTarget::Post(post_id), kind)
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(9, 6)]
     pub fn update_post_reaction(origin, post_id: PostId, reaction_id: ReactionId, new_kind: ReactionKind) -> DispatchResult {
       let owner = ensure_signed(origin)?;
+      Self::do_update_reaction(owner, ReactionTarget::Post(post_id), reaction_id, new_kind)
+    }
 
-      ensure!(
-        <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
-        Error::<T>::ReactionByAccountNotFound
-      );
-
-      let mut reaction = Self::require_reaction(reaction_id)?;
-      let post = &mut Posts::require_post(post_id)?;
-
-      ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
-      ensure!(reaction.kind != new_kind, Error::<T>::SameReaction);
-
-      if let Some(space_id) = post.try_get_space_id() {
-        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
-      }
-
-      reaction.kind = new_kind;
-      reaction.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
-
-      match new_kind {
-        ReactionKind::Upvote => {
-          post.inc_upvotes();
-          post.dec_downvotes();
-        },
-        ReactionKind::Downvote => {
-          post.inc_downvotes();
-          post.dec_upvotes();
-        },
-      }
-
-      <ReactionById<T>>::insert(reaction_id, reaction);
-      <PostById<T>>::insert(post_id, post);
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(7, 7)]
+    pub fn delete_post_reaction(origin, post_id: PostId, reaction_id: ReactionId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+      Self::do_delete_reaction(owner, ReactionTarget::Post(post_id), reaction_id)
+    }
 
-      Self::deposit_event(RawEvent::PostReactionUpdated(owner, post_id, reaction_id, new_kind));
-      Ok(())
+    /// React on a post, space or account. Spaces and accounts can only be upvoted or downvoted.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(9, 8)]
+    pub fn create_reaction(origin, target: ReactionTarget<T::AccountId>, kind: ReactionKind) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+      Self::do_create_reaction(owner, target, kind)
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
-    pub fn delete_post_reaction(origin, post_id: PostId, reaction_id: ReactionId) -> DispatchResult {
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(9, 6)]
+    pub fn update_reaction(
+      origin,
+      target: ReactionTarget<T::AccountId>,
+      reaction_id: ReactionId,
+      new_kind: ReactionKind
+    ) -> DispatchResult {
       let owner = ensure_signed(origin)?;
+      Self::do_update_reaction(owner, target, reaction_id, new_kind)
+    }
 
-      ensure!(
-        <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
-        Error::<T>::ReactionByAccountNotFound
-      );
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(7, 7)]
+    pub fn delete_reaction(origin, target: ReactionTarget<T::AccountId>, reaction_id: ReactionId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+      Self::do_delete_reaction(owner, target, reaction_id)
+    }
 
-      // TODO extract Self::require_reaction(reaction_id)?;
-      let reaction = Self::require_reaction(reaction_id)?;
-      let post = &mut Posts::require_post(post_id)?;
+    /// Restrict the emojis that can be used to react on posts in a given space.
+    /// If `emojis_opt` is `None`, any emoji is allowed.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn update_allowed_emojis(origin, space_id: SpaceId, emojis_opt: Option<Vec<Vec<u8>>>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
 
-      ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
-      if let Some(space_id) = post.try_get_space_id() {
-        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
-      }
+      let space = Spaces::<T>::require_space(space_id)?;
+      Spaces::<T>::ensure_account_has_space_permission(
+        owner.clone(),
+        &space,
+        SpacePermission::UpdateSpaceSettings,
+        Error::<T>::NoPermissionToUpdateAllowedEmojis.into()
+      )?;
+
+      if let Some(mut emojis) = emojis_opt {
+        ensure!(
+          emojis.len() <= T::MaxAllowedEmojisPerSpace::get() as usize,
+          Error::<T>::TooManyAllowedEmojis
+        );
+
+        for emoji in emojis.iter() {
+          Self::ensure_valid_emoji(emoji)?;
+        }
 
-      match reaction.kind {
-        ReactionKind::Upvote => post.dec_upvotes(),
-        ReactionKind::Downvote => post.dec_downvotes(),
+        emojis.sort();
+        emojis.dedup();
+        AllowedEmojisBySpaceId::insert(space_id, emojis);
+      } else {
+        AllowedEmojisBySpaceId::remove(space_id);
       }
 
-      <PostById<T>>::insert(post_id, post.clone());
-      <ReactionById<T>>::remove(reaction_id);
-      ReactionIdsByPostId::mutate(post.id, |ids| remove_from_vec(ids, reaction_id));
-      <PostReactionIdByAccount<T>>::remove((owner.clone(), post_id));
-
-      Self::deposit_event(RawEvent::PostReactionDeleted(owner, post_id, reaction_id, reaction.kind));
+      Self::deposit_event(RawEvent::AllowedEmojisUpdated(owner, space_id));
       Ok(())
     }
   }
@@ -266,4 +363,316 @@ impl<T: Config> Module<T> {
     pub fn require_reaction(reaction_id: ReactionId) -> Result<Reaction<T>, DispatchError> {
         Ok(Self::reaction_by_id(reaction_id).ok_or(Error::<T>::ReactionNotFound)?)
     }
+
+    /// Get the ids of all reactions on a given post.
+    pub fn reaction_ids_by_post_id(post_id: PostId) -> Vec<ReactionId> {
+        Self::reaction_ids_by_target(ReactionTarget::Post(post_id))
+    }
+
+    fn do_create_reaction(
+        owner: T::AccountId,
+        target: ReactionTarget<T::AccountId>,
+        kind: ReactionKind,
+    ) -> DispatchResult {
+        ensure!(
+            !<ReactionIdByAccountAndTarget<T>>::contains_key(&owner, &target),
+            Error::<T>::AccountAlreadyReacted
+        );
+
+        match &target {
+            ReactionTarget::Post(post_id) => {
+                let post_id = *post_id;
+                let post = &mut Posts::require_post(post_id)?;
+
+                Self::ensure_account_can_react_on_post(&owner, post, &kind)?;
+
+                T::ReactionScores::score_post_on_reaction(owner.clone(), post, &kind)?;
+
+                Self::inc_reactions_count(post, &kind);
+                <PostById<T>>::insert(post_id, post.clone());
+            },
+            ReactionTarget::Space(space_id) => {
+                let space = &mut Spaces::<T>::require_space(*space_id)?;
+                ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
+                ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+                Self::ensure_not_blocked_by(&owner, space.owner.clone())?;
+
+                Self::ensure_account_can_vote_on_space(owner.clone(), space, &kind)?;
+
+                T::ReactionScores::score_space_on_reaction(owner.clone(), space, &kind)?;
+                <SpaceById<T>>::insert(space.id, space.clone());
+            },
+            ReactionTarget::Account(account) => {
+                ensure!(owner != *account, Error::<T>::CannotReactOnOwnAccount);
+                Self::ensure_not_blocked_by(&owner, account.clone())?;
+                Self::ensure_vote(&kind)?;
+
+                T::ReactionScores::score_account_on_reaction(owner.clone(), account.clone(), &kind)?;
+            },
+        }
+
+        let reaction_id = Self::insert_new_reaction(owner.clone(), kind.clone());
+        <ReactionIdsByTarget<T>>::mutate(&target, |ids| ids.push(reaction_id));
+        <ReactionIdByAccountAndTarget<T>>::insert(&owner, &target, reaction_id);
+
+        T::AfterReactionCreated::after_reaction_created(owner.clone(), &target, reaction_id);
+
+        Self::deposit_event(match target {
+            ReactionTarget::Post(post_id) => RawEvent::PostReactionCreated(owner, post_id, reaction_id, kind),
+            target => RawEvent::ReactionCreated(owner, target, reaction_id, kind),
+        });
+        Ok(())
+    }
+
+    fn do_update_reaction(
+        owner: T::AccountId,
+        target: ReactionTarget<T::AccountId>,
+        reaction_id: ReactionId,
+        new_kind: ReactionKind,
+    ) -> DispatchResult {
+        Self::ensure_reaction_by_account_on_target(&owner, &target, reaction_id)?;
+
+        let mut reaction = Self::require_reaction(reaction_id)?;
+
+        ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
+        ensure!(reaction.kind != new_kind, Error::<T>::SameReaction);
+
+        match &target {
+            ReactionTarget::Post(post_id) => {
+                let post_id = *post_id;
+                let post = &mut Posts::require_post(post_id)?;
+
+                // A reaction is updated to a new kind, so it's checked like a new reaction.
+                Self::ensure_account_can_react_on_post(&owner, post, &new_kind)?;
+
+                T::ReactionScores::revert_post_score_on_reaction(owner.clone(), post, &reaction.kind)?;
+                T::ReactionScores::score_post_on_reaction(owner.clone(), post, &new_kind)?;
+
+                Self::dec_reactions_count(post, &reaction.kind);
+                Self::inc_reactions_count(post, &new_kind);
+
+                <PostById<T>>::insert(post_id, post);
+            },
+            ReactionTarget::Space(space_id) => {
+                let space = &mut Spaces::<T>::require_space(*space_id)?;
+                ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
+                ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+                Self::ensure_not_blocked_by(&owner, space.owner.clone())?;
+
+                Self::ensure_account_can_vote_on_space(owner.clone(), space, &new_kind)?;
+
+                T::ReactionScores::revert_space_score_on_reaction(owner.clone(), space, &reaction.kind)?;
+                T::ReactionScores::score_space_on_reaction(owner.clone(), space, &new_kind)?;
+
+                <SpaceById<T>>::insert(space.id, space.clone());
+            },
+            ReactionTarget::Account(account) => {
+                Self::ensure_not_blocked_by(&owner, account.clone())?;
+                Self::ensure_vote(&new_kind)?;
+
+                T::ReactionScores::revert_account_score_on_reaction(owner.clone(), account.clone(), &reaction.kind)?;
+                T::ReactionScores::score_account_on_reaction(owner.clone(), account.clone(), &new_kind)?;
+            },
+        }
+
+        reaction.kind = new_kind.clone();
+        reaction.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+        <ReactionById<T>>::insert(reaction_id, reaction);
+
+        Self::deposit_event(match target {
+            ReactionTarget::Post(post_id) => RawEvent::PostReactionUpdated(owner, post_id, reaction_id, new_kind),
+            target => RawEvent::ReactionUpdated(owner, target, reaction_id, new_kind),
+        });
+        Ok(())
+    }
+
+    fn do_delete_reaction(
+        owner: T::AccountId,
+        target: ReactionTarget<T::AccountId>,
+        reaction_id: ReactionId,
+    ) -> DispatchResult {
+        Self::ensure_reaction_by_account_on_target(&owner, &target, reaction_id)?;
+
+        let reaction = Self::require_reaction(reaction_id)?;
+        ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
+
+        match &target {
+            ReactionTarget::Post(post_id) => {
+                // A post could have been deleted or expired, but the reaction should still be removable.
+                if let Some(mut post) = Posts::<T>::post_by_id(post_id) {
+                    if let Some(space_id) = post.try_get_space_id() {
+                        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
+                    }
+
+                    T::ReactionScores::revert_post_score_on_reaction(owner.clone(), &mut post, &reaction.kind)?;
+                    Self::dec_reactions_count(&mut post, &reaction.kind);
+
+                    <PostById<T>>::insert(post_id, post);
+                } else if let ReactionKind::Emoji(emoji) = &reaction.kind {
+                    Self::dec_emoji_reactions_count(*post_id, emoji);
+                }
+            },
+            ReactionTarget::Space(space_id) => {
+                // A space could have been deleted, but the reaction should still be removable.
+                if let Some(mut space) = Spaces::<T>::space_by_id(space_id) {
+                    ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+                    T::ReactionScores::revert_space_score_on_reaction(owner.clone(), &mut space, &reaction.kind)?;
+                    <SpaceById<T>>::insert(space.id, space);
+                }
+            },
+            ReactionTarget::Account(account) => {
+                T::ReactionScores::revert_account_score_on_reaction(owner.clone(), account.clone(), &reaction.kind)?;
+            },
+        }
+
+        <ReactionById<T>>::remove(reaction_id);
+        <ReactionIdsByTarget<T>>::mutate(&target, |ids| remove_from_vec(ids, reaction_id));
+        <ReactionIdByAccountAndTarget<T>>::remove(&owner, &target);
+
+        Self::deposit_event(match target {
+            ReactionTarget::Post(post_id) => RawEvent::PostReactionDeleted(owner, post_id, reaction_id, reaction.kind),
+            target => RawEvent::ReactionDeleted(owner, target, reaction_id, reaction.kind),
+        });
+        Ok(())
+    }
+
+    fn ensure_reaction_by_account_on_target(
+        account: &T::AccountId,
+        target: &ReactionTarget<T::AccountId>,
+        reaction_id: ReactionId,
+    ) -> DispatchResult {
+        ensure!(
+            Self::reaction_id_by_account_and_target(account, target) == Some(reaction_id),
+            Error::<T>::ReactionByAccountNotFound
+        );
+        Ok(())
+    }
+
+    /// An account cannot react to the content of an account that blocked it.
+    fn ensure_not_blocked_by(account: &T::AccountId, content_owner: T::AccountId) -> DispatchResult {
+        ensure!(
+            !T::IsAccountBlockedBy::is_account_blocked_by(account.clone(), content_owner),
+            Error::<T>::BlockedByContentOwner
+        );
+        Ok(())
+    }
+
+    /// Spaces and accounts can only be upvoted or downvoted.
+    fn ensure_vote(kind: &ReactionKind) -> DispatchResult {
+        match kind {
+            ReactionKind::Upvote | ReactionKind::Downvote => Ok(()),
+            ReactionKind::Emoji(_) => Err(Error::<T>::OnlyVotesAllowedOnTarget.into()),
+        }
+    }
+
+    fn ensure_account_can_vote_on_space(
+        account: T::AccountId,
+        space: &Space<T>,
+        kind: &ReactionKind,
+    ) -> DispatchResult {
+        Self::ensure_vote(kind)?;
+
+        let (permission, error) = match kind {
+            ReactionKind::Downvote => (SpacePermission::Downvote, Error::<T>::NoPermissionToDownvote),
+            _ => (SpacePermission::Upvote, Error::<T>::NoPermissionToUpvote),
+        };
+
+        Spaces::<T>::ensure_account_has_space_permission(account, space, permission, error.into())
+    }
+
+    /// Check that an account can react with a given kind on a post: neither the post nor its space
+    /// is hidden, the account is not blocked and has a permission to react with this kind.
+    fn ensure_account_can_react_on_post(
+        account: &T::AccountId,
+        post: &Post<T>,
+        kind: &ReactionKind,
+    ) -> DispatchResult {
+        let space = post.get_space()?;
+        ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
+        ensure!(Posts::<T>::is_root_post_visible(post.id)?, Error::<T>::CannotReactWhenPostHidden);
+
+        ensure!(T::IsAccountBlocked::is_allowed_account(account.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+        ensure!(T::IsPostBlocked::is_allowed_post(post.id, space.id), UtilsError::<T>::PostIsBlocked);
+        Self::ensure_not_blocked_by(account, post.owner.clone())?;
+
+        Self::ensure_account_can_react(account.clone(), post, &space, kind)
+    }
+
+    fn ensure_account_can_react(
+        account: T::AccountId,
+        post: &Post<T>,
+        space: &Space<T>,
+        kind: &ReactionKind,
+    ) -> DispatchResult {
+        let (permission, error) = match kind {
+            ReactionKind::Upvote => (SpacePermission::Upvote, Error::<T>::NoPermissionToUpvote),
+            ReactionKind::Downvote => (SpacePermission::Downvote, Error::<T>::NoPermissionToDownvote),
+            ReactionKind::Emoji(emoji) => {
+                Self::ensure_valid_emoji(emoji)?;
+                if let Some(allowed_emojis) = Self::allowed_emojis_by_space_id(space.id) {
+                    ensure!(allowed_emojis.contains(emoji), Error::<T>::EmojiIsNotAllowed);
+                }
+                (SpacePermission::React, Error::<T>::NoPermissionToReact)
+            },
+        };
+
+        Posts::ensure_account_has_post_permission(account, post, space, permission, error.into())
+    }
+
+    /// An emoji should contain non-ASCII characters, unless it's a shortcode like `:tada:`.
+    /// Upvotes and downvotes are serialized as `U` and `D` in RPC, so such emojis are rejected.
+    fn ensure_valid_emoji(emoji: &[u8]) -> DispatchResult {
+        let is_shortcode = emoji.len() > 2
+            && emoji.starts_with(b":")
+            && emoji.ends_with(b":")
+            && emoji[1..emoji.len() - 1].iter().all(
+                |c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, b'_' | b'+' | b'-')
+            );
+
+        ensure!(
+            !emoji.is_empty()
+                && emoji.len() <= T::MaxEmojiLen::get() as usize
+                && sp_std::str::from_utf8(emoji).is_ok()
+                && (is_shortcode || !emoji.is_ascii()),
+            Error::<T>::InvalidEmoji
+        );
+        Ok(())
+    }
+
+    fn inc_reactions_count(post: &mut Post<T>, kind: &ReactionKind) {
+        match kind {
+            ReactionKind::Upvote => post.inc_upvotes(),
+            ReactionKind::Downvote => post.inc_downvotes(),
+            ReactionKind::Emoji(emoji) =>
+                EmojiReactionsCount::mutate(post.id, emoji, |count| *count = count.saturating_add(1)),
+        }
+    }
+
+    fn dec_reactions_count(post: &mut Post<T>, kind: &ReactionKind) {
+        match kind {
+            ReactionKind::Upvote => post.dec_upvotes(),
+            ReactionKind::Downvote => post.dec_downvotes(),
+            ReactionKind::Emoji(emoji) => Self::dec_emoji_reactions_count(post.id, emoji),
+        }
+    }
+
+    fn dec_emoji_reactions_count(post_id: PostId, emoji: &[u8]) {
+        let count = Self::emoji_reactions_count(post_id, emoji).saturating_sub(1);
+        if count == 0 {
+            EmojiReactionsCount::remove(post_id, emoji);
+        } else {
+            EmojiReactionsCount::insert(post_id, emoji, count);
+        }
+    }
+}
+
+impl<T: Config> SpaceCleanupHandler for Module<T> {
+    fn cleanup_deleted_space(space_id: SpaceId, limit: u32) -> u32 {
+        if limit > 0 && AllowedEmojisBySpaceId::contains_key(space_id) {
+            AllowedEmojisBySpaceId::remove(space_id);
+            return 1;
+        }
+        0
+    }
 }
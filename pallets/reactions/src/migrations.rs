@@ -0,0 +1,32 @@
+use frame_support::{storage::migration::storage_key_iter, Twox64Concat};
+
+use super::*;
+
+/// Move post reactions from the storages keyed by post ids
+/// (`ReactionIdsByPostId` and `PostReactionIdByAccount`) to the storages keyed by reaction targets.
+pub fn move_post_reactions_to_targets<T: Config>() -> frame_support::weights::Weight {
+    let mut items_migrated = 0;
+
+    let reaction_ids_by_post_id = storage_key_iter::<PostId, Vec<ReactionId>, Twox64Concat>(
+        b"ReactionsModule", b"ReactionIdsByPostId"
+    ).drain();
+
+    for (post_id, reaction_ids) in reaction_ids_by_post_id {
+        items_migrated += 1;
+        <ReactionIdsByTarget<T>>::insert(ReactionTarget::Post(post_id), reaction_ids);
+    }
+
+    let post_reaction_id_by_account = storage_key_iter::<(T::AccountId, PostId), ReactionId, Twox64Concat>(
+        b"ReactionsModule", b"PostReactionIdByAccount"
+    ).drain();
+
+    for ((account, post_id), reaction_id) in post_reaction_id_by_account {
+        items_migrated += 1;
+        <ReactionIdByAccountAndTarget<T>>::insert(account, ReactionTarget::Post(post_id), reaction_id);
+    }
+
+    ReactionTargetsMigrated::put(true);
+
+    // Every migrated item is removed from the old storage and inserted to the new one.
+    T::DbWeight::get().reads_writes(items_migrated + 1, items_migrated * 2 + 1)
+}
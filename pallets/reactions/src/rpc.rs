@@ -1,13 +1,15 @@
 use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
+use frame_support::storage::IterableStorageDoubleMap;
 use sp_runtime::traits::Zero;
 use sp_std::collections::btree_map::BTreeMap;
-use sp_std::prelude::*;
+use sp_std::{prelude::*, vec};
 
+use pallet_posts::Module as Posts;
 use pallet_utils::{PostId, rpc::FlatWhoAndWhen};
 
-use crate::{Module, Reaction, ReactionId, ReactionKind, Config};
+use crate::{Module, Reaction, ReactionId, ReactionKind, ReactionTarget, Config, EmojiReactionsCount};
 
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
@@ -22,9 +24,10 @@ pub struct FlatReaction<AccountId, BlockNumber> {
 #[cfg(feature = "std")]
 impl Serialize for ReactionKind {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
-        let reaction_kind_bytes: &[u8; 1] = match self {
+        let reaction_kind_bytes: &[u8] = match self {
             ReactionKind::Upvote => b"U",
-            ReactionKind::Downvote => b"D"
+            ReactionKind::Downvote => b"D",
+            ReactionKind::Emoji(emoji) => emoji,
         };
 
         serializer.serialize_str(
@@ -59,10 +62,19 @@ impl<T: Config> Module<T> {
         post_id: PostId,
         limit: u64,
         offset: u64,
+    ) -> Vec<FlatReaction<T::AccountId, T::BlockNumber>> {
+        Self::get_reactions_by_target(ReactionTarget::Post(post_id), limit, offset)
+    }
+
+    /// Get the reactions on a given post, space or account, starting from the latest ones.
+    pub fn get_reactions_by_target(
+        target: ReactionTarget<T::AccountId>,
+        limit: u64,
+        offset: u64,
     ) -> Vec<FlatReaction<T::AccountId, T::BlockNumber>> {
         let mut reactions = Vec::new();
 
-        let reaction_ids: Vec<PostId> = Self::reaction_ids_by_post_id(&post_id);
+        let reaction_ids: Vec<ReactionId> = Self::reaction_ids_by_target(&target);
         let mut i = reaction_ids.len().saturating_sub(1 + offset as usize);
 
         while reactions.len() < limit as usize {
@@ -80,19 +92,58 @@ impl<T: Config> Module<T> {
         reactions
     }
 
+    fn get_reaction_kind_by_account_and_target(
+        account: &T::AccountId,
+        target: &ReactionTarget<T::AccountId>,
+    ) -> Option<ReactionKind> {
+        Self::reaction_id_by_account_and_target(account, target)
+            .and_then(|reaction_id| Self::require_reaction(reaction_id).ok())
+            .map(|reaction| reaction.kind)
+    }
+
     pub fn get_reaction_kinds_by_post_ids_and_reactor(
         post_ids: Vec<PostId>,
         reactor: T::AccountId,
     ) -> BTreeMap<PostId, ReactionKind> {
-        let res = post_ids.iter()
-            .filter_map(|post_id| Some(*post_id).zip(
-                Option::from(Self::post_reaction_id_by_account((&reactor, post_id)))
-                    .filter(|v| *v != 0)
-                    .and_then(|reaction_id|
-                        Self::require_reaction(reaction_id).ok().map(|reaction| reaction.kind)
-                    )
-            ));
-
-        res.clone().collect()
+        post_ids.into_iter()
+            .filter_map(|post_id|
+                Self::get_reaction_kind_by_account_and_target(&reactor, &ReactionTarget::Post(post_id))
+                    .map(|kind| (post_id, kind))
+            )
+            .collect()
     }
-}
\ No newline at end of file
+
+    /// Get the kinds of reactions made by `reactor` on given posts, spaces or accounts.
+    /// Targets that `reactor` has not reacted to are skipped.
+    pub fn get_reaction_kinds_by_targets_and_reactor(
+        targets: Vec<ReactionTarget<T::AccountId>>,
+        reactor: T::AccountId,
+    ) -> Vec<(ReactionTarget<T::AccountId>, ReactionKind)> {
+        targets.into_iter()
+            .filter_map(|target|
+                Self::get_reaction_kind_by_account_and_target(&reactor, &target)
+                    .map(|kind| (target, kind))
+            )
+            .collect()
+    }
+
+    /// Get the numbers of reactions of each kind (including emojis) on given posts.
+    pub fn get_reaction_counts_by_post_ids(
+        post_ids: Vec<PostId>,
+    ) -> BTreeMap<PostId, Vec<(ReactionKind, u32)>> {
+        post_ids.into_iter()
+            .filter_map(|post_id| Posts::<T>::post_by_id(post_id).map(|post| {
+                let mut counts: Vec<(ReactionKind, u32)> = vec![
+                    (ReactionKind::Upvote, post.upvotes_count),
+                    (ReactionKind::Downvote, post.downvotes_count),
+                ];
+                counts.extend(
+                    EmojiReactionsCount::iter_prefix(post_id)
+                        .map(|(emoji, count)| (ReactionKind::Emoji(emoji), count))
+                );
+
+                (post_id, counts)
+            }))
+            .collect()
+    }
+}
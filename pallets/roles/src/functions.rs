@@ -1,7 +1,9 @@
 use super::*;
 
 use frame_support::dispatch::DispatchError;
-use pallet_permissions::SpacePermissionsContext;
+use sp_std::vec;
+use df_traits::SpaceForRoles;
+use pallet_permissions::{SpacePermissions, SpacePermissionsContext};
 
 impl<T: Config> Module<T> {
 
@@ -39,6 +41,18 @@ impl<T: Config> Module<T> {
   ) -> DispatchResult {
 
     let space = T::Spaces::get_space(space_id)?;
+    let ctx = Self::build_space_permissions_context(&user, space_id, space, None);
+    Self::ensure_user_has_space_permission(user, ctx, permission, error)
+  }
+
+  /// Build a permissions context of a user in a loaded space,
+  /// applying the permission overrides of a post (if any) on top of the space permissions.
+  fn build_space_permissions_context(
+    user: &User<T::AccountId>,
+    space_id: SpaceId,
+    space: SpaceForRoles<T::AccountId>,
+    post_perms: Option<SpacePermissions>,
+  ) -> SpacePermissionsContext {
 
     let mut is_owner = false;
     let mut is_follower = false;
@@ -53,19 +67,25 @@ impl<T: Config> Module<T> {
       User::Space(_) => (/* Not implemented yet. */),
     }
 
-    Self::ensure_user_has_space_permission(
-      user,
-      SpacePermissionsContext {
-        space_id,
-        is_space_owner: is_owner,
-        is_space_follower: is_follower,
-        space_perms: space.permissions
-      },
-      permission,
-      error
-    )
+    SpacePermissionsContext {
+      space_id,
+      is_space_owner: is_owner,
+      is_space_follower: is_follower,
+      space_perms: space.permissions,
+      post_perms,
+    }
   }
 
+  /// Check a permission against the space (and post, if provided in `ctx`) permissions first,
+  /// then against the reputation thresholds and the roles granted to a user in this space.
+  /// If a permission is forbidden by the post or space permissions (`none`),
+  /// it cannot be granted by any reputation or role.
+  ///
+  /// A subspace that doesn't override its permissions inherits the permissions of the nearest
+  /// parent space that does, checking no more than `MaxParentSpacesToCheck` parents. The default
+  /// permissions are used only if none of these parents override them. The inherited permissions
+  /// (with the post overrides on top, if any) are checked for the user as a member of the subspace
+  /// and then of every parent space on the way, so the roles granted in any of them apply too.
   fn ensure_user_has_space_permission(
     user: User<T::AccountId>,
     ctx: SpacePermissionsContext,
@@ -73,29 +93,75 @@ impl<T: Config> Module<T> {
     error: DispatchError,
   ) -> DispatchResult {
 
-    match Permissions::<T>::has_user_a_space_permission(
-      ctx.clone(),
-      permission.clone()
-    ) {
-      Some(true) => return Ok(()),
-      Some(false) => return Err(error),
-      _ => (/* Need to check in dynamic roles */)
+    let mut contexts = vec![ctx];
+    let mut parents_checked: u32 = 0;
+
+    while parents_checked < T::MaxParentSpacesToCheck::get() {
+      let ctx = &contexts[contexts.len() - 1];
+      if ctx.space_perms.is_some() {
+        break;
+      }
+
+      let parent = T::Spaces::get_space(ctx.space_id).ok()
+        .and_then(|space| space.parent_id)
+        .and_then(|parent_id| T::Spaces::get_space(parent_id).ok().map(|parent_space| (parent_id, parent_space)));
+
+      match parent {
+        Some((parent_id, parent_space)) => {
+          let parent_ctx = Self::build_space_permissions_context(&user, parent_id, parent_space, ctx.post_perms.clone());
+          contexts.push(parent_ctx);
+          parents_checked += 1;
+        },
+        None => break,
+      }
     }
 
-    Self::has_permission_in_space_roles(
-      user,
-      ctx.space_id,
-      permission,
-      error
-    )
+    let inherited_space_perms = contexts[contexts.len() - 1].space_perms.clone();
+
+    for ctx in contexts {
+      let ctx = SpacePermissionsContext { space_perms: inherited_space_perms.clone(), ..ctx };
+
+      match Permissions::<T>::has_user_a_space_permission(
+        ctx.clone(),
+        permission.clone()
+      ) {
+        Some(true) => return Ok(()),
+        Some(false) => return Err(error),
+        _ => (/* Need to check in reputation thresholds and dynamic roles */)
+      }
+
+      if Self::has_enough_reputation_for_permission(&user, ctx.clone(), &permission) {
+        return Ok(());
+      }
+
+      if Self::has_permission_in_space_roles(user.clone(), ctx.space_id, &permission) {
+        return Ok(());
+      }
+    }
+
+    Err(error)
+  }
+
+  fn has_enough_reputation_for_permission(
+    user: &User<T::AccountId>,
+    ctx: SpacePermissionsContext,
+    permission: &SpacePermission,
+  ) -> bool {
+
+    if let User::Account(account) = user {
+      if let Some(threshold) = Permissions::<T>::get_reputation_threshold(ctx, permission) {
+        return T::Reputation::get_reputation(account.clone()) >= threshold;
+      }
+    }
+
+    false
   }
 
   fn has_permission_in_space_roles(
     user: User<T::AccountId>,
     space_id: SpaceId,
-    permission: SpacePermission,
-    error: DispatchError,
-  ) -> DispatchResult {
+    permission: &SpacePermission,
+  ) -> bool {
 
     let role_ids = Self::role_ids_by_user_in_space(user, space_id);
 
@@ -112,13 +178,13 @@ impl<T: Config> Module<T> {
           }
         }
 
-        if !is_expired && role.permissions.contains(&permission) {
-          return Ok(());
+        if !is_expired && role.permissions.contains(permission) {
+          return true;
         }
       }
     }
 
-    Err(error)
+    false
   }
 }
 
@@ -204,3 +270,51 @@ impl<T: Config> PermissionChecker for Module<T> {
     )
   }
 }
+
+impl<T: Config> SpaceCleanupHandler for Module<T> {
+  /// Delete all roles of a deleted space and revoke them from their users.
+  fn cleanup_deleted_space(space_id: SpaceId, limit: u32) -> u32 {
+    let mut role_ids = Self::role_ids_by_space_id(space_id);
+    let mut removed = 0;
+
+    while removed < limit {
+      let role_id = match role_ids.last() {
+        Some(role_id) => *role_id,
+        None => break,
+      };
+
+      let mut users = <UsersByRoleId<T>>::take(role_id);
+      while removed < limit {
+        if let Some(user) = users.pop() {
+          // All the roles in this space are going to be deleted.
+          <RoleIdsByUserInSpace<T>>::remove(user, space_id);
+          removed += 1;
+        } else {
+          break;
+        }
+      }
+
+      if !users.is_empty() {
+        <UsersByRoleId<T>>::insert(role_id, users);
+        break;
+      }
+
+      if removed < limit {
+        <RoleById<T>>::remove(role_id);
+        role_ids.pop();
+        removed += 1;
+      } else {
+        // The role has no users left, it will be deleted within the next batch.
+        break;
+      }
+    }
+
+    if role_ids.is_empty() {
+      RoleIdsBySpaceId::remove(space_id);
+    } else {
+      RoleIdsBySpaceId::insert(space_id, role_ids);
+    }
+
+    removed
+  }
+}
@@ -23,7 +23,8 @@ use sp_std::{collections::btree_set::BTreeSet, prelude::*};
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::{
-    PermissionChecker, SpaceFollowsProvider, SpaceForRolesProvider,
+    PermissionChecker, ReputationProvider, SpaceFollowsProvider, SpaceForRolesProvider,
+    SpaceCleanupHandler,
     moderation::{IsAccountBlocked, IsContentBlocked},
 };
 use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissionSet};
@@ -92,10 +93,17 @@ pub trait Config: system::Config
     /// then `TooManyUsersToDeleteRole` error will be returned and the dispatch will fail.
     type MaxUsersToProcessPerDeleteRole: Get<u16>;
 
+    /// The max number of parent spaces to walk up when checking the permissions
+    /// inherited by a subspace.
+    type MaxParentSpacesToCheck: Get<u32>;
+
     type Spaces: SpaceForRolesProvider<AccountId=Self::AccountId>;
 
     type SpaceFollows: SpaceFollowsProvider<AccountId=Self::AccountId>;
 
+    /// Used to check the reputation thresholds of space permissions.
+    type Reputation: ReputationProvider<AccountId=Self::AccountId>;
+
     type IsAccountBlocked: IsAccountBlocked<Self::AccountId>;
 
     type IsContentBlocked: IsContentBlocked;
@@ -183,6 +191,8 @@ decl_module! {
 
     const MaxUsersToProcessPerDeleteRole: u16 = T::MaxUsersToProcessPerDeleteRole::get();
 
+    const MaxParentSpacesToCheck: u32 = T::MaxParentSpacesToCheck::get();
+
     // Initializing errors
     type Error = Error<T>;
 
@@ -21,7 +21,7 @@ use pallet_permissions::{
     SpacePermission,
     SpacePermission as SP,
 };
-use df_traits::{SpaceForRoles, SpaceFollowsProvider, SpaceForRolesProvider};
+use df_traits::{SpaceForRoles, SpaceFollowsProvider, SpaceForRolesProvider, ReputationProvider};
 use pallet_utils::{SpaceId, User, Content, DEFAULT_MIN_HANDLE_LEN, DEFAULT_MAX_HANDLE_LEN};
 
 use crate as roles;
@@ -121,13 +121,16 @@ impl pallet_permissions::Config for Test {
 
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 20;
+  pub const MaxParentSpacesToCheck: u32 = 5;
 }
 
 impl Config for Test {
     type Event = Event;
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+    type MaxParentSpacesToCheck = MaxParentSpacesToCheck;
     type Spaces = Roles;
     type SpaceFollows = Roles;
+    type Reputation = Roles;
     type IsAccountBlocked = ();
     type IsContentBlocked = ();
 }
@@ -142,7 +145,7 @@ impl<T: Config> SpaceForRolesProvider for Module<T> {
     // Currently, we have a list of valid space id's to check
     fn get_space(id: SpaceId) -> Result<SpaceForRoles<Self::AccountId>, DispatchError> {
         if self::valid_space_ids().contains(&id) {
-            return Ok(SpaceForRoles { owner: ACCOUNT1, permissions: None })
+            return Ok(SpaceForRoles { owner: ACCOUNT1, parent_id: None, permissions: None })
         }
 
         Err("SpaceNotFound".into())
@@ -157,6 +160,14 @@ impl<T: Config> SpaceFollowsProvider for Module<T> {
     }
 }
 
+impl<T: Config> ReputationProvider for Module<T> {
+    type AccountId = AccountId;
+
+    fn get_reputation(_account: Self::AccountId) -> u32 {
+        1
+    }
+}
+
 
 pub struct ExtBuilder;
 
@@ -0,0 +1,471 @@
+//! # Scores Module
+//!
+//! The Scores module updates the scores of posts and spaces, as well as the reputation
+//! of their owners, when other accounts react to, share or comment on posts,
+//! follow spaces or accounts, and upvote or downvote spaces or accounts.
+//!
+//! A score diff of an action is a weight of this action, configured in the runtime,
+//! multiplied by `1 + log2(reputation)` of the account that made the action.
+//! Every score change is stored, so it can be reverted when the action is undone
+//! (e.g. a reaction is removed, a comment is deleted or a space is unfollowed).
+//! Actions on one's own posts, spaces or account don't change any scores.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use frame_support::{
+    decl_event, decl_module, decl_storage,
+    dispatch::DispatchResult,
+    traits::Get,
+};
+use frame_system as system;
+use sp_runtime::RuntimeDebug;
+
+use pallet_posts::{Module as Posts, Post, PostById, PostExtension, PostScores};
+use pallet_profile_follows::{BeforeAccountFollowed, BeforeAccountUnfollowed};
+use pallet_profiles::{Module as Profiles, SocialAccountById};
+use pallet_reactions::{ReactionScores, ReactionKind};
+use pallet_space_follows::{BeforeSpaceFollowed, BeforeSpaceUnfollowed};
+use pallet_spaces::{Space, SpaceById};
+use pallet_utils::{log_2, PostId, SpaceId};
+
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum ScoringAction {
+    UpvotePost,
+    DownvotePost,
+    SharePost,
+    CreateComment,
+    UpvoteComment,
+    DownvoteComment,
+    ShareComment,
+    FollowSpace,
+    FollowAccount,
+    UpvoteSpace,
+    DownvoteSpace,
+    UpvoteAccount,
+    DownvoteAccount,
+}
+
+/// A change of scores made by a scoring action, kept to revert it when the action is undone.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ScoreChange<AccountId> {
+    /// An account whose reputation was changed.
+    pub account: AccountId,
+    /// A diff applied to the score of a post or space.
+    pub score_diff: i16,
+    /// A diff actually applied to the reputation of `account`.
+    /// It can differ from `score_diff`, because reputation never goes below 1.
+    pub reputation_diff: i16,
+}
+
+/// The pallet's configuration trait.
+pub trait Config: system::Config
+    + pallet_utils::Config
+    + pallet_profiles::Config
+    + pallet_profile_follows::Config
+    + pallet_spaces::Config
+    + pallet_space_follows::Config
+    + pallet_posts::Config
+    + pallet_reactions::Config
+{
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+
+    // Weights of the scoring actions.
+
+    type FollowSpaceActionWeight: Get<i16>;
+    type FollowAccountActionWeight: Get<i16>;
+
+    type SharePostActionWeight: Get<i16>;
+    type UpvotePostActionWeight: Get<i16>;
+    type DownvotePostActionWeight: Get<i16>;
+
+    type CreateCommentActionWeight: Get<i16>;
+    type ShareCommentActionWeight: Get<i16>;
+    type UpvoteCommentActionWeight: Get<i16>;
+    type DownvoteCommentActionWeight: Get<i16>;
+
+    type UpvoteSpaceActionWeight: Get<i16>;
+    type DownvoteSpaceActionWeight: Get<i16>;
+    type UpvoteAccountActionWeight: Get<i16>;
+    type DownvoteAccountActionWeight: Get<i16>;
+}
+
+// This pallet's storage items.
+decl_storage! {
+    trait Store for Module<T: Config> as ScoresModule {
+
+        /// Score changes made by an account's actions on posts.
+        /// For reactions a post id is the id of a reacted post,
+        /// for comments and shares it's the id of a new comment or sharing post.
+        pub PostScoreChangeByAccount get(fn post_score_change_by_account):
+            map hasher(blake2_128_concat) (T::AccountId, PostId, ScoringAction) => Option<ScoreChange<T::AccountId>>;
+
+        /// Score changes made by an account following a space.
+        pub SpaceScoreChangeByAccount get(fn space_score_change_by_account):
+            map hasher(blake2_128_concat) (T::AccountId, SpaceId) => Option<ScoreChange<T::AccountId>>;
+
+        /// Reputation diffs made by an account (follower) following another account.
+        pub AccountReputationDiffByAccount get(fn account_reputation_diff_by_account):
+            map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => Option<i16>;
+
+        /// Score changes made by an account's reaction on a space.
+        pub SpaceScoreChangeByReaction get(fn space_score_change_by_reaction):
+            map hasher(blake2_128_concat) (T::AccountId, SpaceId) => Option<ScoreChange<T::AccountId>>;
+
+        /// Reputation diffs made by an account's (reactor) reaction on another account.
+        pub AccountReputationDiffByReaction get(fn account_reputation_diff_by_reaction):
+            map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => Option<i16>;
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as system::Config>::AccountId,
+    {
+        /// Reputation of an account was changed by a scoring action. [account, action, new_reputation]
+        AccountReputationChanged(AccountId, ScoringAction, u32),
+    }
+);
+
+decl_module! {
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    const FollowSpaceActionWeight: i16 = T::FollowSpaceActionWeight::get();
+    const FollowAccountActionWeight: i16 = T::FollowAccountActionWeight::get();
+
+    const SharePostActionWeight: i16 = T::SharePostActionWeight::get();
+    const UpvotePostActionWeight: i16 = T::UpvotePostActionWeight::get();
+    const DownvotePostActionWeight: i16 = T::DownvotePostActionWeight::get();
+
+    const CreateCommentActionWeight: i16 = T::CreateCommentActionWeight::get();
+    const ShareCommentActionWeight: i16 = T::ShareCommentActionWeight::get();
+    const UpvoteCommentActionWeight: i16 = T::UpvoteCommentActionWeight::get();
+    const DownvoteCommentActionWeight: i16 = T::DownvoteCommentActionWeight::get();
+
+    const UpvoteSpaceActionWeight: i16 = T::UpvoteSpaceActionWeight::get();
+    const DownvoteSpaceActionWeight: i16 = T::DownvoteSpaceActionWeight::get();
+    const UpvoteAccountActionWeight: i16 = T::UpvoteAccountActionWeight::get();
+    const DownvoteAccountActionWeight: i16 = T::DownvoteAccountActionWeight::get();
+
+    // Initializing events
+    fn deposit_event() = default;
+  }
+}
+
+impl<T: Config> Module<T> {
+
+    pub fn weight_of_scoring_action(action: ScoringAction) -> i16 {
+        use ScoringAction::*;
+        match action {
+            UpvotePost => T::UpvotePostActionWeight::get(),
+            DownvotePost => T::DownvotePostActionWeight::get(),
+            SharePost => T::SharePostActionWeight::get(),
+            CreateComment => T::CreateCommentActionWeight::get(),
+            UpvoteComment => T::UpvoteCommentActionWeight::get(),
+            DownvoteComment => T::DownvoteCommentActionWeight::get(),
+            ShareComment => T::ShareCommentActionWeight::get(),
+            FollowSpace => T::FollowSpaceActionWeight::get(),
+            FollowAccount => T::FollowAccountActionWeight::get(),
+            UpvoteSpace => T::UpvoteSpaceActionWeight::get(),
+            DownvoteSpace => T::DownvoteSpaceActionWeight::get(),
+            UpvoteAccount => T::UpvoteAccountActionWeight::get(),
+            DownvoteAccount => T::DownvoteAccountActionWeight::get(),
+        }
+    }
+
+    pub fn score_diff_for_action(actor_reputation: u32, action: ScoringAction) -> i16 {
+        Self::smooth_reputation(actor_reputation).saturating_mul(Self::weight_of_scoring_action(action))
+    }
+
+    /// Reputation is smoothed logarithmically, so that accounts with a high reputation
+    /// don't get an overwhelming influence on scores.
+    fn smooth_reputation(reputation: u32) -> i16 {
+        log_2(reputation).map_or(1, |log| log as i16 + 1)
+    }
+
+    /// Emoji reactions don't affect scores, so there is no scoring action for them.
+    fn scoring_action_by_reaction(post: &Post<T>, reaction_kind: &ReactionKind) -> Option<ScoringAction> {
+        match (post.is_comment(), reaction_kind) {
+            (false, ReactionKind::Upvote) => Some(ScoringAction::UpvotePost),
+            (false, ReactionKind::Downvote) => Some(ScoringAction::DownvotePost),
+            (true, ReactionKind::Upvote) => Some(ScoringAction::UpvoteComment),
+            (true, ReactionKind::Downvote) => Some(ScoringAction::DownvoteComment),
+            (_, ReactionKind::Emoji(_)) => None,
+        }
+    }
+
+    /// Spaces and accounts can only be upvoted or downvoted, so emojis give no scoring action.
+    fn scoring_action_by_vote(
+        reaction_kind: &ReactionKind,
+        upvote_action: ScoringAction,
+        downvote_action: ScoringAction,
+    ) -> Option<ScoringAction> {
+        match reaction_kind {
+            ReactionKind::Upvote => Some(upvote_action),
+            ReactionKind::Downvote => Some(downvote_action),
+            ReactionKind::Emoji(_) => None,
+        }
+    }
+
+    fn scoring_action_by_share(original_post: &Post<T>) -> ScoringAction {
+        if original_post.is_comment() {
+            ScoringAction::ShareComment
+        } else {
+            ScoringAction::SharePost
+        }
+    }
+
+    /// Change the reputation of an account, but don't let it go below 1.
+    /// Returns a diff that was actually applied.
+    fn change_account_reputation(account: T::AccountId, diff: i16, action: ScoringAction) -> i16 {
+        let mut social_account = Profiles::<T>::get_or_new_social_account(account.clone());
+        let old_reputation = social_account.reputation;
+
+        social_account.change_reputation(diff);
+        social_account.reputation = social_account.reputation.max(1);
+
+        let new_reputation = social_account.reputation;
+        <SocialAccountById<T>>::insert(account.clone(), social_account);
+
+        Self::deposit_event(RawEvent::AccountReputationChanged(account, action, new_reputation));
+        (new_reputation as i64 - old_reputation as i64) as i16
+    }
+
+    /// Change the score of a post and, if it's a root post, the score of its space.
+    fn change_post_score(post: &mut Post<T>, diff: i16) {
+        post.change_score(diff);
+
+        if !post.is_comment() {
+            if let Some(space_id) = post.space_id {
+                <SpaceById<T>>::mutate(space_id, |space_opt| {
+                    if let Some(space) = space_opt {
+                        space.change_score(diff);
+                    }
+                });
+            }
+        }
+    }
+
+    fn score_post(actor: T::AccountId, post: &mut Post<T>, action_post_id: PostId, action: ScoringAction) {
+        let key = (actor.clone(), action_post_id, action);
+        if actor == post.owner || <PostScoreChangeByAccount<T>>::contains_key(&key) {
+            return;
+        }
+
+        let actor_reputation = Profiles::<T>::get_or_new_social_account(actor).reputation;
+        let score_diff = Self::score_diff_for_action(actor_reputation, action);
+
+        Self::change_post_score(post, score_diff);
+        let reputation_diff = Self::change_account_reputation(post.owner.clone(), score_diff, action);
+
+        <PostScoreChangeByAccount<T>>::insert(key, ScoreChange {
+            account: post.owner.clone(),
+            score_diff,
+            reputation_diff,
+        });
+    }
+
+    /// Revert the score change of an action. The scored post can be `None` if it was deleted.
+    fn revert_post_score(
+        actor: T::AccountId,
+        post_opt: Option<&mut Post<T>>,
+        action_post_id: PostId,
+        action: ScoringAction,
+    ) {
+        if let Some(change) = <PostScoreChangeByAccount<T>>::take((actor, action_post_id, action)) {
+            if let Some(post) = post_opt {
+                Self::change_post_score(post, -change.score_diff);
+            }
+            Self::change_account_reputation(change.account, -change.reputation_diff, action);
+        }
+    }
+
+    fn revert_stored_post_score(
+        actor: T::AccountId,
+        scored_post_id: PostId,
+        action_post_id: PostId,
+        action: ScoringAction,
+    ) {
+        let mut scored_post_opt = Posts::<T>::post_by_id(scored_post_id);
+
+        Self::revert_post_score(actor, scored_post_opt.as_mut(), action_post_id, action);
+
+        if let Some(scored_post) = scored_post_opt {
+            <PostById<T>>::insert(scored_post_id, scored_post);
+        }
+    }
+}
+
+impl<T: Config> PostScores<T> for Module<T> {
+    fn score_post_on_new_share(account: T::AccountId, sharing_post_id: PostId, original_post_id: PostId) -> DispatchResult {
+        if let Some(mut original_post) = Posts::<T>::post_by_id(original_post_id) {
+            let action = Self::scoring_action_by_share(&original_post);
+            Self::score_post(account, &mut original_post, sharing_post_id, action);
+            <PostById<T>>::insert(original_post_id, original_post);
+        }
+        Ok(())
+    }
+
+    fn score_root_post_on_new_comment(account: T::AccountId, comment_id: PostId, root_post_id: PostId) -> DispatchResult {
+        if let Some(mut root_post) = Posts::<T>::post_by_id(root_post_id) {
+            Self::score_post(account, &mut root_post, comment_id, ScoringAction::CreateComment);
+            <PostById<T>>::insert(root_post_id, root_post);
+        }
+        Ok(())
+    }
+
+    fn revert_scores_on_post_deleted(post: &Post<T>) -> DispatchResult {
+        match post.extension {
+            PostExtension::RegularPost | PostExtension::Poll(_) => (),
+            PostExtension::Comment(comment_ext) => Self::revert_stored_post_score(
+                post.owner.clone(), comment_ext.root_post_id, post.id, ScoringAction::CreateComment
+            ),
+            PostExtension::SharedPost(original_post_id) => {
+                // The original post could have been deleted, so both share actions are checked.
+                for action in [ScoringAction::SharePost, ScoringAction::ShareComment].iter() {
+                    Self::revert_stored_post_score(post.owner.clone(), original_post_id, post.id, *action);
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+impl<T: Config> ReactionScores<T> for Module<T> {
+    fn score_post_on_reaction(actor: T::AccountId, post: &mut Post<T>, reaction_kind: &ReactionKind) -> DispatchResult {
+        if let Some(action) = Self::scoring_action_by_reaction(post, reaction_kind) {
+            let post_id = post.id;
+            Self::score_post(actor, post, post_id, action);
+        }
+        Ok(())
+    }
+
+    fn revert_post_score_on_reaction(actor: T::AccountId, post: &mut Post<T>, reaction_kind: &ReactionKind) -> DispatchResult {
+        if let Some(action) = Self::scoring_action_by_reaction(post, reaction_kind) {
+            let post_id = post.id;
+            Self::revert_post_score(actor, Some(post), post_id, action);
+        }
+        Ok(())
+    }
+
+    fn score_space_on_reaction(actor: T::AccountId, space: &mut Space<T>, reaction_kind: &ReactionKind) -> DispatchResult {
+        let key = (actor.clone(), space.id);
+        if actor == space.owner || <SpaceScoreChangeByReaction<T>>::contains_key(&key) {
+            return Ok(());
+        }
+
+        let action = match Self::scoring_action_by_vote(
+            reaction_kind, ScoringAction::UpvoteSpace, ScoringAction::DownvoteSpace
+        ) {
+            Some(action) => action,
+            None => return Ok(()),
+        };
+
+        let actor_reputation = Profiles::<T>::get_or_new_social_account(actor).reputation;
+        let score_diff = Self::score_diff_for_action(actor_reputation, action);
+
+        space.change_score(score_diff);
+        let reputation_diff = Self::change_account_reputation(space.owner.clone(), score_diff, action);
+
+        <SpaceScoreChangeByReaction<T>>::insert(key, ScoreChange {
+            account: space.owner.clone(),
+            score_diff,
+            reputation_diff,
+        });
+        Ok(())
+    }
+
+    fn revert_space_score_on_reaction(actor: T::AccountId, space: &mut Space<T>, reaction_kind: &ReactionKind) -> DispatchResult {
+        if let Some(action) = Self::scoring_action_by_vote(
+            reaction_kind, ScoringAction::UpvoteSpace, ScoringAction::DownvoteSpace
+        ) {
+            if let Some(change) = <SpaceScoreChangeByReaction<T>>::take((actor, space.id)) {
+                space.change_score(-change.score_diff);
+                Self::change_account_reputation(change.account, -change.reputation_diff, action);
+            }
+        }
+        Ok(())
+    }
+
+    fn score_account_on_reaction(actor: T::AccountId, account: T::AccountId, reaction_kind: &ReactionKind) -> DispatchResult {
+        let key = (actor.clone(), account.clone());
+        if actor == account || <AccountReputationDiffByReaction<T>>::contains_key(&key) {
+            return Ok(());
+        }
+
+        if let Some(action) = Self::scoring_action_by_vote(
+            reaction_kind, ScoringAction::UpvoteAccount, ScoringAction::DownvoteAccount
+        ) {
+            let actor_reputation = Profiles::<T>::get_or_new_social_account(actor).reputation;
+            let score_diff = Self::score_diff_for_action(actor_reputation, action);
+
+            let reputation_diff = Self::change_account_reputation(account, score_diff, action);
+            <AccountReputationDiffByReaction<T>>::insert(key, reputation_diff);
+        }
+        Ok(())
+    }
+
+    fn revert_account_score_on_reaction(actor: T::AccountId, account: T::AccountId, reaction_kind: &ReactionKind) -> DispatchResult {
+        if let Some(action) = Self::scoring_action_by_vote(
+            reaction_kind, ScoringAction::UpvoteAccount, ScoringAction::DownvoteAccount
+        ) {
+            if let Some(reputation_diff) = <AccountReputationDiffByReaction<T>>::take((actor, account.clone())) {
+                Self::change_account_reputation(account, -reputation_diff, action);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Config> BeforeSpaceFollowed<T> for Module<T> {
+    fn before_space_followed(follower: T::AccountId, follower_reputation: u32, space: &mut Space<T>) -> DispatchResult {
+        if follower == space.owner {
+            return Ok(());
+        }
+
+        let action = ScoringAction::FollowSpace;
+        let score_diff = Self::score_diff_for_action(follower_reputation, action);
+
+        space.change_score(score_diff);
+        let reputation_diff = Self::change_account_reputation(space.owner.clone(), score_diff, action);
+
+        <SpaceScoreChangeByAccount<T>>::insert((follower, space.id), ScoreChange {
+            account: space.owner.clone(),
+            score_diff,
+            reputation_diff,
+        });
+        Ok(())
+    }
+}
+
+impl<T: Config> BeforeSpaceUnfollowed<T> for Module<T> {
+    fn before_space_unfollowed(follower: T::AccountId, space: &mut Space<T>) -> DispatchResult {
+        if let Some(change) = <SpaceScoreChangeByAccount<T>>::take((follower, space.id)) {
+            space.change_score(-change.score_diff);
+            Self::change_account_reputation(change.account, -change.reputation_diff, ScoringAction::FollowSpace);
+        }
+        Ok(())
+    }
+}
+
+impl<T: Config> BeforeAccountFollowed<T> for Module<T> {
+    fn before_account_followed(follower: T::AccountId, follower_reputation: u32, following: T::AccountId) -> DispatchResult {
+        let action = ScoringAction::FollowAccount;
+        let score_diff = Self::score_diff_for_action(follower_reputation, action);
+
+        let reputation_diff = Self::change_account_reputation(following.clone(), score_diff, action);
+        <AccountReputationDiffByAccount<T>>::insert((follower, following), reputation_diff);
+        Ok(())
+    }
+}
+
+impl<T: Config> BeforeAccountUnfollowed<T> for Module<T> {
+    fn before_account_unfollowed(follower: T::AccountId, following: T::AccountId) -> DispatchResult {
+        if let Some(reputation_diff) = <AccountReputationDiffByAccount<T>>::take((follower, following.clone())) {
+            Self::change_account_reputation(following, -reputation_diff, ScoringAction::FollowAccount);
+        }
+        Ok(())
+    }
+}
@@ -3,14 +3,18 @@
 use codec::Codec;
 use sp_std::vec::Vec;
 
+use pallet_profiles::rpc::FlatSocialAccount;
 use pallet_utils::SpaceId;
 
 sp_api::decl_runtime_apis! {
-    pub trait SpaceFollowsApi<AccountId> where
-        AccountId: Codec
+    pub trait SpaceFollowsApi<AccountId, BlockNumber> where
+        AccountId: Codec,
+        BlockNumber: Codec
     {
         fn get_space_ids_followed_by_account(account: AccountId) -> Vec<SpaceId>;
 
         fn filter_followed_space_ids(account: AccountId, space_ids: Vec<SpaceId>) -> Vec<SpaceId>;
+
+        fn get_space_followers(space_id: SpaceId, offset: u64, limit: u16) -> Vec<FlatSocialAccount<AccountId, BlockNumber>>;
     }
 }
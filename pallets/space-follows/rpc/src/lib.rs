@@ -6,11 +6,12 @@ use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
 
+use pallet_profiles::rpc::FlatSocialAccount;
 use pallet_utils::{SpaceId, rpc::map_rpc_error};
 pub use space_follows_runtime_api::SpaceFollowsApi as SpaceFollowsRuntimeApi;
 
 #[rpc]
-pub trait SpaceFollowsApi<BlockHash, AccountId> {
+pub trait SpaceFollowsApi<BlockHash, AccountId, BlockNumber> {
     #[rpc(name = "spaceFollows_getSpaceIdsFollowedByAccount")]
     fn get_space_ids_followed_by_account(
         &self,
@@ -25,6 +26,15 @@ pub trait SpaceFollowsApi<BlockHash, AccountId> {
         account: AccountId,
         space_ids: Vec<SpaceId>,
     ) -> Result<Vec<SpaceId>>;
+
+    #[rpc(name = "spaceFollows_getSpaceFollowers")]
+    fn get_space_followers(
+        &self,
+        at: Option<BlockHash>,
+        space_id: SpaceId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatSocialAccount<AccountId, BlockNumber>>>;
 }
 
 pub struct SpaceFollows<C, M> {
@@ -41,13 +51,14 @@ impl<C, M> SpaceFollows<C, M> {
     }
 }
 
-impl<C, Block, AccountId> SpaceFollowsApi<<Block as BlockT>::Hash, AccountId>
+impl<C, Block, AccountId, BlockNumber> SpaceFollowsApi<<Block as BlockT>::Hash, AccountId, BlockNumber>
     for SpaceFollows<C, Block>
 where
     Block: BlockT,
     AccountId: Codec,
+    BlockNumber: Codec,
     C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: SpaceFollowsRuntimeApi<Block, AccountId>,
+    C::Api: SpaceFollowsRuntimeApi<Block, AccountId, BlockNumber>,
 {
     fn get_space_ids_followed_by_account(
         &self,
@@ -73,4 +84,18 @@ where
         let runtime_api_result = api.filter_followed_space_ids(&at, account, space_ids);
         runtime_api_result.map_err(map_rpc_error)
     }
+
+    fn get_space_followers(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        space_id: SpaceId,
+        offset: u64,
+        limit: u16,
+    ) -> Result<Vec<FlatSocialAccount<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let runtime_api_result = api.get_space_followers(&at, space_id, offset, limit);
+        runtime_api_result.map_err(map_rpc_error)
+    }
 }
@@ -3,19 +3,21 @@
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::DispatchResult,
+    storage::IterableStorageDoubleMap,
     traits::Get
 };
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::{
-    SpaceFollowsProvider,
+    SpaceFollowsProvider, SpaceCleanupHandler,
     moderation::IsAccountBlocked,
 };
 use pallet_profiles::{Module as Profiles, SocialAccountById};
 use pallet_spaces::{BeforeSpaceCreated, Module as Spaces, Space, SpaceById};
 use pallet_utils::{Error as UtilsError, SpaceId, remove_from_vec};
 
+pub mod migrations;
 pub mod rpc;
 
 /// The pallet's configuration trait.
@@ -30,6 +32,9 @@ pub trait Config: system::Config
     type BeforeSpaceFollowed: BeforeSpaceFollowed<Self>;
 
     type BeforeSpaceUnfollowed: BeforeSpaceUnfollowed<Self>;
+
+    /// The max number of spaces a single account can follow, including the spaces it created.
+    type MaxFollowedSpaces: Get<u32>;
 }
 
 decl_error! {
@@ -42,20 +47,35 @@ decl_error! {
         NotSpaceFollower,
         /// Not allowed to follow a hidden space.
         CannotFollowHiddenSpace,
+        /// Account has reached the max number of spaces it can follow.
+        TooManyFollowedSpaces,
     }
 }
 
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Config> as SpaceFollowsModule {
+        /// Followers of a given space, one per key, so that a follow does not
+        /// read and write the whole list of followers of a popular space.
         pub SpaceFollowers get(fn space_followers):
-            map hasher(twox_64_concat) SpaceId => Vec<T::AccountId>;
+            double_map hasher(twox_64_concat) SpaceId, hasher(blake2_128_concat) T::AccountId => bool;
 
         pub SpaceFollowedByAccount get(fn space_followed_by_account):
             map hasher(blake2_128_concat) (T::AccountId, SpaceId) => bool;
 
+        /// Spaces followed by a given account, in the order they were followed.
+        /// The list is read and written as a whole, so its size is limited by `MaxFollowedSpaces`.
         pub SpacesFollowedByAccount get(fn spaces_followed_by_account):
             map hasher(blake2_128_concat) T::AccountId => Vec<SpaceId>;
+
+        /// True if `SpaceFollowers` storage is already migrated from a vector of followers per space.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub SpaceFollowersMigrated: bool = false;
+    }
+    add_extra_genesis {
+      build(|_| {
+        SpaceFollowersMigrated::put(true);
+      })
     }
 }
 
@@ -71,13 +91,25 @@ decl_event!(
 // The pallet's dispatchable functions.
 decl_module! {
   pub struct Module<T: Config> for enum Call where origin: T::Origin {
+    const MaxFollowedSpaces: u32 = T::MaxFollowedSpaces::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 5)]
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+      let mut final_weight = 0;
+
+      if !SpaceFollowersMigrated::get() {
+        final_weight = migrations::split_space_followers::<T>();
+      }
+
+      final_weight
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 5)]
     pub fn follow_space(origin, space_id: SpaceId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
@@ -94,7 +126,7 @@ decl_module! {
       Ok(())
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 5)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 5)]
     pub fn unfollow_space(origin, space_id: SpaceId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
@@ -107,6 +139,12 @@ decl_module! {
 
 impl<T: Config> Module<T> {
     fn add_space_follower(follower: T::AccountId, space: &mut Space<T>) -> DispatchResult {
+        let mut followed_space_ids = Self::spaces_followed_by_account(&follower);
+        ensure!(
+            followed_space_ids.len() < T::MaxFollowedSpaces::get() as usize,
+            Error::<T>::TooManyFollowedSpaces
+        );
+
         space.inc_followers();
 
         let mut social_account = Profiles::get_or_new_social_account(follower.clone());
@@ -116,9 +154,10 @@ impl<T: Config> Module<T> {
             follower.clone(), social_account.reputation, space)?;
 
         let space_id = space.id;
-        <SpaceFollowers<T>>::mutate(space_id, |followers| followers.push(follower.clone()));
+        <SpaceFollowers<T>>::insert(space_id, follower.clone(), true);
         <SpaceFollowedByAccount<T>>::insert((follower.clone(), space_id), true);
-        <SpacesFollowedByAccount<T>>::mutate(follower.clone(), |space_ids| space_ids.push(space_id));
+        followed_space_ids.push(space_id);
+        <SpacesFollowedByAccount<T>>::insert(follower.clone(), followed_space_ids);
         <SocialAccountById<T>>::insert(follower.clone(), social_account);
 
         Self::deposit_event(RawEvent::SpaceFollowed(follower, space_id));
@@ -136,7 +175,7 @@ impl<T: Config> Module<T> {
         T::BeforeSpaceUnfollowed::before_space_unfollowed(follower.clone(), space)?;
 
         <SpacesFollowedByAccount<T>>::mutate(follower.clone(), |space_ids| remove_from_vec(space_ids, space_id));
-        <SpaceFollowers<T>>::mutate(space_id, |account_ids| remove_from_vec(account_ids, follower.clone()));
+        <SpaceFollowers<T>>::remove(space_id, follower.clone());
         <SpaceFollowedByAccount<T>>::remove((follower.clone(), space_id));
         <SocialAccountById<T>>::insert(follower.clone(), social_account);
         <SpaceById<T>>::insert(space_id, space);
@@ -154,9 +193,33 @@ impl<T: Config> SpaceFollowsProvider for Module<T> {
     }
 }
 
+impl<T: Config> SpaceCleanupHandler for Module<T> {
+    /// Unfollow a deleted space by all its followers.
+    fn cleanup_deleted_space(space_id: SpaceId, limit: u32) -> u32 {
+        // Keys are collected first, because the storage should not be changed while iterating over it.
+        let followers: Vec<T::AccountId> = <SpaceFollowers<T>>::iter_key_prefix(space_id)
+            .take(limit as usize)
+            .collect();
+
+        for follower in followers.iter() {
+            <SpaceFollowers<T>>::remove(space_id, follower);
+            <SpacesFollowedByAccount<T>>::mutate(follower, |space_ids| remove_from_vec(space_ids, space_id));
+            <SpaceFollowedByAccount<T>>::remove((follower.clone(), space_id));
+            <SocialAccountById<T>>::mutate(follower, |social_account_opt| {
+                if let Some(social_account) = social_account_opt {
+                    social_account.dec_following_spaces();
+                }
+            });
+        }
+
+        followers.len() as u32
+    }
+}
+
 impl<T: Config> BeforeSpaceCreated<T> for Module<T> {
     fn before_space_created(creator: T::AccountId, space: &mut Space<T>) -> DispatchResult {
-        // Make a space creator the first follower of this space:
+        // Make a space creator the first follower of this space.
+        // A space cannot be created if its creator already follows `MaxFollowedSpaces` spaces.
         Module::<T>::add_space_follower(creator, space)
     }
 }
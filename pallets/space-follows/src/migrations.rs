@@ -0,0 +1,31 @@
+use frame_support::{
+    Twox64Concat,
+    storage::{StoragePrefixedMap, migration::{remove_storage_prefix, storage_key_iter}},
+};
+use pallet_utils::SpaceId;
+
+use super::*;
+
+/// Move the followers of every space from a single vector to separate `SpaceFollowers` keys.
+pub fn split_space_followers<T: Config>() -> frame_support::weights::Weight {
+    let module = SpaceFollowers::<T>::module_prefix();
+    let item = SpaceFollowers::<T>::storage_prefix();
+
+    // Before this migration `SpaceFollowers` was a map of a space id to a vector of its followers.
+    let old_followers: Vec<(SpaceId, Vec<T::AccountId>)> =
+        storage_key_iter::<SpaceId, Vec<T::AccountId>, Twox64Concat>(module, item).collect();
+
+    remove_storage_prefix(module, item, &[]);
+
+    let mut followers_migrated = 0;
+    for (space_id, followers) in old_followers.iter() {
+        for follower in followers {
+            SpaceFollowers::<T>::insert(space_id, follower, true);
+            followers_migrated += 1;
+        }
+    }
+
+    SpaceFollowersMigrated::put(true);
+
+    T::DbWeight::get().reads_writes(old_followers.len() as u64, followers_migrated + 2)
+}
@@ -1,8 +1,10 @@
+use frame_support::storage::IterableStorageDoubleMap;
 use sp_std::prelude::*;
 
+use pallet_profiles::{Module as Profiles, rpc::FlatSocialAccount};
 use pallet_utils::SpaceId;
 
-use crate::{Module, Config};
+use crate::{Module, Config, SpaceFollowers};
 
 impl<T: Config> Module<T> {
     pub fn get_space_ids_followed_by_account(account: T::AccountId) -> Vec<SpaceId> {
@@ -14,4 +16,21 @@ impl<T: Config> Module<T> {
             .filter(|space_id| Self::space_followed_by_account((&account, space_id)))
             .cloned().collect()
     }
-}
\ No newline at end of file
+
+    /// Get the social accounts of the followers of a given space.
+    /// Followers are not sorted by the time they followed the space, but their order is stable
+    /// as long as nobody follows or unfollows the space between requests.
+    /// Followers before `offset` are iterated over too, so the cost of a page grows with its offset.
+    pub fn get_space_followers(
+        space_id: SpaceId,
+        offset: u64,
+        limit: u16,
+    ) -> Vec<FlatSocialAccount<T::AccountId, T::BlockNumber>> {
+        let followers = SpaceFollowers::<T>::iter_key_prefix(space_id)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+
+        Profiles::<T>::get_social_accounts_by_ids(followers)
+    }
+}
@@ -10,6 +10,8 @@ use frame_system::{self as system};
 use pallet_utils::{SpaceId, WhoAndWhen};
 use pallet_spaces::{Space, SpaceUpdate, AfterSpaceUpdated};
 
+pub mod migrations;
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct SpaceHistoryRecord<T: Config> {
@@ -28,12 +30,33 @@ decl_storage! {
     trait Store for Module<T: Config> as SpaceHistoryModule {
         pub EditHistory get(fn edit_history):
             map hasher(twox_64_concat) SpaceId => Vec<SpaceHistoryRecord<T>>;
+
+        /// True if the space permissions in `EditHistory` storage are already migrated
+        /// to `SpacePermissions` with the `reputation_thresholds` field.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub EditHistoryReputationThresholdsMigrated: bool = false;
+    }
+    add_extra_genesis {
+      build(|_| {
+        EditHistoryReputationThresholdsMigrated::put(true);
+      })
     }
 }
 
 // The pallet's dispatchable functions.
 decl_module! {
-  pub struct Module<T: Config> for enum Call where origin: T::Origin {}
+  pub struct Module<T: Config> for enum Call where origin: T::Origin {
+
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+      let mut final_weight = 0;
+
+      if !EditHistoryReputationThresholdsMigrated::get() {
+        final_weight = migrations::add_reputation_thresholds_to_edit_history::<T>();
+      }
+
+      final_weight
+    }
+  }
 }
 
 impl<T: Config> SpaceHistoryRecord<T> {
@@ -0,0 +1,51 @@
+use frame_support::{storage::IterableStorageMap, traits::Get};
+use pallet_permissions::migrations::SpacePermissionsWithoutReputation;
+use pallet_utils::Content;
+
+use super::*;
+
+/// A `SpaceUpdate` struct as it was stored in the edit history
+/// before `SpacePermissions` got the `reputation_thresholds` field.
+#[derive(Decode)]
+struct SpaceUpdateWithoutReputationThresholds {
+    parent_id: Option<Option<SpaceId>>,
+    handle: Option<Option<Vec<u8>>>,
+    content: Option<Content>,
+    hidden: Option<bool>,
+    permissions: Option<Option<SpacePermissionsWithoutReputation>>,
+}
+
+#[derive(Decode)]
+struct SpaceHistoryRecordWithoutReputationThresholds<T: Config> {
+    edited: WhoAndWhen<T>,
+    old_data: SpaceUpdateWithoutReputationThresholds,
+}
+
+pub fn add_reputation_thresholds_to_edit_history<T: Config>() -> frame_support::weights::Weight {
+    let mut spaces_migrated = 0;
+
+    EditHistory::<T>::translate::<Vec<SpaceHistoryRecordWithoutReputationThresholds<T>>, _>(|_, old_records| {
+        spaces_migrated += 1;
+
+        Some(
+            old_records
+                .into_iter()
+                .map(|old_record| SpaceHistoryRecord {
+                    edited: old_record.edited,
+                    old_data: SpaceUpdate {
+                        parent_id: old_record.old_data.parent_id,
+                        handle: old_record.old_data.handle,
+                        content: old_record.old_data.content,
+                        hidden: old_record.old_data.hidden,
+                        permissions: old_record.old_data.permissions
+                            .map(|perms_opt| perms_opt.map(Into::into)),
+                    },
+                })
+                .collect()
+        )
+    });
+
+    EditHistoryReputationThresholdsMigrated::put(true);
+
+    T::DbWeight::get().reads_writes(spaces_migrated + 1, spaces_migrated + 1)
+}
@@ -2,6 +2,8 @@
 //!
 //! Spaces are the primary components of Subsocial. This module allows you to create a Space
 //! and customize it by updating its' owner(s), content, unique handle, and permissions.
+//! A deleted space is cleaned up (posts, roles, follows) in batches over the next blocks,
+//! a space that has subspaces cannot be deleted.
 //!
 //! To understand how Spaces fit into the Subsocial ecosystem, you can think of how
 //! folders and files work in a file system. Spaces are similar to folders, that can contain Posts,
@@ -26,6 +28,7 @@ use scale_info::TypeInfo;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+    storage::IterableStorageMap,
     traits::{Get, Currency, ExistenceRequirement, ReservableCurrency},
     weights::Pays,
 };
@@ -34,11 +37,11 @@ use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed, ensure_root};
 
 use df_traits::{
-    SpaceForRoles, SpaceForRolesProvider, PermissionChecker, SpaceFollowsProvider,
-    moderation::{IsAccountBlocked, IsContentBlocked},
+    SpaceForRoles, SpaceForRolesProvider, PermissionChecker, SpaceFollowsProvider, SpaceCleanupHandler,
+    IsHandleReserved, moderation::{IsAccountBlocked, IsContentBlocked},
 };
 use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissions, SpacePermissionsContext};
-use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, WhoAndWhen, Content};
+use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, WhoAndWhen, Content, remove_from_vec};
 
 pub mod rpc;
 pub mod migrations;
@@ -136,13 +139,22 @@ pub trait Config: system::Config
     type IsContentBlocked: IsContentBlocked;
 
     type HandleDeposit: Get<BalanceOf<Self>>;
+
+    /// Used to check that a space handle is not reserved by a profile.
+    type ProfileHandles: IsHandleReserved;
+
+    /// Removes posts, roles, follows, etc. related to a deleted space.
+    type SpaceCleanupHandler: SpaceCleanupHandler;
+
+    /// The max number of items that can be cleaned up per block after spaces were deleted.
+    type MaxSpaceCleanupItemsPerBlock: Get<u32>;
 }
 
 decl_error! {
   pub enum Error for Module<T: Config> {
     /// Space was not found by id.
     SpaceNotFound,
-    /// Space handle is not unique.
+    /// Space handle is not unique, it is already reserved by another space or profile.
     SpaceHandleIsNotUnique,
     /// Handles are disabled in `PalletSettings`.
     HandlesAreDisabled,
@@ -158,6 +170,16 @@ decl_error! {
     SpaceIsAtRoot,
     /// New spaces' settings don't differ from the old ones.
     NoUpdatesForSpacesSettings,
+    /// A subspace owner is not allowed to delete their own subspaces in the parent space.
+    NoPermissionToDeleteOwnSubspaces,
+    /// User has no permission to delete any subspaces in the parent space.
+    NoPermissionToDeleteAnySubspace,
+    /// User has no permission to override permissions of subspaces in the parent space.
+    NoPermissionToOverrideSubspacePermissions,
+    /// A space cannot be moved into itself or into one of its subspaces.
+    CannotMoveSpaceIntoItsSubspace,
+    /// A space that has subspaces cannot be deleted.
+    SpaceHasSubspaces,
   }
 }
 
@@ -193,14 +215,34 @@ decl_storage! {
 
         pub PalletSettings get(fn settings): SpacesSettings;
 
+        /// The number of subspaces of a given space.
+        pub SubspacesCount get(fn subspaces_count):
+            map hasher(twox_64_concat) SpaceId => u32;
+
+        /// Whether a space is deleted. A deleted space is kept in `SpaceById`
+        /// until its related data (posts, roles, follows) is cleaned up.
+        pub DeletedSpaceIds get(fn is_space_deleted):
+            map hasher(twox_64_concat) SpaceId => bool;
+
         /// True if `SpaceIdByHandle` storage is already fixed.
         // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
         pub SpaceIdByHandleStorageFixed: bool = false;
+
+        /// True if the `permissions` of spaces in `SpaceById` storage are already migrated
+        /// to `SpacePermissions` with the `reputation_thresholds` field.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub SpaceReputationThresholdsMigrated: bool = false;
+
+        /// True if the subspaces of existing spaces are already counted in `SubspacesCount`.
+        // TODO delete this storage and corresponding migration, after the migration executed and the storage value is `true`.
+        pub SubspacesCounted: bool = false;
     }
     add_extra_genesis {
       config(endowed_account): T::AccountId;
       build(|_: &Self| {
         SpaceIdByHandleStorageFixed::put(true);
+        SpaceReputationThresholdsMigrated::put(true);
+        SubspacesCounted::put(true);
       })
     }
 }
@@ -212,6 +254,8 @@ decl_event!(
         SpaceCreated(AccountId, SpaceId),
         SpaceUpdated(AccountId, SpaceId),
         SpaceDeleted(AccountId, SpaceId),
+        /// All the data related to a deleted space has been cleaned up.
+        SpaceCleanedUp(SpaceId),
     }
 );
 
@@ -221,6 +265,8 @@ decl_module! {
 
     const HandleDeposit: BalanceOf<T> = T::HandleDeposit::get();
 
+    const MaxSpaceCleanupItemsPerBlock: u32 = T::MaxSpaceCleanupItemsPerBlock::get();
+
     // Initializing errors
     type Error = Error<T>;
 
@@ -234,9 +280,23 @@ decl_module! {
         final_weight = migrations::fix_corrupted_handles_storage::<T>();
       }
 
+      if !SpaceReputationThresholdsMigrated::get() {
+        final_weight = final_weight.saturating_add(
+          migrations::add_reputation_thresholds_to_space_permissions::<T>()
+        );
+      }
+
+      if !SubspacesCounted::get() {
+        final_weight = final_weight.saturating_add(migrations::count_subspaces::<T>());
+      }
+
       final_weight
     }
 
+    fn on_initialize(_n: T::BlockNumber) -> frame_support::weights::Weight {
+      Self::cleanup_deleted_spaces()
+    }
+
     #[weight = 500_000 + T::DbWeight::get().reads_writes(5, 4)]
     pub fn create_space(
       origin,
@@ -266,6 +326,15 @@ decl_module! {
           SpacePermission::CreateSubspaces,
           Error::<T>::NoPermissionToCreateSubspaces.into()
         )?;
+
+        if permissions_opt.is_some() {
+          Self::ensure_account_has_space_permission(
+            owner.clone(),
+            &parent_space,
+            SpacePermission::OverrideSubspacePermissions,
+            Error::<T>::NoPermissionToOverrideSubspacePermissions.into()
+          )?;
+        }
       }
 
       let permissions = permissions_opt.map(|perms| {
@@ -284,6 +353,9 @@ decl_module! {
 
       <SpaceById<T>>::insert(space_id, new_space);
       <SpaceIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(space_id));
+      if let Some(parent_id) = parent_id_opt {
+        Self::inc_subspaces_count(parent_id);
+      }
       NextSpaceId::mutate(|n| { *n += 1; });
 
       Self::deposit_event(RawEvent::SpaceCreated(owner, space_id));
@@ -307,12 +379,7 @@ decl_module! {
 
       ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
 
-      Self::ensure_account_has_space_permission(
-        owner.clone(),
-        &space,
-        SpacePermission::UpdateSpace,
-        Error::<T>::NoPermissionToUpdateSpace.into()
-      )?;
+      Self::ensure_account_can_update_space(&owner, &space, &update)?;
 
       let mut is_update_applied = false;
       let mut old_data = SpaceUpdate::default();
@@ -323,6 +390,7 @@ decl_module! {
 
           if let Some(parent_id) = parent_id_opt {
             let parent_space = Self::require_space(parent_id)?;
+            Self::ensure_space_is_not_ancestor_of(space_id, &parent_space)?;
 
             Self::ensure_account_has_space_permission(
               owner.clone(),
@@ -363,6 +431,15 @@ decl_module! {
 
       if let Some(overrides_opt) = update.permissions {
         if space.permissions != overrides_opt {
+          if let Some(parent_space) = space.parent_id.and_then(Self::space_by_id) {
+            Self::ensure_account_has_space_permission(
+              owner.clone(),
+              &parent_space,
+              SpacePermission::OverrideSubspacePermissions,
+              Error::<T>::NoPermissionToOverrideSubspacePermissions.into()
+            )?;
+          }
+
           old_data.permissions = Some(space.permissions);
 
           if let Some(overrides) = overrides_opt.clone() {
@@ -386,6 +463,15 @@ decl_module! {
       if is_update_applied {
         space.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
 
+        if let Some(old_parent_id_opt) = old_data.parent_id {
+          if let Some(old_parent_id) = old_parent_id_opt {
+            Self::dec_subspaces_count(old_parent_id);
+          }
+          if let Some(parent_id) = space.parent_id {
+            Self::inc_subspaces_count(parent_id);
+          }
+        }
+
         <SpaceById<T>>::insert(space_id, space.clone());
         T::AfterSpaceUpdated::after_space_updated(owner.clone(), &space, old_data);
 
@@ -394,6 +480,32 @@ decl_module! {
       Ok(())
     }
 
+    /// Delete a space that has no subspaces. Its handle and handle deposit are released right away,
+    /// while its posts are detached and its roles and follows are removed in batches over the next blocks.
+    /// The space is marked as deleted and kept in the storage until its cleanup is finished.
+    #[weight = 500_000 + T::DbWeight::get().reads_writes(6, 5)]
+    pub fn delete_space(origin, space_id: SpaceId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let space = Self::require_space(space_id)?;
+
+      Self::ensure_account_can_delete_space(&who, &space)?;
+      ensure!(Self::subspaces_count(space_id) == 0, Error::<T>::SpaceHasSubspaces);
+
+      if let Some(handle) = space.handle.clone() {
+        space.unreserve_handle(handle)?;
+      }
+
+      <SpaceIdsByOwner<T>>::mutate(&space.owner, |space_ids| remove_from_vec(space_ids, space_id));
+      if let Some(parent_id) = space.parent_id {
+        Self::dec_subspaces_count(parent_id);
+      }
+      DeletedSpaceIds::insert(space_id, true);
+
+      Self::deposit_event(RawEvent::SpaceDeleted(who, space_id));
+      Ok(())
+    }
+
     #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
     pub fn update_settings(origin, new_settings: SpacesSettings) -> DispatchResult {
       ensure_root(origin)?;
@@ -491,6 +603,10 @@ impl<T: Config> Space<T> {
         self.followers_count = self.followers_count.saturating_sub(1);
     }
 
+    pub fn change_score(&mut self, diff: i16) {
+        self.score = self.score.saturating_add(diff.into());
+    }
+
     pub fn try_get_parent(&self) -> Result<SpaceId, DispatchError> {
         self.parent_id.ok_or_else(|| Error::<T>::SpaceIsAtRoot.into())
     }
@@ -526,15 +642,20 @@ impl<T: Config> Space<T> {
 
 impl<T: Config> Module<T> {
 
-    /// Check that there is a `Space` with such `space_id` in the storage
+    /// Check that there is a `Space` with such `space_id` in the storage and it's not deleted
     /// or return`SpaceNotFound` error.
     pub fn ensure_space_exists(space_id: SpaceId) -> DispatchResult {
-        ensure!(<SpaceById<T>>::contains_key(space_id), Error::<T>::SpaceNotFound);
+        ensure!(
+            <SpaceById<T>>::contains_key(space_id) && !Self::is_space_deleted(space_id),
+            Error::<T>::SpaceNotFound
+        );
         Ok(())
     }
 
     /// Get `Space` by id from the storage or return `SpaceNotFound` error.
+    /// A deleted space is treated as not found, even if it's not cleaned up yet.
     pub fn require_space(space_id: SpaceId) -> Result<Space<T>, DispatchError> {
+        ensure!(!Self::is_space_deleted(space_id), Error::<T>::SpaceNotFound);
         Ok(Self::space_by_id(space_id).ok_or(Error::<T>::SpaceNotFound)?)
     }
 
@@ -543,6 +664,18 @@ impl<T: Config> Module<T> {
         space: &Space<T>,
         permission: SpacePermission,
         error: DispatchError,
+    ) -> DispatchResult {
+        Self::ensure_account_has_space_permission_with_post_perms(account, space, None, permission, error)
+    }
+
+    /// Check a permission in a space, applying the permission overrides of a post
+    /// (if any) on top of the space permissions.
+    pub fn ensure_account_has_space_permission_with_post_perms(
+        account: T::AccountId,
+        space: &Space<T>,
+        post_perms: Option<SpacePermissions>,
+        permission: SpacePermission,
+        error: DispatchError,
     ) -> DispatchResult {
         let is_owner = space.is_owner(&account);
         let is_follower = space.is_follower(&account);
@@ -552,6 +685,7 @@ impl<T: Config> Module<T> {
             is_space_owner: is_owner,
             is_space_follower: is_follower,
             space_perms: space.permissions.clone(),
+            post_perms,
         };
 
         T::Roles::ensure_account_has_space_permission(
@@ -562,6 +696,129 @@ impl<T: Config> Module<T> {
         )
     }
 
+    /// A space can be updated by an account that has `UpdateSpace` permission in this space
+    /// or `UpdateAnySubspace` permission in its parent space. If the update only changes
+    /// the visibility of a subspace, `HideAnySubspace` permission in the parent space is enough.
+    fn ensure_account_can_update_space(
+        who: &T::AccountId,
+        space: &Space<T>,
+        update: &SpaceUpdate,
+    ) -> DispatchResult {
+        let update_space_result = Self::ensure_account_has_space_permission(
+            who.clone(),
+            space,
+            SpacePermission::UpdateSpace,
+            Error::<T>::NoPermissionToUpdateSpace.into()
+        );
+
+        let parent_space = match space.parent_id.and_then(Self::space_by_id) {
+            Some(parent_space) if update_space_result.is_err() => parent_space,
+            _ => return update_space_result,
+        };
+
+        let is_visibility_update_only =
+            update.hidden.is_some() &&
+            update.parent_id.is_none() &&
+            update.handle.is_none() &&
+            update.content.is_none() &&
+            update.permissions.is_none();
+
+        if is_visibility_update_only {
+            let can_hide_subspace = Self::ensure_account_has_space_permission(
+                who.clone(),
+                &parent_space,
+                SpacePermission::HideAnySubspace,
+                Error::<T>::NoPermissionToUpdateSpace.into()
+            );
+
+            if can_hide_subspace.is_ok() {
+                return Ok(());
+            }
+        }
+
+        Self::ensure_account_has_space_permission(
+            who.clone(),
+            &parent_space,
+            SpacePermission::UpdateAnySubspace,
+            Error::<T>::NoPermissionToUpdateSpace.into()
+        )
+    }
+
+    /// Ensure that a space with `space_id` is neither `new_parent` itself nor any of its ancestors,
+    /// so that the spaces hierarchy never has cycles.
+    fn ensure_space_is_not_ancestor_of(space_id: SpaceId, new_parent: &Space<T>) -> DispatchResult {
+        let mut ancestor_id_opt = Some(new_parent.id);
+
+        while let Some(ancestor_id) = ancestor_id_opt {
+            ensure!(ancestor_id != space_id, Error::<T>::CannotMoveSpaceIntoItsSubspace);
+            ancestor_id_opt = Self::space_by_id(ancestor_id).and_then(|space| space.parent_id);
+        }
+
+        Ok(())
+    }
+
+    fn ensure_account_can_delete_space(who: &T::AccountId, space: &Space<T>) -> DispatchResult {
+        let parent_space_opt = space.parent_id.and_then(Self::space_by_id);
+
+        if let Some(parent_space) = parent_space_opt {
+            let (permission_to_check, permission_error) = if space.is_owner(who) {
+                (SpacePermission::DeleteOwnSubspaces, Error::<T>::NoPermissionToDeleteOwnSubspaces)
+            } else {
+                (SpacePermission::DeleteAnySubspace, Error::<T>::NoPermissionToDeleteAnySubspace)
+            };
+
+            Self::ensure_account_has_space_permission(
+                who.clone(),
+                &parent_space,
+                permission_to_check,
+                permission_error.into()
+            )
+        } else {
+            space.ensure_space_owner(who.clone())
+        }
+    }
+
+    /// Clean up the data related to deleted spaces, processing at most
+    /// `MaxSpaceCleanupItemsPerBlock` items per call. Returns the consumed weight.
+    /// A deleted space is removed from `SpaceById` once all its data is cleaned up.
+    pub(crate) fn cleanup_deleted_spaces() -> frame_support::weights::Weight {
+        let limit = T::MaxSpaceCleanupItemsPerBlock::get();
+
+        // Visiting a space counts as one item too, so no more than `limit` spaces are read.
+        let space_ids: Vec<SpaceId> = DeletedSpaceIds::iter_keys().take(limit as usize).collect();
+        if space_ids.is_empty() {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut processed: u32 = 0;
+
+        for space_id in space_ids {
+            if processed >= limit {
+                break;
+            }
+
+            let budget = limit - processed;
+            let removed = T::SpaceCleanupHandler::cleanup_deleted_space(space_id, budget);
+
+            processed = processed.saturating_add(removed).saturating_add(1);
+
+            if removed >= budget {
+                // There is still something left to clean up in this space.
+                break;
+            }
+
+            DeletedSpaceIds::remove(space_id);
+            SubspacesCount::remove(space_id);
+            <SpaceById<T>>::remove(space_id);
+            Self::deposit_event(RawEvent::SpaceCleanedUp(space_id));
+        }
+
+        T::DbWeight::get().reads_writes(
+            1 + 2 * processed as u64,
+            3 * processed as u64,
+        )
+    }
+
     pub fn ensure_handles_enabled() -> DispatchResult {
         ensure!(Self::settings().handles_enabled, Error::<T>::HandlesAreDisabled);
         Ok(())
@@ -569,12 +826,22 @@ impl<T: Config> Module<T> {
 
     pub fn try_move_space_to_root(space_id: SpaceId) -> DispatchResult {
         let mut space = Self::require_space(space_id)?;
-        space.parent_id = None;
+        if let Some(parent_id) = space.parent_id.take() {
+            Self::dec_subspaces_count(parent_id);
+        }
 
         SpaceById::<T>::insert(space_id, space);
         Ok(())
     }
 
+    fn inc_subspaces_count(space_id: SpaceId) {
+        SubspacesCount::mutate(space_id, |count| *count = count.saturating_add(1));
+    }
+
+    fn dec_subspaces_count(space_id: SpaceId) {
+        SubspacesCount::mutate(space_id, |count| *count = count.saturating_sub(1));
+    }
+
     pub fn mutate_space_by_id<F: FnOnce(&mut Space<T>)> (
         space_id: SpaceId,
         f: F
@@ -591,12 +858,13 @@ impl<T: Config> Module<T> {
         })
     }
 
-    /// Lowercase a handle and ensure that it's unique, i.e. no space reserved this handle yet.
+    /// Lowercase a handle and ensure that it's unique, i.e. no space or profile reserved this handle yet.
     fn lowercase_and_ensure_unique_handle(handle: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
         let handle_in_lowercase = Utils::<T>::lowercase_and_validate_a_handle(handle)?;
 
-        // Check if a handle is unique across all spaces' handles:
+        // Check if a handle is unique across all spaces' and profiles' handles:
         ensure!(Self::space_id_by_handle(handle_in_lowercase.clone()).is_none(), Error::<T>::SpaceHandleIsNotUnique);
+        ensure!(!T::ProfileHandles::is_handle_reserved(handle_in_lowercase.clone()), Error::<T>::SpaceHandleIsNotUnique);
 
         Ok(handle_in_lowercase)
     }
@@ -678,11 +946,18 @@ impl<T: Config> SpaceForRolesProvider for Module<T> {
 
         Ok(SpaceForRoles {
             owner: space.owner,
+            parent_id: space.parent_id,
             permissions: space.permissions,
         })
     }
 }
 
+impl<T: Config> IsHandleReserved for Module<T> {
+    fn is_handle_reserved(handle_in_lowercase: Vec<u8>) -> bool {
+        Self::space_id_by_handle(handle_in_lowercase).is_some()
+    }
+}
+
 pub trait BeforeSpaceCreated<T: Config> {
     fn before_space_created(follower: T::AccountId, space: &mut Space<T>) -> DispatchResult;
 }
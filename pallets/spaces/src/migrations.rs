@@ -1,6 +1,7 @@
 use frame_support::storage::IterableStorageMap;
 use sp_runtime::traits::Saturating;
 
+use pallet_permissions::migrations::SpacePermissionsWithoutReputation;
 use pallet_utils as Utils;
 
 use super::*;
@@ -37,3 +38,70 @@ pub fn fix_corrupted_handles_storage<T: Config>() -> frame_support::weights::Wei
         removed + 1,
     )
 }
+
+/// A `Space` struct as it was stored before `SpacePermissions` got the `reputation_thresholds` field.
+#[derive(Decode)]
+struct SpaceWithoutReputationThresholds<T: Config> {
+    id: SpaceId,
+    created: WhoAndWhen<T>,
+    updated: Option<WhoAndWhen<T>>,
+    owner: T::AccountId,
+    parent_id: Option<SpaceId>,
+    handle: Option<Vec<u8>>,
+    content: Content,
+    hidden: bool,
+    posts_count: u32,
+    hidden_posts_count: u32,
+    followers_count: u32,
+    score: i32,
+    permissions: Option<SpacePermissionsWithoutReputation>,
+}
+
+pub fn add_reputation_thresholds_to_space_permissions<T: Config>() -> frame_support::weights::Weight {
+    let mut spaces_migrated = 0;
+
+    SpaceById::<T>::translate::<SpaceWithoutReputationThresholds<T>, _>(|_, old_space| {
+        spaces_migrated += 1;
+
+        Some(Space {
+            id: old_space.id,
+            created: old_space.created,
+            updated: old_space.updated,
+            owner: old_space.owner,
+            parent_id: old_space.parent_id,
+            handle: old_space.handle,
+            content: old_space.content,
+            hidden: old_space.hidden,
+            posts_count: old_space.posts_count,
+            hidden_posts_count: old_space.hidden_posts_count,
+            followers_count: old_space.followers_count,
+            score: old_space.score,
+            permissions: old_space.permissions.map(Into::into),
+        })
+    });
+
+    SpaceReputationThresholdsMigrated::put(true);
+
+    T::DbWeight::get().reads_writes(spaces_migrated + 1, spaces_migrated + 1)
+}
+
+pub fn count_subspaces<T: Config>() -> frame_support::weights::Weight {
+    let mut spaces_iterated = 0;
+    let mut parents_updated = 0;
+
+    for space in SpaceById::<T>::iter_values() {
+        spaces_iterated += 1;
+
+        if let Some(parent_id) = space.parent_id {
+            Module::<T>::inc_subspaces_count(parent_id);
+            parents_updated += 1;
+        }
+    }
+
+    SubspacesCounted::put(true);
+
+    T::DbWeight::get().reads_writes(
+        spaces_iterated + parents_updated,
+        parents_updated + 1,
+    )
+}
@@ -3,6 +3,8 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 use sp_std::prelude::*;
 
+#[cfg(feature = "std")]
+use pallet_utils::rpc::bytes_to_string;
 use pallet_utils::{bool_to_option, SpaceId, rpc::{FlatContent, FlatWhoAndWhen, ShouldSkip}};
 
 use crate::{Module, Space, Config, FIRST_SPACE_ID};
@@ -36,15 +38,6 @@ pub struct FlatSpace<AccountId, BlockNumber> {
     pub followers_count: u32,
 }
 
-#[cfg(feature = "std")]
-fn bytes_to_string<S>(field: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
-    let field_unwrapped = field.clone().unwrap_or_default();
-    // If Bytes slice is invalid, then empty string will be returned
-    serializer.serialize_str(
-        std::str::from_utf8(&field_unwrapped).unwrap_or_default()
-    )
-}
-
 impl<T: Config> From<Space<T>> for FlatSpace<T::AccountId, T::BlockNumber> {
     fn from(from: Space<T>) -> Self {
         let Space {
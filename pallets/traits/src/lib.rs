@@ -1,6 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_std::vec::Vec;
 
 use pallet_permissions::{
   SpacePermission,
@@ -14,6 +15,7 @@ pub mod moderation;
 /// Minimal set of fields from Space struct that are required by roles pallet.
 pub struct SpaceForRoles<AccountId> {
   pub owner: AccountId,
+  pub parent_id: Option<SpaceId>,
   pub permissions: Option<SpacePermissions>,
 }
 
@@ -29,6 +31,24 @@ pub trait SpaceFollowsProvider {
   fn is_space_follower(account: Self::AccountId, space_id: SpaceId) -> bool;
 }
 
+pub trait ReputationProvider {
+  type AccountId;
+
+  fn get_reputation(account: Self::AccountId) -> u32;
+}
+
+/// Checks whether a handle is already reserved, so that different kinds of entities
+/// (e.g. spaces and profiles) could share one namespace of handles.
+pub trait IsHandleReserved {
+  fn is_handle_reserved(handle_in_lowercase: Vec<u8>) -> bool;
+}
+
+impl IsHandleReserved for () {
+  fn is_handle_reserved(_handle_in_lowercase: Vec<u8>) -> bool {
+    false
+  }
+}
+
 pub trait PermissionChecker {
   type AccountId;
 
@@ -54,3 +74,27 @@ pub trait PermissionChecker {
     )
   }
 }
+
+/// Handler that removes the data related to a deleted space (e.g. roles, follows or posts).
+/// It's called on every block until it reports that there is nothing left to clean up,
+/// so that a cleanup of a big space can be spread across multiple blocks.
+pub trait SpaceCleanupHandler {
+  /// Remove up to `limit` items related to a deleted space.
+  /// Returns the number of removed items. If it's less than `limit`, the cleanup is finished.
+  fn cleanup_deleted_space(space_id: SpaceId, limit: u32) -> u32;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl SpaceCleanupHandler for Tuple {
+  // Variables are unused when the handler is an empty tuple `()`.
+  #[allow(unused_variables, unused_mut)]
+  fn cleanup_deleted_space(space_id: SpaceId, limit: u32) -> u32 {
+    let mut removed = 0;
+    for_tuples!( #(
+      if removed < limit {
+        removed += Tuple::cleanup_deleted_space(space_id, limit - removed);
+      }
+    )* );
+    removed
+  }
+}
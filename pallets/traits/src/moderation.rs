@@ -15,6 +15,17 @@ impl<AccountId> IsAccountBlocked<AccountId> for () {
     }
 }
 
+/// Personal blocks between accounts, not scoped to a space.
+pub trait IsAccountBlockedBy<AccountId> {
+    fn is_account_blocked_by(account: AccountId, blocker: AccountId) -> bool;
+}
+
+impl<AccountId> IsAccountBlockedBy<AccountId> for () {
+    fn is_account_blocked_by(_account: AccountId, _blocker: AccountId) -> bool {
+        false
+    }
+}
+
 pub trait IsSpaceBlocked {
     fn is_blocked_space(space_id: SpaceId, scope: SpaceId) -> bool;
     fn is_allowed_space(space_id: SpaceId, scope: SpaceId) -> bool;
@@ -262,7 +262,7 @@ impl<T: Config> Module<T> {
 
     /// Check if a handle contains only valid chars: 0-9, a-z, _.
     /// An example of a valid handle: `good_handle_123`.
-    fn is_valid_handle_char(c: u8) -> bool {
+    pub fn is_valid_handle_char(c: u8) -> bool {
         matches!(c, b'0'..=b'9' | b'a'..=b'z' | b'_')
     }
 
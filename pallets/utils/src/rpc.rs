@@ -104,6 +104,15 @@ impl<T> ShouldSkip for Option<T> {
     }
 }
 
+#[cfg(feature = "std")]
+pub fn bytes_to_string<S>(field: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+    let field_unwrapped = field.clone().unwrap_or_default();
+    // If Bytes slice is invalid, then empty string will be returned
+    serializer.serialize_str(
+        std::str::from_utf8(&field_unwrapped).unwrap_or_default()
+    )
+}
+
 #[cfg(feature = "std")]
 pub fn map_rpc_error(err: impl std::fmt::Debug) -> RpcError {
     RpcError {
@@ -55,12 +55,15 @@ use frame_system::{
 use pallet_transaction_payment::CurrencyAdapter;
 use static_assertions::const_assert;
 
+use pallet_bookmarks::{BookmarkListId, rpc::FlatBookmarkList};
+use pallet_notifications::rpc::FlatNotification;
 use pallet_permissions::SpacePermission;
-use pallet_posts::rpc::{FlatPost, FlatPostKind, RepliesByPostId};
+use pallet_posts::{Tag, rpc::{FlatPost, FlatPostKind, FlatTimeline, RepliesByPostId, TimelineCursor}};
 use pallet_profiles::rpc::FlatSocialAccount;
 use pallet_reactions::{
     ReactionId,
     ReactionKind,
+    ReactionTarget,
     rpc::FlatReaction,
 };
 use pallet_spaces::rpc::FlatSpace;
@@ -99,10 +102,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("subsocial"),
 	impl_name: create_runtime_str!("dappforce-subsocial"),
 	authoring_version: 0,
-	spec_version: 18,
+	spec_version: 19,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 3,
+	transaction_version: 4,
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -337,6 +340,28 @@ impl pallet_utils::Config for Runtime {
 	type MaxHandleLen = MaxHandleLen;
 }
 
+parameter_types! {
+	pub const MaxBlockedAccounts: u32 = 1_000;
+}
+
+impl pallet_account_blocks::Config for Runtime {
+	type Event = Event;
+	type MaxBlockedAccounts = MaxBlockedAccounts;
+}
+
+parameter_types! {
+	pub const MaxBookmarkListNameLen: u32 = 100;
+	pub const MaxBookmarkListsPerAccount: u32 = 50;
+	pub const MaxPostsPerBookmarkList: u32 = 1_000;
+}
+
+impl pallet_bookmarks::Config for Runtime {
+	type Event = Event;
+	type MaxBookmarkListNameLen = MaxBookmarkListNameLen;
+	type MaxBookmarkListsPerAccount = MaxBookmarkListsPerAccount;
+	type MaxPostsPerBookmarkList = MaxPostsPerBookmarkList;
+}
+
 use pallet_permissions::default_permissions::DefaultSpacePermissions;
 
 impl pallet_permissions::Config for Runtime {
@@ -345,51 +370,152 @@ impl pallet_permissions::Config for Runtime {
 
 parameter_types! {
   pub const MaxCommentDepth: u32 = 10;
+  pub const MaxRepliesToDelete: u32 = 100;
+  pub const MaxPinnedPostsPerSpace: u32 = 10;
+  pub const MaxTagsPerPost: u32 = 10;
+  pub const MaxTagLen: u32 = 32;
+  pub const MaxMentionsPerPost: u32 = 20;
+  pub const MaxExpiredPostsPerBlock: u32 = 100;
+  pub const MaxPollOptions: u8 = 10;
+  pub const MaxQuoteDepth: u32 = 5;
+  pub ScheduledPostDeposit: Balance = DOLLARS;
+  pub const MaxScheduledPostsPerAccount: u32 = 20;
 }
 
 impl pallet_posts::Config for Runtime {
 	type Event = Event;
 	type MaxCommentDepth = MaxCommentDepth;
+	type MaxRepliesToDelete = MaxRepliesToDelete;
+	type MaxPinnedPostsPerSpace = MaxPinnedPostsPerSpace;
+	type MaxTagsPerPost = MaxTagsPerPost;
+	type MaxTagLen = MaxTagLen;
+	type MaxMentionsPerPost = MaxMentionsPerPost;
+	type MaxExpiredPostsPerBlock = MaxExpiredPostsPerBlock;
+	type MaxPollOptions = MaxPollOptions;
+	type MaxQuoteDepth = MaxQuoteDepth;
+	type ScheduledPostDeposit = ScheduledPostDeposit;
+	type MaxScheduledPostsPerAccount = MaxScheduledPostsPerAccount;
+	type AfterPostCreated = Notifications;
 	type AfterPostUpdated = PostHistory;
-	type IsPostBlocked = ()/*Moderation*/;
+	type IsPostBlocked = Moderation;
+	type IsAccountBlockedBy = AccountBlocks;
+	type PostScores = Scores;
+	type ScheduledCall = Call;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
 }
 
 impl pallet_post_history::Config for Runtime {}
 
+parameter_types! {
+	pub const MaxNotificationsPerAccount: u32 = 500;
+}
+
+impl pallet_notifications::Config for Runtime {
+	type Event = Event;
+	type MaxNotificationsPerAccount = MaxNotificationsPerAccount;
+}
+
+parameter_types! {
+	pub const MaxFollowedAccounts: u32 = 500;
+}
+
 impl pallet_profile_follows::Config for Runtime {
 	type Event = Event;
-	type BeforeAccountFollowed = ();
-	type BeforeAccountUnfollowed = ();
+	type BeforeAccountFollowed = (AccountBlocks, Scores);
+	type BeforeAccountUnfollowed = Scores;
+	type AfterAccountFollowed = Notifications;
+	type MaxFollowedAccounts = MaxFollowedAccounts;
 }
 
 impl pallet_profiles::Config for Runtime {
 	type Event = Event;
+	type Currency = Balances;
+	type HandleDeposit = HandleDeposit;
+	type SpaceHandles = Spaces;
 	type AfterProfileUpdated = ProfileHistory;
 }
 
 impl pallet_profile_history::Config for Runtime {}
 
+parameter_types! {
+	pub const MaxEmojiLen: u32 = 32;
+	pub const MaxAllowedEmojisPerSpace: u32 = 100;
+}
+
 impl pallet_reactions::Config for Runtime {
 	type Event = Event;
+	type ReactionScores = Scores;
+	type AfterReactionCreated = Notifications;
+	type MaxEmojiLen = MaxEmojiLen;
+	type MaxAllowedEmojisPerSpace = MaxAllowedEmojisPerSpace;
 }
 
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+  pub const MaxParentSpacesToCheck: u32 = 5;
 }
 
 impl pallet_roles::Config for Runtime {
 	type Event = Event;
 	type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+	type MaxParentSpacesToCheck = MaxParentSpacesToCheck;
 	type Spaces = Spaces;
 	type SpaceFollows = SpaceFollows;
-	type IsAccountBlocked = ()/*Moderation*/;
-	type IsContentBlocked = ()/*Moderation*/;
+	type Reputation = Profiles;
+	type IsAccountBlocked = Moderation;
+	type IsContentBlocked = Moderation;
+}
+
+parameter_types! {
+  pub const FollowSpaceActionWeight: i16 = 7;
+  pub const FollowAccountActionWeight: i16 = 3;
+
+  pub const SharePostActionWeight: i16 = 7;
+  pub const UpvotePostActionWeight: i16 = 5;
+  pub const DownvotePostActionWeight: i16 = -3;
+
+  pub const CreateCommentActionWeight: i16 = 5;
+  pub const ShareCommentActionWeight: i16 = 5;
+  pub const UpvoteCommentActionWeight: i16 = 4;
+  pub const DownvoteCommentActionWeight: i16 = -2;
+
+  pub const UpvoteSpaceActionWeight: i16 = 5;
+  pub const DownvoteSpaceActionWeight: i16 = -3;
+  pub const UpvoteAccountActionWeight: i16 = 3;
+  pub const DownvoteAccountActionWeight: i16 = -2;
+}
+
+impl pallet_scores::Config for Runtime {
+	type Event = Event;
+
+	type FollowSpaceActionWeight = FollowSpaceActionWeight;
+	type FollowAccountActionWeight = FollowAccountActionWeight;
+
+	type SharePostActionWeight = SharePostActionWeight;
+	type UpvotePostActionWeight = UpvotePostActionWeight;
+	type DownvotePostActionWeight = DownvotePostActionWeight;
+
+	type CreateCommentActionWeight = CreateCommentActionWeight;
+	type ShareCommentActionWeight = ShareCommentActionWeight;
+	type UpvoteCommentActionWeight = UpvoteCommentActionWeight;
+	type DownvoteCommentActionWeight = DownvoteCommentActionWeight;
+
+	type UpvoteSpaceActionWeight = UpvoteSpaceActionWeight;
+	type DownvoteSpaceActionWeight = DownvoteSpaceActionWeight;
+	type UpvoteAccountActionWeight = UpvoteAccountActionWeight;
+	type DownvoteAccountActionWeight = DownvoteAccountActionWeight;
+}
+
+parameter_types! {
+	pub const MaxFollowedSpaces: u32 = 500;
 }
 
 impl pallet_space_follows::Config for Runtime {
 	type Event = Event;
-	type BeforeSpaceFollowed = ();
-	type BeforeSpaceUnfollowed = ();
+	type BeforeSpaceFollowed = Scores;
+	type BeforeSpaceUnfollowed = Scores;
+	type MaxFollowedSpaces = MaxFollowedSpaces;
 }
 
 impl pallet_space_ownership::Config for Runtime {
@@ -401,6 +527,10 @@ parameter_types! {
 	pub HandleDeposit: Balance = 5 * DOLLARS;
 }
 
+parameter_types! {
+	pub const MaxSpaceCleanupItemsPerBlock: u32 = 100;
+}
+
 impl pallet_spaces::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
@@ -408,9 +538,12 @@ impl pallet_spaces::Config for Runtime {
 	type SpaceFollows = SpaceFollows;
 	type BeforeSpaceCreated = SpaceFollows;
 	type AfterSpaceUpdated = SpaceHistory;
-	type IsAccountBlocked = ()/*Moderation*/;
-	type IsContentBlocked = ()/*Moderation*/;
+	type IsAccountBlocked = Moderation;
+	type IsContentBlocked = Moderation;
 	type HandleDeposit = HandleDeposit;
+	type ProfileHandles = Profiles;
+	type SpaceCleanupHandler = (Posts, Roles, SpaceFollows, Reactions);
+	type MaxSpaceCleanupItemsPerBlock = MaxSpaceCleanupItemsPerBlock;
 }
 
 parameter_types! {
@@ -439,14 +572,14 @@ impl Contains<Call> for BaseFilter {
     }
 }
 
-/*parameter_types! {
+parameter_types! {
     pub const DefaultAutoblockThreshold: u16 = 20;
 }
 
 impl pallet_moderation::Config for Runtime {
     type Event = Event;
     type DefaultAutoblockThreshold = DefaultAutoblockThreshold;
-}*/
+}
 
 impl pallet_faucets::Config for Runtime {
 	type Event = Event;
@@ -473,15 +606,15 @@ construct_runtime!(
 		// Subsocial custom pallets:
 
 		Permissions: pallet_permissions::{Pallet, Call},
-		Posts: pallet_posts::{Pallet, Call, Storage, Event<T>},
-		PostHistory: pallet_post_history::{Pallet, Storage},
-		ProfileFollows: pallet_profile_follows::{Pallet, Call, Storage, Event<T>},
-		Profiles: pallet_profiles::{Pallet, Call, Storage, Event<T>},
+		Posts: pallet_posts::{Pallet, Call, Storage, Event<T>, Config},
+		PostHistory: pallet_post_history::{Pallet, Storage, Config},
+		ProfileFollows: pallet_profile_follows::{Pallet, Call, Storage, Event<T>, Config},
+		Profiles: pallet_profiles::{Pallet, Call, Storage, Event<T>, Config},
 		ProfileHistory: pallet_profile_history::{Pallet, Storage},
-		Reactions: pallet_reactions::{Pallet, Call, Storage, Event<T>},
+		Reactions: pallet_reactions::{Pallet, Call, Storage, Event<T>, Config},
 		Roles: pallet_roles::{Pallet, Call, Storage, Event<T>},
-		SpaceFollows: pallet_space_follows::{Pallet, Call, Storage, Event<T>},
-		SpaceHistory: pallet_space_history::{Pallet, Storage},
+		SpaceFollows: pallet_space_follows::{Pallet, Call, Storage, Event<T>, Config},
+		SpaceHistory: pallet_space_history::{Pallet, Storage, Config},
 		SpaceOwnership: pallet_space_ownership::{Pallet, Call, Storage, Event<T>},
 		Spaces: pallet_spaces::{Pallet, Call, Storage, Event<T>, Config<T>},
 		Utils: pallet_utils::{Pallet, Storage, Event<T>, Config<T>},
@@ -490,7 +623,14 @@ construct_runtime!(
 
 		Faucets: pallet_faucets::{Pallet, Call, Storage, Event<T>},
 		DotsamaClaims: pallet_dotsama_claims::{Pallet, Call, Storage, Event<T>},
-		// Moderation: pallet_moderation::{Pallet, Call, Storage, Event<T>},
+
+		// New pallets are appended to the end to keep the indices of the existing ones.
+
+		Moderation: pallet_moderation::{Pallet, Call, Storage, Event<T>},
+		Scores: pallet_scores::{Pallet, Storage, Event<T>},
+		Bookmarks: pallet_bookmarks::{Pallet, Call, Storage, Event<T>},
+		Notifications: pallet_notifications::{Pallet, Call, Storage, Event<T>},
+		AccountBlocks: pallet_account_blocks::{Pallet, Call, Storage, Event<T>},
     }
 );
 
@@ -730,12 +870,16 @@ impl_runtime_apis! {
             storage_info.append(&mut SpaceHistory::storage_info());
             storage_info.append(&mut SpaceFollows::storage_info());
             storage_info.append(&mut Roles::storage_info());
+            storage_info.append(&mut Scores::storage_info());
             storage_info.append(&mut Reactions::storage_info());
             storage_info.append(&mut ProfileHistory::storage_info());
             storage_info.append(&mut Profiles::storage_info());
             storage_info.append(&mut ProfileFollows::storage_info());
             storage_info.append(&mut PostHistory::storage_info());
             storage_info.append(&mut Posts::storage_info());
+            storage_info.append(&mut Bookmarks::storage_info());
+            storage_info.append(&mut Notifications::storage_info());
+            storage_info.append(&mut AccountBlocks::storage_info());
             storage_info.append(&mut Utility::storage_info());
             storage_info.append(&mut Scheduler::storage_info());
             storage_info.append(&mut Sudo::storage_info());
@@ -784,7 +928,7 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl space_follows_runtime_api::SpaceFollowsApi<Block, AccountId> for Runtime
+	impl space_follows_runtime_api::SpaceFollowsApi<Block, AccountId, BlockNumber> for Runtime
     {
     	fn get_space_ids_followed_by_account(account: AccountId) -> Vec<SpaceId> {
     		SpaceFollows::get_space_ids_followed_by_account(account)
@@ -793,6 +937,10 @@ impl_runtime_apis! {
     	fn filter_followed_space_ids(account: AccountId, space_ids: Vec<SpaceId>) -> Vec<SpaceId> {
     		SpaceFollows::filter_followed_space_ids(account, space_ids)
     	}
+
+    	fn get_space_followers(space_id: SpaceId, offset: u64, limit: u16) -> Vec<FlatSocialAccount<AccountId, BlockNumber>> {
+    		SpaceFollows::get_space_followers(space_id, offset, limit)
+    	}
     }
 
 	impl spaces_runtime_api::SpacesApi<Block, AccountId, BlockNumber> for Runtime
@@ -834,6 +982,50 @@ impl_runtime_apis! {
         }
     }
 
+    impl account_blocks_runtime_api::AccountBlocksApi<Block, AccountId> for Runtime
+    {
+		fn get_blocked_accounts(account: AccountId) -> Vec<AccountId> {
+			AccountBlocks::get_blocked_accounts(account)
+		}
+
+		fn filter_blocked_accounts(account: AccountId, maybe_blocked: Vec<AccountId>) -> Vec<AccountId> {
+			AccountBlocks::filter_blocked_accounts(account, maybe_blocked)
+		}
+    }
+
+    impl bookmarks_runtime_api::BookmarksApi<Block, AccountId, BlockNumber> for Runtime
+    {
+		fn get_bookmark_lists_by_account(
+			account: AccountId,
+			only_public: bool,
+		) -> Vec<FlatBookmarkList<AccountId, BlockNumber>> {
+			Bookmarks::get_bookmark_lists_by_account(account, only_public)
+		}
+
+		fn get_bookmarked_posts(
+			list_id: BookmarkListId,
+			offset: u64,
+			limit: u16,
+		) -> Vec<FlatPost<AccountId, BlockNumber>> {
+			Bookmarks::get_bookmarked_posts(list_id, offset, limit)
+		}
+    }
+
+    impl notifications_runtime_api::NotificationsApi<Block, AccountId, BlockNumber> for Runtime
+    {
+		fn get_notifications(
+			account: AccountId,
+			offset: u64,
+			limit: u16,
+		) -> Vec<FlatNotification<AccountId, BlockNumber>> {
+			Notifications::get_notifications(account, offset, limit)
+		}
+
+		fn get_unread_notifications_count(account: AccountId) -> u32 {
+			Notifications::get_unread_notifications_count(account)
+		}
+    }
+
     impl posts_runtime_api::PostsApi<Block, AccountId, BlockNumber> for Runtime
     {
 		fn get_posts_by_ids(post_ids: Vec<PostId>, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>> {
@@ -844,8 +1036,13 @@ impl_runtime_apis! {
 			Posts::get_public_posts(kind_filter, start_id, limit)
 		}
 
-		fn get_public_posts_by_space_id(space_id: SpaceId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>> {
-			Posts::get_public_posts_by_space_id(space_id, offset, limit)
+		fn get_public_posts_by_space_id(
+			space_id: SpaceId,
+			offset: u64,
+			limit: u16,
+			pinned_first: bool,
+		) -> Vec<FlatPost<AccountId, BlockNumber>> {
+			Posts::get_public_posts_by_space_id(space_id, offset, limit, pinned_first)
 		}
 
 		fn get_unlisted_posts_by_space_id(space_id: SpaceId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>> {
@@ -880,16 +1077,52 @@ impl_runtime_apis! {
 			Posts::get_next_post_id()
 		}
 
-		fn get_feed(account: AccountId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>> {
-			Posts::get_feed(account, offset, limit)
+		fn get_post_ids_by_account(account: AccountId, kind_filter: Vec<FlatPostKind>, offset: u64, limit: u16) -> Vec<PostId> {
+			Posts::get_post_ids_by_account(account, kind_filter, offset, limit)
+		}
+
+		fn get_posts_by_account(account: AccountId, kind_filter: Vec<FlatPostKind>, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>> {
+			Posts::get_posts_by_account(account, kind_filter, offset, limit)
+		}
+
+		fn get_sharing_post_ids(post_id: PostId, offset: u64, limit: u16) -> Vec<PostId> {
+			Posts::get_sharing_post_ids(post_id, offset, limit)
+		}
+
+		fn get_sharing_posts(post_id: PostId, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>> {
+			Posts::get_sharing_posts(post_id, offset, limit)
+		}
+
+		fn get_feed(account: AccountId, kind_filter: Vec<FlatPostKind>, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>> {
+			Posts::get_feed(account, kind_filter, offset, limit)
+		}
+
+		fn get_timeline(account: AccountId, cursor: Option<TimelineCursor<AccountId>>, limit: u16) -> FlatTimeline<AccountId, BlockNumber> {
+			Posts::get_timeline(account, cursor, limit)
+		}
+
+		fn get_post_ids_by_tag(tag: Tag, offset: u64, limit: u16) -> Vec<PostId> {
+			Posts::get_post_ids_by_tag(tag, offset, limit)
+		}
+
+		fn get_posts_by_tag(tag: Tag, offset: u64, limit: u16) -> Vec<FlatPost<AccountId, BlockNumber>> {
+			Posts::get_posts_by_tag(tag, offset, limit)
 		}
     }
 
-	impl profile_follows_runtime_api::ProfileFollowsApi<Block, AccountId> for Runtime
+	impl profile_follows_runtime_api::ProfileFollowsApi<Block, AccountId, BlockNumber> for Runtime
     {
     	fn filter_followed_accounts(account: AccountId, maybe_following: Vec<AccountId>) -> Vec<AccountId> {
     		ProfileFollows::filter_followed_accounts(account, maybe_following)
     	}
+
+    	fn get_account_followers(account: AccountId, offset: u64, limit: u16) -> Vec<FlatSocialAccount<AccountId, BlockNumber>> {
+    		ProfileFollows::get_account_followers(account, offset, limit)
+    	}
+
+    	fn get_accounts_followed_by_account(account: AccountId, offset: u64, limit: u16) -> Vec<FlatSocialAccount<AccountId, BlockNumber>> {
+    		ProfileFollows::get_accounts_followed_by_account(account, offset, limit)
+    	}
     }
 
 	impl profiles_runtime_api::ProfilesApi<Block, AccountId, BlockNumber> for Runtime
@@ -899,6 +1132,14 @@ impl_runtime_apis! {
         ) -> Vec<FlatSocialAccount<AccountId, BlockNumber>> {
         	Profiles::get_social_accounts_by_ids(account_ids)
         }
+
+		fn get_account_id_by_handle(handle: Vec<u8>) -> Option<AccountId> {
+			Profiles::get_account_id_by_handle(handle)
+		}
+
+		fn get_social_account_by_handle(handle: Vec<u8>) -> Option<FlatSocialAccount<AccountId, BlockNumber>> {
+			Profiles::get_social_account_by_handle(handle)
+		}
 	}
 
     impl reactions_runtime_api::ReactionsApi<Block, AccountId, BlockNumber> for Runtime
@@ -915,12 +1156,33 @@ impl_runtime_apis! {
 			Reactions::get_reactions_by_post_id(post_id, limit, offset)
 		}
 
+		fn get_reactions_by_target(
+			target: ReactionTarget<AccountId>,
+			limit: u64,
+			offset: u64
+		) -> Vec<FlatReaction<AccountId, BlockNumber>> {
+			Reactions::get_reactions_by_target(target, limit, offset)
+		}
+
 		fn get_reaction_kinds_by_post_ids_and_reactor(
 			post_ids: Vec<PostId>,
         	reactor: AccountId,
 		) -> BTreeMap<PostId, ReactionKind> {
 			Reactions::get_reaction_kinds_by_post_ids_and_reactor(post_ids, reactor)
 		}
+
+		fn get_reaction_kinds_by_targets_and_reactor(
+			targets: Vec<ReactionTarget<AccountId>>,
+			reactor: AccountId,
+		) -> Vec<(ReactionTarget<AccountId>, ReactionKind)> {
+			Reactions::get_reaction_kinds_by_targets_and_reactor(targets, reactor)
+		}
+
+		fn get_reaction_counts_by_post_ids(
+			post_ids: Vec<PostId>,
+		) -> BTreeMap<PostId, Vec<(ReactionKind, u32)>> {
+			Reactions::get_reaction_counts_by_post_ids(post_ids)
+		}
     }
 
 	impl roles_runtime_api::RolesApi<Block, AccountId> for Runtime